@@ -15,16 +15,18 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, ListItem, Paragraph},
+    widgets::{Block, Borders, Gauge, ListItem, Paragraph},
     Frame, Terminal,
 };
+use sha2::{Digest, Sha256};
 use std::{
     io::{self, Write},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        mpsc::{self, Receiver},
         Arc,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::task;
 
@@ -48,6 +50,10 @@ struct CliArgs {
     /// How large in MB to attempt usage for the RAM benchmark
     #[arg(long, default_value_t = 0)]
     ram_mb: usize,
+
+    /// Target CPU utilization (percent) for the stress test
+    #[arg(long, default_value_t = 50, value_parser = clap::value_parser!(u32).range(1..=100))]
+    target_load: u32,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,6 +62,9 @@ enum Benchmark {
     Cpu,
     Ram,
     Combined,
+    FixedWork,
+    Stress,
+    Latency,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -63,14 +72,56 @@ enum Screen {
     Welcome,
     Menu,
     BenchInProgress,
+    Results,
     Exit,
 }
 
+/// Outcome of the fixed-work CPU benchmark.
+#[derive(Debug, Clone)]
+struct FixedWorkResult {
+    elapsed: Duration,
+    total_bytes: u64,
+    checksum: String,
+}
+
+/// Average access latency measured at one working-set size.
+#[derive(Debug, Clone)]
+struct LatencyPoint {
+    working_set_bytes: usize,
+    nanos_per_access: f64,
+}
+
+/// Result of a benchmark that runs to completion, shown on the Results screen.
+#[derive(Debug, Clone)]
+enum BenchResult {
+    FixedWork(FixedWorkResult),
+    Latency(Vec<LatencyPoint>),
+}
+
 struct App {
     screen: Screen,
     active_bench: Benchmark,
     status_message: String,
     cli_ram_mb: usize,
+    /// Receives the result of a running fixed-work or latency benchmark
+    pending_result: Option<Receiver<Option<BenchResult>>>,
+    /// The most recent completed result, shown on the Results screen
+    last_result: Option<BenchResult>,
+    /// Duty-cycle controller for the target-load stress test
+    stress: StressControl,
+}
+
+/// Shared state for the target-load stress test. Workers read `duty`; the UI
+/// loop measures utilization and nudges `duty` toward `target_percent`.
+struct StressControl {
+    target_percent: u32,
+    /// Busy fraction of each work period, in per-mille (0..=1000)
+    duty: Arc<AtomicU32>,
+    /// Most recent measured utilization (percent); `None` until the first sample
+    measured: Option<f64>,
+    /// Previous (busy, total) jiffies from `/proc/stat`
+    last_jiffies: Option<(u64, u64)>,
+    last_sample: Instant,
 }
 
 /// RAII guard for raw mode
@@ -113,6 +164,15 @@ async fn main() -> Result<()> {
         active_bench: Benchmark::None,
         status_message: String::new(),
         cli_ram_mb: args.ram_mb,
+        pending_result: None,
+        last_result: None,
+        stress: StressControl {
+            target_percent: args.target_load,
+            duty: Arc::new(AtomicU32::new(args.target_load * 10)),
+            measured: None,
+            last_jiffies: None,
+            last_sample: Instant::now(),
+        },
     };
 
     // Run main TUI loop
@@ -179,6 +239,14 @@ async fn run_app(
             }
         }
 
+        // Collect a finished fixed-work or latency result, if any
+        poll_bench_result(&mut app);
+
+        // Steer the stress-test duty cycle toward its target
+        if app.active_bench == Benchmark::Stress {
+            update_stress_control(&mut app);
+        }
+
         // Exit condition
         if app.screen == Screen::Exit {
             break;
@@ -211,8 +279,12 @@ fn draw_ui(frame: &mut Frame, app: &App) {
     // Main content
     match app.screen {
         Screen::Welcome => draw_welcome(frame, chunks[1]),
-        Screen::Menu => draw_menu(frame, chunks[1]),
+        Screen::Menu => draw_menu(frame, app, chunks[1]),
+        Screen::BenchInProgress if app.active_bench == Benchmark::Stress => {
+            draw_stress_in_progress(frame, app, chunks[1])
+        }
         Screen::BenchInProgress => draw_bench_in_progress(frame, app, chunks[1]),
+        Screen::Results => draw_results(frame, app, chunks[1]),
         Screen::Exit => {}
     }
 
@@ -264,12 +336,19 @@ fn draw_welcome(frame: &mut Frame, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
-fn draw_menu(frame: &mut Frame, area: Rect) {
+fn draw_menu(frame: &mut Frame, app: &App, area: Rect) {
+    let stress = format!(
+        "5) Stress Test at Target Load ({}%)",
+        app.stress.target_percent
+    );
     let opts = [
         "1) CPU Benchmark",
         "2) RAM Benchmark",
         "3) Combined CPU+RAM",
-        "4) Exit",
+        "4) Fixed-Work CPU Score (SHA-256)",
+        stress.as_str(),
+        "6) Memory Latency (pointer chasing)",
+        "7) Exit",
     ];
     let items: Vec<ListItem> = opts.iter().map(|&s| ListItem::new(Span::raw(s))).collect();
 
@@ -287,6 +366,9 @@ fn draw_bench_in_progress(frame: &mut Frame, app: &App, area: Rect) {
         Benchmark::Cpu => "CPU Benchmark Running (Esc=stop)",
         Benchmark::Ram => "RAM Benchmark Running (Esc=stop)",
         Benchmark::Combined => "Combined CPU+RAM Running (Esc=stop)",
+        Benchmark::FixedWork => "Fixed-Work SHA-256 Running (Esc=cancel)",
+        Benchmark::Stress => "Stress Test Running (Esc=stop)",
+        Benchmark::Latency => "Memory Latency Running (Esc=cancel)",
         Benchmark::None => "No active benchmark...",
     };
     let block = Block::default()
@@ -300,6 +382,100 @@ fn draw_bench_in_progress(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+/// Live view of the stress test: target vs achieved utilization.
+fn draw_stress_in_progress(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title("Stress Test In Progress (+/-=adjust target, Esc=stop)")
+        .borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = ratatui::layout::Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // figures
+            Constraint::Length(3), // target gauge
+            Constraint::Length(3), // achieved gauge
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    let stress = &app.stress;
+    let duty = stress.duty.load(Ordering::Relaxed) as f64 / 10.0;
+    let achieved = match stress.measured {
+        Some(m) => format!("{m:.1}%"),
+        None if cfg!(target_os = "linux") => "measuring...".to_string(),
+        None => "unavailable (open-loop duty cycle)".to_string(),
+    };
+    let lines = vec![
+        Line::from(format!(
+            "Workers: {} (one per logical CPU)",
+            num_cpus::get()
+        )),
+        Line::from(format!("Target utilization: {}%", stress.target_percent)),
+        Line::from(Span::styled(
+            format!("Achieved utilization: {achieved}"),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!("Worker duty cycle: {duty:.1}%")),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        chunks[0],
+    );
+
+    let target_gauge = Gauge::default()
+        .block(Block::default().title(" Target ").borders(Borders::ALL))
+        .gauge_style(Style::default().fg(Color::Yellow))
+        .percent(stress.target_percent.min(100) as u16);
+    frame.render_widget(target_gauge, chunks[1]);
+
+    let measured = stress.measured.unwrap_or(0.0).clamp(0.0, 100.0);
+    let achieved_gauge = Gauge::default()
+        .block(Block::default().title(" Achieved ").borders(Borders::ALL))
+        .gauge_style(Style::default().fg(Color::Magenta))
+        .ratio(measured / 100.0)
+        .label(format!("{measured:.1}%"));
+    frame.render_widget(achieved_gauge, chunks[2]);
+}
+
+fn draw_results(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .title("Benchmark Results (Enter/Esc=back)")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+
+    let lines = match &app.last_result {
+        Some(BenchResult::Latency(points)) => latency_result_lines(points),
+        Some(BenchResult::FixedWork(r)) => {
+            let secs = r.elapsed.as_secs_f64();
+            let mib = r.total_bytes as f64 / (1024.0 * 1024.0);
+            vec![
+                Line::from(format!(
+                    "Workload: {FIXED_WORK_ROUNDS} x SHA-256 over {} MiB ({mib:.0} MiB total)",
+                    FIXED_WORK_INPUT_BYTES / (1024 * 1024)
+                )),
+                Line::from(format!("Wall-clock time: {secs:.3} s")),
+                Line::from(Span::styled(
+                    format!("Score: {:.1} MiB/s", mib / secs.max(f64::EPSILON)),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(format!("Checksum: {}", r.checksum)),
+            ]
+        }
+        None => vec![Line::from("No results yet.")],
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center);
+    frame.render_widget(paragraph, area);
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Input Handling
 ////////////////////////////////////////////////////////////////////////////////
@@ -319,14 +495,31 @@ async fn handle_key_event(key: KeyEvent, app: &mut App, run_flag: &Arc<AtomicBoo
             KeyCode::Char('1') => start_benchmark(app, Benchmark::Cpu, run_flag).await?,
             KeyCode::Char('2') => start_benchmark(app, Benchmark::Ram, run_flag).await?,
             KeyCode::Char('3') => start_benchmark(app, Benchmark::Combined, run_flag).await?,
-            KeyCode::Char('4') => app.screen = Screen::Exit,
+            KeyCode::Char('4') => start_benchmark(app, Benchmark::FixedWork, run_flag).await?,
+            KeyCode::Char('5') => start_benchmark(app, Benchmark::Stress, run_flag).await?,
+            KeyCode::Char('6') => start_benchmark(app, Benchmark::Latency, run_flag).await?,
+            KeyCode::Char('7') => app.screen = Screen::Exit,
             _ => {}
         },
         Screen::BenchInProgress => {
+            if app.active_bench == Benchmark::Stress {
+                match key.code {
+                    KeyCode::Char('+') | KeyCode::Char('=') => adjust_stress_target(app, 5),
+                    KeyCode::Char('-') => adjust_stress_target(app, -5),
+                    _ => {}
+                }
+            }
             if key.code == KeyCode::Esc {
                 // Stop the current benchmark
                 run_flag.store(false, Ordering::SeqCst);
                 app.active_bench = Benchmark::None;
+                app.pending_result = None;
+                app.screen = Screen::Menu;
+                app.status_message.clear();
+            }
+        }
+        Screen::Results => {
+            if matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
                 app.screen = Screen::Menu;
                 app.status_message.clear();
             }
@@ -363,6 +556,22 @@ async fn start_benchmark(
             spawn_cpu_bench(run_flag.clone()).await;
             spawn_ram_bench(run_flag.clone(), app.cli_ram_mb).await;
         }
+        Benchmark::FixedWork => {
+            app.pending_result = Some(spawn_fixed_work_bench(run_flag.clone()));
+        }
+        Benchmark::Latency => {
+            app.pending_result = Some(spawn_latency_bench(run_flag.clone()));
+        }
+        Benchmark::Stress => {
+            let stress = &mut app.stress;
+            stress
+                .duty
+                .store(stress.target_percent * 10, Ordering::SeqCst);
+            stress.measured = None;
+            stress.last_jiffies = read_cpu_jiffies();
+            stress.last_sample = Instant::now();
+            spawn_stress_workers(run_flag.clone(), stress.duty.clone());
+        }
         Benchmark::None => {}
     }
 
@@ -402,3 +611,317 @@ async fn spawn_ram_bench(run_flag: Arc<AtomicBool>, cli_mb: usize) {
         }
     });
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// Target-Load Stress Test
+////////////////////////////////////////////////////////////////////////////////
+
+/// Length of one busy+sleep cycle in each stress worker.
+const STRESS_PERIOD: Duration = Duration::from_millis(100);
+/// How often utilization is sampled and the duty cycle corrected.
+const STRESS_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+/// Proportional gain of the duty-cycle controller (per-mille duty per percent of error).
+const STRESS_GAIN: f64 = 5.0;
+
+/// Spawns one OS thread per logical CPU, each busy-spinning for `duty` of
+/// every `STRESS_PERIOD` and sleeping for the rest.
+fn spawn_stress_workers(run_flag: Arc<AtomicBool>, duty: Arc<AtomicU32>) {
+    for _ in 0..num_cpus::get() {
+        let r = run_flag.clone();
+        let duty = duty.clone();
+        std::thread::spawn(move || {
+            while r.load(Ordering::SeqCst) {
+                let cycle_start = Instant::now();
+                let busy = STRESS_PERIOD.mul_f64(duty.load(Ordering::Relaxed) as f64 / 1000.0);
+                while cycle_start.elapsed() < busy {
+                    std::hint::black_box(2.0_f64.sqrt().sin().cos().tan());
+                }
+                if let Some(rest) = STRESS_PERIOD.checked_sub(cycle_start.elapsed()) {
+                    std::thread::sleep(rest);
+                }
+            }
+        });
+    }
+}
+
+fn adjust_stress_target(app: &mut App, delta: i32) {
+    let stress = &mut app.stress;
+    stress.target_percent = stress
+        .target_percent
+        .saturating_add_signed(delta)
+        .clamp(5, 100);
+    app.status_message = format!("Stress target set to {}%", stress.target_percent);
+}
+
+/// Samples system-wide utilization and nudges the workers' duty cycle so the
+/// measured load converges on the target (simple proportional control).
+fn update_stress_control(app: &mut App) {
+    let stress = &mut app.stress;
+    if stress.last_sample.elapsed() < STRESS_SAMPLE_INTERVAL {
+        return;
+    }
+    stress.last_sample = Instant::now();
+
+    let Some((busy, total)) = read_cpu_jiffies() else {
+        // No measurement available: run open-loop at the target duty
+        stress
+            .duty
+            .store(stress.target_percent * 10, Ordering::Relaxed);
+        return;
+    };
+    if let Some((prev_busy, prev_total)) = stress.last_jiffies {
+        let total_delta = total.saturating_sub(prev_total);
+        if total_delta > 0 {
+            let measured = busy.saturating_sub(prev_busy) as f64 * 100.0 / total_delta as f64;
+            stress.measured = Some(measured);
+
+            let error = stress.target_percent as f64 - measured;
+            let duty = stress.duty.load(Ordering::Relaxed) as f64 + STRESS_GAIN * error;
+            stress
+                .duty
+                .store(duty.clamp(0.0, 1000.0) as u32, Ordering::Relaxed);
+            app.status_message = format!(
+                "Stress: target {}%, achieved {measured:.1}%",
+                stress.target_percent
+            );
+        }
+    }
+    stress.last_jiffies = Some((busy, total));
+}
+
+/// Returns aggregate (busy, total) CPU jiffies from the first line of `/proc/stat`.
+#[cfg(target_os = "linux")]
+fn read_cpu_jiffies() -> Option<(u64, u64)> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let fields: Vec<u64> = stat
+        .lines()
+        .next()?
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|f| f.parse().ok())
+        .collect();
+    // user nice system idle iowait irq softirq steal ...
+    let idle = fields.get(3)? + fields.get(4).unwrap_or(&0);
+    let total: u64 = fields.iter().take(8).sum();
+    Some((total - idle, total))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_cpu_jiffies() -> Option<(u64, u64)> {
+    None
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Fixed-Work CPU Benchmark
+////////////////////////////////////////////////////////////////////////////////
+
+/// Size of the fixed input buffer hashed each round.
+const FIXED_WORK_INPUT_BYTES: usize = 1024 * 1024;
+/// Number of chained SHA-256 rounds over the input buffer.
+const FIXED_WORK_ROUNDS: u32 = 256;
+/// Seed for the input buffer, so every run hashes identical bytes.
+const FIXED_WORK_SEED: u64 = 0x5EED_BE4C_0000_0001;
+
+/// Runs the fixed SHA-256 workload on a blocking thread and reports back over a channel.
+/// Sends `None` if the run was cancelled via `run_flag`.
+fn spawn_fixed_work_bench(run_flag: Arc<AtomicBool>) -> Receiver<Option<BenchResult>> {
+    let (tx, rx) = mpsc::channel();
+    task::spawn_blocking(move || {
+        let _ = tx.send(run_fixed_work(&run_flag).map(BenchResult::FixedWork));
+    });
+    rx
+}
+
+/// Hashes a seeded 1 MiB buffer `FIXED_WORK_ROUNDS` times, chaining each digest
+/// into the next round so the work can't be skipped or reordered.
+fn run_fixed_work(run_flag: &AtomicBool) -> Option<FixedWorkResult> {
+    let input = seeded_input(FIXED_WORK_SEED, FIXED_WORK_INPUT_BYTES);
+    let mut digest = [0u8; 32];
+
+    let start = Instant::now();
+    for _ in 0..FIXED_WORK_ROUNDS {
+        if !run_flag.load(Ordering::SeqCst) {
+            return None;
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(digest);
+        hasher.update(&input);
+        digest.copy_from_slice(&hasher.finalize());
+    }
+    let elapsed = start.elapsed();
+
+    Some(FixedWorkResult {
+        elapsed,
+        total_bytes: FIXED_WORK_INPUT_BYTES as u64 * u64::from(FIXED_WORK_ROUNDS),
+        checksum: digest[..8].iter().map(|b| format!("{b:02x}")).collect(),
+    })
+}
+
+/// Fills a buffer from a seeded xorshift64 generator (identical on every machine).
+fn seeded_input(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    let mut buf = Vec::with_capacity(len);
+    while buf.len() < len {
+        buf.extend_from_slice(&xorshift64(&mut state).to_le_bytes());
+    }
+    buf.truncate(len);
+    buf
+}
+
+/// Advances a xorshift64 generator and returns the new state.
+fn xorshift64(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+/// Moves to the Results screen once a fixed-work or latency benchmark reports back.
+fn poll_bench_result(app: &mut App) {
+    let Some(rx) = &app.pending_result else {
+        return;
+    };
+    match rx.try_recv() {
+        Ok(Some(result)) => {
+            app.status_message = match &result {
+                BenchResult::FixedWork(r) => format!(
+                    "Fixed-work benchmark finished in {:.3} s",
+                    r.elapsed.as_secs_f64()
+                ),
+                BenchResult::Latency(points) => {
+                    format!("Memory latency measured at {} sizes", points.len())
+                }
+            };
+            app.last_result = Some(result);
+            app.pending_result = None;
+            app.active_bench = Benchmark::None;
+            app.screen = Screen::Results;
+        }
+        Ok(None) | Err(mpsc::TryRecvError::Disconnected) => {
+            app.pending_result = None;
+        }
+        Err(mpsc::TryRecvError::Empty) => {}
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Memory Latency (pointer chasing)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Working-set sizes measured, from well inside L1 to well beyond L3.
+const LATENCY_WORKING_SETS: [usize; 9] = [
+    16 << 10,
+    64 << 10,
+    256 << 10,
+    1 << 20,
+    4 << 20,
+    16 << 20,
+    32 << 20,
+    64 << 20,
+    256 << 20,
+];
+/// One chain node per cache line, so every hop touches a new line.
+const LATENCY_STRIDE_BYTES: usize = 64;
+/// Dependent loads timed at each working-set size.
+const LATENCY_ACCESSES: usize = 4_000_000;
+/// Seed for the chain order, so every run walks the same permutation.
+const LATENCY_SEED: u64 = 0x5EED_1A7E_0000_0001;
+
+/// Runs the pointer-chasing benchmark on a blocking thread and reports back
+/// over a channel. Sends `None` if the run was cancelled via `run_flag`.
+fn spawn_latency_bench(run_flag: Arc<AtomicBool>) -> Receiver<Option<BenchResult>> {
+    let (tx, rx) = mpsc::channel();
+    task::spawn_blocking(move || {
+        let points = LATENCY_WORKING_SETS
+            .iter()
+            .map(|&size| measure_latency(size, &run_flag))
+            .collect::<Option<Vec<_>>>();
+        let _ = tx.send(points.map(BenchResult::Latency));
+    });
+    rx
+}
+
+/// Builds a random cyclic pointer chain over `working_set_bytes` and times how
+/// long each dependent load takes. Because every address depends on the
+/// previous load and the order is random, prefetchers can't hide the latency.
+fn measure_latency(working_set_bytes: usize, run_flag: &AtomicBool) -> Option<LatencyPoint> {
+    let slots_per_node = LATENCY_STRIDE_BYTES / std::mem::size_of::<usize>();
+    let nodes = working_set_bytes / LATENCY_STRIDE_BYTES;
+
+    // Sattolo's algorithm: a random permutation that forms a single cycle,
+    // so the chase visits every node before repeating
+    let mut order: Vec<usize> = (0..nodes).collect();
+    let mut state = LATENCY_SEED;
+    for i in (1..nodes).rev() {
+        let j = (xorshift64(&mut state) % i as u64) as usize;
+        order.swap(i, j);
+    }
+    let mut chain = vec![0usize; nodes * slots_per_node];
+    for (node, &next) in order.iter().enumerate() {
+        chain[node * slots_per_node] = next * slots_per_node;
+    }
+
+    // One full lap to warm caches and TLBs up to the working-set size
+    let mut index = 0;
+    for _ in 0..nodes {
+        index = chain[index];
+    }
+
+    const CHECK_EVERY: usize = 1 << 16;
+    let start = Instant::now();
+    for _ in 0..LATENCY_ACCESSES / CHECK_EVERY {
+        if !run_flag.load(Ordering::SeqCst) {
+            return None;
+        }
+        for _ in 0..CHECK_EVERY {
+            index = chain[index];
+        }
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(index);
+
+    let accesses = LATENCY_ACCESSES / CHECK_EVERY * CHECK_EVERY;
+    Some(LatencyPoint {
+        working_set_bytes,
+        nanos_per_access: elapsed.as_nanos() as f64 / accesses as f64,
+    })
+}
+
+/// Results table: one row per working-set size, with the jump from the
+/// previous size so cache-level transitions stand out.
+fn latency_result_lines(points: &[LatencyPoint]) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!(
+            "Random pointer chase, {LATENCY_ACCESSES} dependent loads per size, {LATENCY_STRIDE_BYTES}-byte stride"
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("{:>12}  {:>12}  {:>8}", "Working set", "Latency", "vs prev"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    let mut previous: Option<f64> = None;
+    for p in points {
+        let size = if p.working_set_bytes >= 1 << 20 {
+            format!("{} MiB", p.working_set_bytes >> 20)
+        } else {
+            format!("{} KiB", p.working_set_bytes >> 10)
+        };
+        let ratio = previous.map_or(1.0, |prev| p.nanos_per_access / prev.max(f64::EPSILON));
+        // A latency jump of 50%+ usually means the set just spilled out of a cache level
+        let style = if ratio >= 1.5 {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{:>12}  {:>9.2} ns  {:>7.2}x",
+                size, p.nanos_per_access, ratio
+            ),
+            style,
+        )));
+        previous = Some(p.nanos_per_access);
+    }
+    lines
+}
@@ -6,27 +6,32 @@
 // Imports
 ////////////////////////////////////////////////////////////////////////////////
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use chrono::{DateTime, Local};
 use clap::Parser;
 use crossterm::{
-    cursor::MoveTo,
-    event::{self, Event, KeyCode, KeyModifiers},
+    cursor::{MoveTo, MoveToColumn},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
     execute,
+    style::Stylize,
     terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
 };
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame, Terminal,
 };
 use std::{
+    collections::HashSet,
     fs, io,
-    io::Write,
+    io::{Read, Write},
     path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
     time::Duration,
 };
 
@@ -55,6 +60,18 @@ struct CliArgs {
     /// Whether to enable verbose mode
     #[arg(long, short)]
     verbose: bool,
+
+    /// Follow symbolic links when recursing into directories (toggle with 'l')
+    #[arg(long)]
+    follow_symlinks: bool,
+
+    /// Only log what file operations would do, without touching the filesystem (toggle with 'd')
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Draw the tree view with ASCII (`|--`, `` `-- ``) instead of Unicode box characters
+    #[arg(long)]
+    ascii_tree: bool,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -85,16 +102,60 @@ impl Drop for RawModeGuard {
 // Application State
 ////////////////////////////////////////////////////////////////////////////////
 
+/// The log keeps only this many of the most recent lines.
+const LOG_CAPACITY: usize = 5000;
+
 /// Tracks the current state of the File Commander TUI application.
 struct AppState {
     /// The current working directory
     current_dir: PathBuf,
     /// The scrolling log displayed at the bottom
     log_lines: Vec<String>,
+    /// First visible log line while scrolled back; `None` follows the newest output
+    log_scroll: Option<usize>,
+    /// Log lines that fit in the log pane (updated on every draw)
+    log_page_rows: usize,
     /// The index of the currently highlighted menu item
     menu_index: usize,
     /// The list of menu items
     menu_items: Vec<&'static str>,
+    /// Whether hidden files are included in the (live) listing
+    show_hidden: bool,
+    /// Order of the (live) listing
+    listing_sort: ListingSort,
+    /// Log range `(start, end)` occupied by the most recent listing
+    listing_range: Option<(usize, usize)>,
+    /// Whether watch mode (live listing) is enabled
+    watch_mode: bool,
+    /// Active filesystem watcher; `None` when unavailable or watch mode is off
+    watcher: Option<RecommendedWatcher>,
+    /// Channel receiving filesystem events from the watcher
+    watch_rx: Option<Receiver<notify::Result<notify::Event>>>,
+    /// Description of the most recent change seen in watch mode
+    last_change: Option<String>,
+    /// Whether recursive operations descend into symlinked directories
+    follow_symlinks: bool,
+    /// Recently visited directories, most recent first (persisted)
+    recent_dirs: Vec<PathBuf>,
+    /// Whether file operations only log what they would do
+    dry_run: bool,
+    /// Draw the tree view with ASCII instead of box-drawing characters
+    ascii_tree: bool,
+    /// System clipboard, opened on first use. Kept alive because on X11 the
+    /// copied text disappears when the owning handle is dropped.
+    clipboard: Option<arboard::Clipboard>,
+    /// Reversible operations, most recent last (see `undo_last_operation`)
+    undo_stack: Vec<Operation>,
+    /// Collects operations into one undo step while a multi-file action runs
+    undo_group: Option<Vec<Operation>>,
+    /// Whether the middle pane lists `current_dir` for marking entries instead of the menu
+    select_mode: bool,
+    /// Entries shown in selection mode (see `selectable_entries`)
+    select_entries: Vec<PathBuf>,
+    /// Highlighted row of `select_entries`
+    select_index: usize,
+    /// Marked paths; copy, move, and delete act on all of them when any are marked
+    selected: HashSet<PathBuf>,
 }
 
 impl AppState {
@@ -102,23 +163,136 @@ impl AppState {
         Ok(Self {
             current_dir: std::env::current_dir().context("Failed to get current directory")?,
             log_lines: Vec::new(),
+            log_scroll: None,
+            log_page_rows: 1,
             menu_index: 0,
             menu_items: vec![
                 "1) Change directory (cd)",
                 "2) List contents (ls)",
                 "3) Show directory tree (tree)",
                 "4) Show directory info",
-                "5) Create file (touch)",
+                "5) Create file (empty/template/paste)",
                 "6) Create directory (mkdir)",
                 "7) Copy file/directory (cp)",
                 "8) Move/rename file/directory (mv)",
                 "9) Delete file/directory (rm)",
                 "10) Duplicate file/directory",
                 "11) Organize files (by extension/date/size)",
-                "12) Exit",
+                "12) Toggle watch mode (live listing)",
+                "13) Create symbolic link (ln -s)",
+                "14) Batch rename (find/replace, prefix/suffix, numbering)",
+                "15) Edit text file",
+                "16) Change permissions (chmod)",
+                "17) Open with default application",
+                "18) View operation log",
+                "19) Find empty directories (cleanup)",
+                "20) Find large files (cleanup)",
+                "21) Find files (by name)",
+                "22) Undo last operation",
+                "23) Exit",
             ],
+            show_hidden: false,
+            listing_sort: ListingSort::default(),
+            listing_range: None,
+            watch_mode: false,
+            watcher: None,
+            watch_rx: None,
+            last_change: None,
+            follow_symlinks: false,
+            recent_dirs: load_recent_dirs(),
+            dry_run: false,
+            ascii_tree: false,
+            clipboard: None,
+            undo_stack: Vec::new(),
+            undo_group: None,
+            select_mode: false,
+            select_entries: Vec::new(),
+            select_index: 0,
+            selected: HashSet::new(),
         })
     }
+
+    /// Records a completed operation so it can be undone later.
+    fn push_undo(&mut self, operation: Operation) {
+        if let Some(group) = &mut self.undo_group {
+            group.push(operation);
+            return;
+        }
+        self.undo_stack.push(operation);
+        let excess = self.undo_stack.len().saturating_sub(UNDO_LIMIT);
+        self.undo_stack.drain(..excess);
+    }
+
+    /// Starts collecting operations into a single undo step.
+    fn begin_undo_group(&mut self) {
+        self.undo_group = Some(Vec::new());
+    }
+
+    /// Records the operations collected since `begin_undo_group` as one step.
+    fn end_undo_group(&mut self, description: String) {
+        let Some(operations) = self.undo_group.take() else {
+            return;
+        };
+        if !operations.is_empty() {
+            self.push_undo(Operation::Batch {
+                description,
+                operations,
+            });
+        }
+    }
+
+    /// Logs what an operation would have done while dry-run mode is on.
+    fn log_dry_run(&mut self, what: String) {
+        self.log_lines.push(format!("[DRY RUN] Would {}", what));
+    }
+
+    /// Logs `lines` and prints them right away as well. Prompts block before
+    /// the screen is redrawn, so a list the user is asked about must be printed.
+    fn show_lines(&mut self, lines: impl IntoIterator<Item = String>) {
+        for line in lines {
+            let _ = execute!(io::stdout(), MoveToColumn(0));
+            print!("{}{}", line, LINE_ENDING);
+            self.log_lines.push(line);
+        }
+        let _ = io::stdout().flush();
+    }
+
+    /// Index of the first log line shown in the log pane.
+    fn log_top(&self) -> usize {
+        let last_page = self.log_lines.len().saturating_sub(self.log_page_rows);
+        self.log_scroll.map_or(last_page, |top| top.min(last_page))
+    }
+
+    /// Scrolls the log by `delta` lines; reaching the bottom follows new output again.
+    fn scroll_log(&mut self, delta: isize) {
+        let last_page = self.log_lines.len().saturating_sub(self.log_page_rows);
+        let top = self.log_top().saturating_add_signed(delta).min(last_page);
+        self.log_scroll = (top < last_page).then_some(top);
+    }
+
+    /// Drops the oldest log lines beyond `LOG_CAPACITY`, keeping the listing
+    /// range and a scrolled-back view pointing at the same lines.
+    fn trim_log(&mut self) {
+        let excess = self.log_lines.len().saturating_sub(LOG_CAPACITY);
+        if excess == 0 {
+            return;
+        }
+        self.log_lines.drain(..excess);
+        self.listing_range = self
+            .listing_range
+            .filter(|(start, _)| *start >= excess)
+            .map(|(start, end)| (start - excess, end - excess));
+        self.log_scroll = self.log_scroll.map(|top| top.saturating_sub(excess));
+    }
+
+    /// Appends an entry to the persistent operation log. A failure to write
+    /// the log is reported in the on-screen log but never aborts the operation.
+    fn record_operation(&mut self, action: &str, paths: &str, outcome: &str) {
+        if let Err(e) = append_operation_log(action, paths, outcome) {
+            self.log_lines
+                .push(format!("Warning: could not write operation log: {:#}", e));
+        }
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -160,6 +334,9 @@ async fn main() -> Result<()> {
 
     // 8) Create our app state
     let mut app_state = AppState::new().context("Failed to initialize AppState")?;
+    app_state.follow_symlinks = args.follow_symlinks;
+    app_state.dry_run = args.dry_run;
+    app_state.ascii_tree = args.ascii_tree;
 
     // 9) Run the main TUI event loop
     let res = run_app(&mut terminal, &mut app_state);
@@ -196,49 +373,102 @@ fn run_app(
                 .direction(Direction::Vertical)
                 .constraints([
                     Constraint::Length(5),  // top area (banner-ish or short instructions)
-                    Constraint::Length(14), // menu area
+                    Constraint::Length(15), // menu area (scrolls with the selection)
                     Constraint::Min(10),    // log area
                 ])
                 .split(size);
 
             // (1) Top pane
             draw_banner(frame, chunks[0], &app_state.current_dir);
+            if app_state.watch_mode {
+                draw_watch_indicator(frame, chunks[0], app_state);
+            }
+            if app_state.dry_run {
+                draw_dry_run_indicator(frame, chunks[0]);
+            }
 
-            // (2) Middle pane: Menu
-            let items: Vec<ListItem> = app_state
-                .menu_items
-                .iter()
-                .enumerate()
-                .map(|(i, &title)| {
-                    let style = if i == app_state.menu_index {
-                        // Highlight the current selection
-                        Style::default().fg(Color::Black).bg(Color::Yellow)
-                    } else {
-                        Style::default().fg(Color::White)
-                    };
-                    ListItem::new(Line::from(Span::styled(title, style)))
-                })
-                .collect();
+            // (2) Middle pane: Menu, or the entries being marked in selection mode
+            if app_state.select_mode {
+                draw_selection_list(frame, chunks[1], app_state);
+            } else {
+                let items: Vec<ListItem> = app_state
+                    .menu_items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &title)| {
+                        let style = if i == app_state.menu_index {
+                            // Highlight the current selection
+                            Style::default().fg(Color::Black).bg(Color::Yellow)
+                        } else {
+                            Style::default().fg(Color::White)
+                        };
+                        ListItem::new(Line::from(Span::styled(title, style)))
+                    })
+                    .collect();
 
-            let menu =
-                List::new(items).block(Block::default().borders(Borders::ALL).title(" Menu "));
-            frame.render_widget(menu, chunks[1]);
+                let mut menu_title = if app_state.follow_symlinks {
+                    " Menu [following symlinks] ".to_string()
+                } else {
+                    " Menu ".to_string()
+                };
+                if !app_state.selected.is_empty() {
+                    menu_title.push_str(&format!(
+                        "[{} selected: copy/move/delete act on them] ",
+                        app_state.selected.len()
+                    ));
+                }
+                let menu = List::new(items)
+                    .block(Block::default().borders(Borders::ALL).title(menu_title));
+                let mut menu_state = ListState::default().with_selected(Some(app_state.menu_index));
+                frame.render_stateful_widget(menu, chunks[1], &mut menu_state);
+            }
 
             // (3) Bottom pane: Log output
-            let log_items: Vec<ListItem> = app_state
-                .log_lines
+            // Only the window of lines that fits is rendered (two rows go to borders)
+            app_state.log_page_rows = chunks[2].height.saturating_sub(2).max(1) as usize;
+            let top = app_state.log_top();
+            let bottom = (top + app_state.log_page_rows).min(app_state.log_lines.len());
+            let log_items: Vec<ListItem> = app_state.log_lines[top..bottom]
                 .iter()
-                .map(|line| ListItem::new(Line::from(line.clone())))
+                .map(|line| {
+                    let item = ListItem::new(Line::from(line.clone()));
+                    if line.starts_with("Warning:") {
+                        item.style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+                    } else {
+                        item
+                    }
+                })
                 .collect();
 
+            let mut log_title = if app_state.watch_mode {
+                " Log [LIVE] ".to_string()
+            } else {
+                " Log ".to_string()
+            };
+            if app_state.log_scroll.is_some() {
+                log_title.push_str(&format!(
+                    "[lines {}-{} of {}, End: follow] ",
+                    top + 1,
+                    bottom,
+                    app_state.log_lines.len()
+                ));
+            }
             let log_widget =
-                List::new(log_items).block(Block::default().borders(Borders::ALL).title(" Log "));
+                List::new(log_items).block(Block::default().borders(Borders::ALL).title(log_title));
             frame.render_widget(log_widget, chunks[2]);
         })?;
 
+        // Pick up any filesystem changes reported by the watcher
+        poll_watch_events(app_state);
+        app_state.trim_log();
+
         // Handle input (non-blocking poll + read)
         if crossterm::event::poll(Duration::from_millis(100))? {
             if let Event::Key(key_event) = event::read()? {
+                // Selection mode takes the keys it uses; the rest fall through
+                if app_state.select_mode && select_mode_key(app_state, key_event)? {
+                    continue;
+                }
                 match (key_event.code, key_event.modifiers) {
                     // Press 'q' to exit
                     (KeyCode::Char('q'), _) => {
@@ -248,32 +478,113 @@ fn run_app(
                         return Ok(());
                     }
                     // Up/Down arrow to navigate
-                    (KeyCode::Up, _) => {
-                        if app_state.menu_index > 0 {
-                            app_state.menu_index -= 1;
+                    (KeyCode::Up, _) if app_state.menu_index > 0 => {
+                        app_state.menu_index -= 1;
+                    }
+                    (KeyCode::Down, _) if app_state.menu_index < app_state.menu_items.len() - 1 => {
+                        app_state.menu_index += 1;
+                    }
+                    // PageUp/PageDown and Home/End scroll the log
+                    (KeyCode::PageUp, _) => {
+                        app_state.scroll_log(-(app_state.log_page_rows as isize));
+                    }
+                    (KeyCode::PageDown, _) => {
+                        app_state.scroll_log(app_state.log_page_rows as isize);
+                    }
+                    (KeyCode::Home, _) => app_state.scroll_log(isize::MIN / 2),
+                    (KeyCode::End, _) => app_state.log_scroll = None,
+                    // Press 'r' to refresh the listing manually
+                    (KeyCode::Char('r'), _) => {
+                        refresh_listing(app_state)?;
+                        if app_state.select_mode {
+                            reload_selection_list(app_state)?;
+                        }
+                    }
+                    // Press 's' to mark entries for a batch copy/move/delete
+                    (KeyCode::Char('s'), _) => toggle_select_mode(app_state)?,
+                    // Press 'l' to toggle following symlinks during recursion
+                    (KeyCode::Char('l'), _) => {
+                        app_state.follow_symlinks = !app_state.follow_symlinks;
+                        let state = if app_state.follow_symlinks {
+                            "on"
+                        } else {
+                            "off"
+                        };
+                        app_state
+                            .log_lines
+                            .push(format!("Follow symlinks: {}", state));
+                    }
+                    // Press 'd' to toggle dry-run mode
+                    (KeyCode::Char('d'), _) => {
+                        app_state.dry_run = !app_state.dry_run;
+                        let state = if app_state.dry_run {
+                            "on - operations are only logged"
+                        } else {
+                            "off"
+                        };
+                        app_state.log_lines.push(format!("Dry run: {}", state));
+                    }
+                    // Press 'y' to copy a path to the clipboard
+                    (KeyCode::Char('y'), _) => {
+                        copy_path_to_clipboard(app_state)?;
+                    }
+                    // Press 'u' to undo the last file operation
+                    (KeyCode::Char('u'), _) => undo_last_operation(app_state)?,
+                    // Press 'p' to preview the first lines of a text file
+                    (KeyCode::Char('p'), _) => preview_file_interactive(app_state)?,
+                    // Press 'g' to go straight to a typed path
+                    (KeyCode::Char('g'), _) => {
+                        go_to_path(app_state)?;
+                        if app_state.watch_mode {
+                            start_watching(app_state);
                         }
                     }
-                    (KeyCode::Down, _) => {
-                        if app_state.menu_index < app_state.menu_items.len() - 1 {
-                            app_state.menu_index += 1;
+                    // Press 'j' to jump to a recently visited directory
+                    (KeyCode::Char('j'), _) => {
+                        jump_to_recent_directory(app_state)?;
+                        if app_state.watch_mode {
+                            start_watching(app_state);
                         }
                     }
                     // Press Enter to select a menu item
                     (KeyCode::Enter, _) => {
                         let choice = app_state.menu_index + 1;
                         match choice {
-                            1 => change_directory(app_state)?,
+                            1 => {
+                                change_directory(app_state)?;
+                                if app_state.watch_mode {
+                                    start_watching(app_state);
+                                }
+                            }
                             2 => list_contents(app_state)?,
                             3 => show_tree_view(app_state)?,
                             4 => show_directory_info(app_state)?,
                             5 => create_file(app_state)?,
                             6 => create_directory(app_state)?,
+                            7 if !app_state.selected.is_empty() => {
+                                transfer_selected(app_state, Transfer::Copy)?
+                            }
+                            8 if !app_state.selected.is_empty() => {
+                                transfer_selected(app_state, Transfer::Move)?
+                            }
+                            9 if !app_state.selected.is_empty() => delete_selected(app_state)?,
                             7 => copy_interactive(app_state)?,
                             8 => move_or_rename_interactive(app_state)?,
                             9 => delete_interactive(app_state)?,
                             10 => duplicate_interactive(app_state)?,
                             11 => organize_files_interactive(app_state)?,
-                            12 => {
+                            12 => toggle_watch_mode(app_state)?,
+                            13 => create_symlink_interactive(app_state)?,
+                            14 => batch_rename_interactive(app_state)?,
+                            15 => edit_file_interactive(terminal, app_state)?,
+                            16 => chmod_interactive(app_state)?,
+                            17 => open_with_default_app(app_state)?,
+                            18 => view_operation_log(app_state)?,
+                            19 => empty_directories_interactive(app_state)?,
+                            20 => large_files_interactive(app_state)?,
+                            21 => find_files_interactive(app_state)?,
+                            22 => undo_last_operation(app_state)?,
+                            23 => {
                                 app_state
                                     .log_lines
                                     .push("Exiting File Commander. Goodbye!".to_string());
@@ -386,6 +697,60 @@ fn draw_banner(frame: &mut Frame, area: Rect, current_dir: &Path) {
     frame.render_widget(paragraph, area);
 }
 
+/// Overlay a watch-mode status line on the last row inside the banner.
+fn draw_watch_indicator(frame: &mut Frame, area: Rect, app_state: &AppState) {
+    if area.height < 4 {
+        return;
+    }
+    let (text, style) = match (&app_state.watcher, &app_state.last_change) {
+        (None, _) => (
+            "Watch unavailable - press 'r' to refresh".to_string(),
+            Style::default().fg(Color::Red),
+        ),
+        (Some(_), Some(change)) => (
+            format!("LIVE - {}", change),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        (Some(_), None) => (
+            "LIVE - watching for changes".to_string(),
+            Style::default().fg(Color::Green),
+        ),
+    };
+    let line_area = Rect {
+        x: area.x + 1,
+        y: area.y + area.height - 2,
+        width: area.width.saturating_sub(2),
+        height: 1,
+    };
+    let paragraph =
+        Paragraph::new(Line::from(Span::styled(text, style))).alignment(Alignment::Center);
+    frame.render_widget(paragraph, line_area);
+}
+
+/// Overlay a red "DRY RUN" badge on the first row inside the banner.
+fn draw_dry_run_indicator(frame: &mut Frame, area: Rect) {
+    if area.height < 3 {
+        return;
+    }
+    let line_area = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: 1,
+    };
+    let badge = Span::styled(
+        " DRY RUN - nothing is changed ",
+        Style::default()
+            .fg(Color::White)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD),
+    );
+    let paragraph = Paragraph::new(Line::from(badge)).alignment(Alignment::Right);
+    frame.render_widget(paragraph, line_area);
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Helper: center a smaller box within a given area
 ////////////////////////////////////////////////////////////////////////////////
@@ -421,7 +786,10 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
 
 /// 1) Change directory (cd).
 fn change_directory(app_state: &mut AppState) -> Result<()> {
-    let path = read_user_input("Enter path to change directory: ")?;
+    let path = read_path_with_completion(
+        "Enter path to change directory (Tab completes): ",
+        &app_state.current_dir,
+    )?;
     let trimmed = path.trim();
     if trimmed.is_empty() {
         app_state
@@ -437,10 +805,7 @@ fn change_directory(app_state: &mut AppState) -> Result<()> {
     };
 
     if target.is_dir() {
-        app_state.current_dir = target.canonicalize().context("canonicalize() failed")?;
-        app_state
-            .log_lines
-            .push(format!("Directory changed to {:?}", app_state.current_dir));
+        enter_directory(app_state, &target)?;
     } else {
         app_state
             .log_lines
@@ -449,25 +814,481 @@ fn change_directory(app_state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
+/// Makes `target` the current directory and records both the directory being
+/// left and the new one in the recent-directories list.
+fn enter_directory(app_state: &mut AppState, target: &Path) -> Result<()> {
+    let previous = app_state.current_dir.clone();
+    app_state.current_dir = target.canonicalize().context("canonicalize() failed")?;
+    app_state
+        .log_lines
+        .push(format!("Directory changed to {:?}", app_state.current_dir));
+
+    remember_directory(&mut app_state.recent_dirs, previous);
+    remember_directory(&mut app_state.recent_dirs, app_state.current_dir.clone());
+    if let Err(e) = save_recent_dirs(&app_state.recent_dirs) {
+        app_state.log_lines.push(format!(
+            "Warning: could not save recent directories: {:#}",
+            e
+        ));
+    }
+    Ok(())
+}
+
 /// 2) List directory contents, similar to `ls`.
 fn list_contents(app_state: &mut AppState) -> Result<()> {
     let show_hidden = read_user_input("Show hidden files? (y/n): ")?;
-    let show_hidden = matches_yes(&show_hidden);
+    app_state.show_hidden = matches_yes(&show_hidden);
+    let sort = read_user_input(
+        "Sort by (n)ame, (s)ize, or (m)odified time; add '-' for descending, e.g. 's-' (default n): ",
+    )?;
+    let Some(key) = SortKey::parse(sort.trim().trim_end_matches('-')) else {
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} is not a sort order.", sort.trim()));
+        return Ok(());
+    };
+    let dirs_first = read_user_input("List directories first? (Y/n): ")?;
+    app_state.listing_sort = ListingSort {
+        key,
+        descending: sort.trim().ends_with('-'),
+        dirs_first: !dirs_first.trim().eq_ignore_ascii_case("n"),
+    };
+    push_listing(app_state)
+}
+
+/// What the listing is sorted by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum SortKey {
+    #[default]
+    Name,
+    Size,
+    Modified,
+}
+
+impl SortKey {
+    /// `n`/`s`/`m` (or the full word); blank means name.
+    fn parse(input: &str) -> Option<Self> {
+        match input.to_ascii_lowercase().as_str() {
+            "" | "n" | "name" => Some(SortKey::Name),
+            "s" | "size" => Some(SortKey::Size),
+            "m" | "modified" | "time" => Some(SortKey::Modified),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ListingSort {
+    key: SortKey,
+    descending: bool,
+    /// Directories before everything else, whatever the order within each group
+    dirs_first: bool,
+}
+
+impl Default for ListingSort {
+    fn default() -> Self {
+        Self {
+            key: SortKey::Name,
+            descending: false,
+            dirs_first: true,
+        }
+    }
+}
+
+/// A directory entry with its metadata read once, for sorting and display.
+struct ListingEntry {
+    name: String,
+    path: PathBuf,
+    is_dir: bool,
+    is_symlink: bool,
+    /// 0 for directories, whose own size says nothing about their contents
+    size: u64,
+    modified: Option<std::time::SystemTime>,
+}
+
+impl ListingEntry {
+    fn new(entry: &fs::DirEntry) -> Self {
+        // `DirEntry::metadata` doesn't follow symlinks, so a link is listed as itself
+        let meta = entry.metadata().ok();
+        let is_dir = meta.as_ref().is_some_and(|m| m.is_dir());
+        Self {
+            name: entry.file_name().to_string_lossy().to_string(),
+            path: entry.path(),
+            is_dir,
+            is_symlink: meta.as_ref().is_some_and(|m| m.file_type().is_symlink()),
+            size: meta.as_ref().filter(|_| !is_dir).map_or(0, |m| m.len()),
+            modified: meta.and_then(|m| m.modified().ok()),
+        }
+    }
+}
+
+/// Sorts by `sort.key` (ties broken by name), reversing the order if
+/// descending, with directories grouped first if asked.
+fn sort_listing(entries: &mut [ListingEntry], sort: ListingSort) {
+    entries.sort_by(|a, b| {
+        let by_name = a.name.to_lowercase().cmp(&b.name.to_lowercase());
+        let order = match sort.key {
+            SortKey::Name => by_name,
+            SortKey::Size => a.size.cmp(&b.size).then(by_name),
+            SortKey::Modified => a.modified.cmp(&b.modified).then(by_name),
+        };
+        let order = if sort.descending {
+            order.reverse()
+        } else {
+            order
+        };
+        if sort.dirs_first {
+            b.is_dir.cmp(&a.is_dir).then(order)
+        } else {
+            order
+        }
+    });
+}
 
+/// Append the contents of `current_dir` to the log, remembering where it lives.
+/// Each line reads like `ls -l`: modification time, size, then the name.
+fn push_listing(app_state: &mut AppState) -> Result<()> {
+    let start = app_state.log_lines.len();
     let dir = &app_state.current_dir;
     let entries = fs::read_dir(dir).context("read_dir failed")?;
     app_state.log_lines.push(format!("Contents of {:?}:", dir));
 
-    for entry in entries.flatten() {
-        let file_name = entry.file_name().to_string_lossy().to_string();
-        if !show_hidden && file_name.starts_with('.') {
-            continue;
-        }
-        app_state.log_lines.push(format!("  {}", file_name));
+    let mut entries: Vec<ListingEntry> = entries
+        .flatten()
+        .filter(|e| app_state.show_hidden || !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| ListingEntry::new(&e))
+        .collect();
+    sort_listing(&mut entries, app_state.listing_sort);
+    // Pad names so the detected-type column lines up (long names just push it over)
+    let width = entries
+        .iter()
+        .map(|e| e.name.chars().count() + usize::from(e.is_dir))
+        .max()
+        .unwrap_or(0)
+        .min(40);
+
+    for entry in entries {
+        let modified = entry
+            .modified
+            .map(|time| {
+                DateTime::<Local>::from(time)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "?".repeat(16));
+        let size = if entry.is_dir {
+            "-".to_string()
+        } else {
+            format_size(entry.size)
+        };
+        let details = if entry.is_symlink {
+            describe_symlink(&entry.path)
+        } else if entry.is_dir {
+            format!("{}/", entry.name)
+        } else {
+            let detected = detect_file_type(&entry.path);
+            let mut line = format!("{:<width$}  [{}]", entry.name, detected.mime);
+            if !detected.matches_extension(&entry.path) {
+                line.push_str(" (extension mismatch)");
+            }
+            line
+        };
+        app_state
+            .log_lines
+            .push(format!("  {}  {:>10}  {}", modified, size, details));
     }
+    app_state.listing_range = Some((start, app_state.log_lines.len()));
     Ok(())
 }
 
+/// Re-render the listing: replace it in place if it is still the last thing
+/// in the log, otherwise append a fresh copy.
+fn refresh_listing(app_state: &mut AppState) -> Result<()> {
+    if let Some((start, end)) = app_state.listing_range {
+        if end == app_state.log_lines.len() {
+            app_state.log_lines.truncate(start);
+        }
+    }
+    push_listing(app_state)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Recent Directories (jump list)
+////////////////////////////////////////////////////////////////////////////////
+
+/// File name (in the home directory) of the recent-directories list, one path per line.
+const RECENT_DIRS_FILE: &str = ".file_commander_recent";
+
+/// Maximum number of directories kept in the jump list.
+const RECENT_DIRS_LIMIT: usize = 15;
+
+fn recent_dirs_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not locate home directory")?;
+    Ok(home.join(RECENT_DIRS_FILE))
+}
+
+/// Loads the jump list, dropping directories that no longer exist.
+/// A missing or unreadable file is an empty list.
+fn load_recent_dirs() -> Vec<PathBuf> {
+    let contents = recent_dirs_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    let mut recent = Vec::new();
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        let dir = PathBuf::from(line);
+        if dir.is_dir() && !recent.contains(&dir) {
+            recent.push(dir);
+        }
+    }
+    recent.truncate(RECENT_DIRS_LIMIT);
+    recent
+}
+
+fn save_recent_dirs(recent: &[PathBuf]) -> Result<()> {
+    let path = recent_dirs_path()?;
+    let mut contents = String::new();
+    for dir in recent {
+        contents.push_str(&dir.to_string_lossy());
+        contents.push('\n');
+    }
+    fs::write(&path, contents).with_context(|| format!("Failed to write {:?}", path))
+}
+
+/// Moves `dir` to the front of the jump list, removing any older copy.
+fn remember_directory(recent: &mut Vec<PathBuf>, dir: PathBuf) {
+    recent.retain(|d| *d != dir);
+    recent.insert(0, dir);
+    recent.truncate(RECENT_DIRS_LIMIT);
+}
+
+/// 'j') List the recently visited directories and jump to the chosen one.
+fn jump_to_recent_directory(app_state: &mut AppState) -> Result<()> {
+    let current = app_state.current_dir.clone();
+    let choices: Vec<PathBuf> = app_state
+        .recent_dirs
+        .iter()
+        .filter(|d| **d != current)
+        .cloned()
+        .collect();
+    if choices.is_empty() {
+        app_state
+            .log_lines
+            .push("No recent directories yet. Use 'Change directory' first.".to_string());
+        return Ok(());
+    }
+
+    app_state.show_lines(
+        std::iter::once("=== Recent directories (most recent first) ===".to_string()).chain(
+            choices
+                .iter()
+                .enumerate()
+                .map(|(i, dir)| format!("  {:>2}) {}", i + 1, dir.display())),
+        ),
+    );
+
+    let input = read_user_input("Jump to which directory? (number, blank to cancel): ")?;
+    let input = input.trim();
+    if input.is_empty() {
+        app_state.log_lines.push("Jump cancelled.".to_string());
+        return Ok(());
+    }
+    let Some(target) = input
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| choices.get(i))
+    else {
+        app_state.log_lines.push(format!(
+            "Error: '{}' is not one of the listed numbers.",
+            input
+        ));
+        return Ok(());
+    };
+
+    if target.is_dir() {
+        enter_directory(app_state, target)
+    } else {
+        // Removed since it was recorded; drop it from the list too
+        app_state.recent_dirs.retain(|d| d != target);
+        let _ = save_recent_dirs(&app_state.recent_dirs);
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} no longer exists.", target));
+        Ok(())
+    }
+}
+
+/// Quick-jump ('g'): goes straight to a typed absolute or relative path,
+/// expanding `~` and environment variables.
+fn go_to_path(app_state: &mut AppState) -> Result<()> {
+    let input = read_user_input("Go to path (~ and $VARS allowed, blank to cancel): ")?;
+    let input = input.trim();
+    if input.is_empty() {
+        app_state
+            .log_lines
+            .push("Go to path cancelled.".to_string());
+        return Ok(());
+    }
+
+    let target = match expand_path(input) {
+        Ok(path) if path.is_absolute() => path,
+        Ok(path) => app_state.current_dir.join(path),
+        Err(e) => {
+            app_state.log_lines.push(format!("Error: {}", e));
+            return Ok(());
+        }
+    };
+    if target.is_dir() {
+        enter_directory(app_state, &target)
+    } else {
+        let reason = if target.exists() {
+            "is not a directory"
+        } else {
+            "does not exist"
+        };
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} {}.", target, reason));
+        Ok(())
+    }
+}
+
+/// Expands a leading `~` (or `~/...`) to the home directory and `$VAR` or
+/// `${VAR}` to the variable's value. Unset variables are an error rather than
+/// silently becoming empty, which would send the jump somewhere unexpected.
+fn expand_path(input: &str) -> Result<PathBuf> {
+    let mut expanded = String::new();
+    let mut rest = input;
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        let home = dirs::home_dir().context("Could not locate home directory")?;
+        expanded.push_str(&home.to_string_lossy());
+        rest = &rest[1..];
+    }
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let (name, remainder) = if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| anyhow!("Missing '}}' in {:?}", input))?;
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        if name.is_empty() {
+            // A lone '$' is just part of the name
+            expanded.push('$');
+        } else {
+            let value = std::env::var(name)
+                .map_err(|_| anyhow!("Environment variable ${} is not set", name))?;
+            expanded.push_str(&value);
+        }
+        rest = remainder;
+    }
+    expanded.push_str(rest);
+    Ok(PathBuf::from(expanded))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Watch Mode (live listing)
+////////////////////////////////////////////////////////////////////////////////
+
+/// 12) Toggle watch mode, which keeps the listing in sync with `current_dir`.
+fn toggle_watch_mode(app_state: &mut AppState) -> Result<()> {
+    if app_state.watch_mode {
+        app_state.watch_mode = false;
+        app_state.watcher = None;
+        app_state.watch_rx = None;
+        app_state.last_change = None;
+        app_state.log_lines.push("Watch mode disabled.".to_string());
+        return Ok(());
+    }
+
+    app_state.watch_mode = true;
+    start_watching(app_state);
+    refresh_listing(app_state)
+}
+
+/// (Re)start the watcher on `current_dir`. Falls back to manual refresh on failure.
+fn start_watching(app_state: &mut AppState) {
+    app_state.watcher = None;
+    app_state.watch_rx = None;
+    app_state.last_change = None;
+
+    let (tx, rx) = mpsc::channel();
+    let watcher = notify::recommended_watcher(tx).and_then(|mut w| {
+        w.watch(&app_state.current_dir, RecursiveMode::NonRecursive)?;
+        Ok(w)
+    });
+
+    match watcher {
+        Ok(w) => {
+            app_state.watcher = Some(w);
+            app_state.watch_rx = Some(rx);
+            app_state.log_lines.push(format!(
+                "Watch mode enabled for {:?}.",
+                app_state.current_dir
+            ));
+        }
+        Err(e) => {
+            app_state.log_lines.push(format!(
+                "Watcher unavailable ({}). Press 'r' to refresh the listing manually.",
+                e
+            ));
+        }
+    }
+}
+
+/// Drain pending watcher events and refresh the listing if anything changed.
+fn poll_watch_events(app_state: &mut AppState) {
+    let Some(rx) = &app_state.watch_rx else {
+        return;
+    };
+
+    let mut change = None;
+    for event in rx.try_iter().flatten() {
+        let action = match event.kind {
+            EventKind::Create(_) => "Created",
+            EventKind::Remove(_) => "Removed",
+            EventKind::Modify(_) => "Modified",
+            _ => continue,
+        };
+        let name = event
+            .paths
+            .first()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        change = Some(format!(
+            "{} {} at {}",
+            action,
+            name,
+            Local::now().format("%H:%M:%S")
+        ));
+    }
+
+    if let Some(change) = change {
+        app_state.last_change = Some(change);
+        if let Err(e) = refresh_listing(app_state) {
+            app_state
+                .log_lines
+                .push(format!("Failed to refresh listing: {}", e));
+        }
+        if app_state.select_mode {
+            if let Err(e) = reload_selection_list(app_state) {
+                app_state
+                    .log_lines
+                    .push(format!("Failed to refresh selection list: {}", e));
+            }
+        }
+    }
+}
+
 /// 3) Show all files/folders in a tree view.
 fn show_tree_view(app_state: &mut AppState) -> Result<()> {
     let path = read_user_input(&format!(
@@ -490,40 +1311,96 @@ fn show_tree_view(app_state: &mut AppState) -> Result<()> {
     app_state
         .log_lines
         .push("=== Directory Tree View ===".to_string());
-    print_directory_tree(&dir_path, 0, app_state)?;
+    app_state.log_lines.push(format!("{}/", dir_path.display()));
+    let glyphs = if app_state.ascii_tree {
+        &ASCII_TREE
+    } else {
+        &UNICODE_TREE
+    };
+    let mut walker = DirWalker::new(&dir_path, app_state.follow_symlinks);
+    print_directory_tree(&dir_path, "", glyphs, &mut walker, app_state)?;
     Ok(())
 }
 
-fn print_directory_tree(dir: &Path, level: usize, app_state: &mut AppState) -> Result<()> {
-    let indent = "  ".repeat(level);
-    let dir_name = dir
-        .file_name()
-        .unwrap_or_default()
-        .to_string_lossy()
-        .to_string();
-    app_state
-        .log_lines
-        .push(format!("{}- {}", indent, dir_name));
+/// Connectors used to draw the tree view, each padded to the same width.
+struct TreeGlyphs {
+    /// Before an entry with more siblings after it
+    branch: &'static str,
+    /// Before the last entry of a directory
+    last: &'static str,
+    /// Below an ancestor that still has siblings to come
+    vertical: &'static str,
+    /// Below an ancestor that was the last entry
+    blank: &'static str,
+}
+
+const UNICODE_TREE: TreeGlyphs = TreeGlyphs {
+    branch: "├── ",
+    last: "└── ",
+    vertical: "│   ",
+    blank: "    ",
+};
+
+const ASCII_TREE: TreeGlyphs = TreeGlyphs {
+    branch: "|-- ",
+    last: "`-- ",
+    vertical: "|   ",
+    blank: "    ",
+};
 
+/// Logs the contents of `dir` like the Unix `tree` command: directories first,
+/// then files, each sorted by name. `prefix` carries the connectors of the
+/// ancestors, so a `│` only continues below ancestors that aren't the last child.
+fn print_directory_tree(
+    dir: &Path,
+    prefix: &str,
+    glyphs: &TreeGlyphs,
+    walker: &mut DirWalker,
+    app_state: &mut AppState,
+) -> Result<()> {
     let entries = fs::read_dir(dir).context("read_dir failed")?;
     let mut dirs = Vec::new();
     let mut files = Vec::new();
     for entry in entries.flatten() {
         let path = entry.path();
-        if path.is_dir() {
-            dirs.push(path);
-        } else {
-            files.push(path);
+        match walker.classify(&path)? {
+            WalkEntry::Directory => dirs.push(path),
+            WalkEntry::Revisited => files.push((path, "/ (already shown)")),
+            WalkEntry::File | WalkEntry::Symlink => files.push((path, "")),
         }
     }
-    for d in dirs {
-        print_directory_tree(&d, level + 1, app_state)?;
+    dirs.sort();
+    files.sort();
+
+    let total = dirs.len() + files.len();
+    for (i, d) in dirs.iter().enumerate() {
+        let is_last = i + 1 == total;
+        let connector = if is_last { glyphs.last } else { glyphs.branch };
+        let name = d.file_name().unwrap_or_default().to_string_lossy();
+        app_state
+            .log_lines
+            .push(format!("{}{}{}/", prefix, connector, name));
+        let child_prefix = format!(
+            "{}{}",
+            prefix,
+            if is_last {
+                glyphs.blank
+            } else {
+                glyphs.vertical
+            }
+        );
+        print_directory_tree(d, &child_prefix, glyphs, walker, app_state)?;
     }
-    for f in files {
-        let file_name = f.file_name().unwrap_or_default().to_string_lossy();
+    for (i, (f, note)) in files.iter().enumerate() {
+        let connector = if dirs.len() + i + 1 == total {
+            glyphs.last
+        } else {
+            glyphs.branch
+        };
+        let name = f.file_name().unwrap_or_default().to_string_lossy();
         app_state
             .log_lines
-            .push(format!("{}  * {}", "  ".repeat(level + 1), file_name));
+            .push(format!("{}{}{}{}", prefix, connector, name, note));
     }
     Ok(())
 }
@@ -550,16 +1427,32 @@ fn show_directory_info(app_state: &mut AppState) -> Result<()> {
     app_state
         .log_lines
         .push("=== Directory Info ===".to_string());
-    let (total_size, file_count, dir_count) = compute_directory_stats(&dir_path)?;
+    let mut walker = DirWalker::new(&dir_path, app_state.follow_symlinks);
+    let stats = compute_directory_stats(&dir_path, &mut walker)?;
     app_state
         .log_lines
         .push(format!("Path: {}", dir_path.display()));
-    app_state
-        .log_lines
-        .push(format!("Total size (bytes): {}", total_size));
-    app_state
-        .log_lines
-        .push(format!("Files: {}, Directories: {}", file_count, dir_count));
+    app_state.log_lines.push(format!(
+        "Total size: {} ({} bytes)",
+        format_size(stats.size),
+        stats.size
+    ));
+    app_state.log_lines.push(format!(
+        "Files: {}, Directories: {}",
+        stats.files, stats.dirs
+    ));
+    if let Some(average) = stats.size.checked_div(stats.files) {
+        app_state
+            .log_lines
+            .push(format!("Average file size: {}", format_size(average)));
+    }
+    if let Some((path, size)) = &stats.largest {
+        app_state.log_lines.push(format!(
+            "Largest file: {} ({})",
+            path.display(),
+            format_size(*size)
+        ));
+    }
 
     #[cfg(unix)]
     {
@@ -576,33 +1469,58 @@ fn show_directory_info(app_state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
+/// Totals gathered by `compute_directory_stats`.
+#[derive(Debug, Default)]
+struct DirectoryStats {
+    /// Bytes in all files (and unfollowed symlinks)
+    size: u64,
+    files: u64,
+    dirs: u64,
+    /// The biggest regular file and its size
+    largest: Option<(PathBuf, u64)>,
+}
+
 /// Recursively compute total size, file count, and directory count of a directory.
-fn compute_directory_stats(dir: &Path) -> Result<(u64, u64, u64)> {
-    let mut total_size = 0;
-    let mut file_count = 0;
-    let mut dir_count = 0;
+/// Unfollowed symlinks count as files of their own (link) size.
+fn compute_directory_stats(dir: &Path, walker: &mut DirWalker) -> Result<DirectoryStats> {
+    let mut stats = DirectoryStats::default();
+    add_directory_stats(dir, walker, &mut stats)?;
+    Ok(stats)
+}
 
+fn add_directory_stats(
+    dir: &Path,
+    walker: &mut DirWalker,
+    stats: &mut DirectoryStats,
+) -> Result<()> {
     for entry in fs::read_dir(dir).context("read_dir failed")? {
         let entry = entry?;
         let path = entry.path();
-        let meta = entry.metadata().context("metadata() failed")?;
-
-        if path.is_dir() {
-            dir_count += 1;
-            let (sub_size, sub_files, sub_dirs) = compute_directory_stats(&path)?;
-            total_size += sub_size;
-            file_count += sub_files;
-            dir_count += sub_dirs;
-        } else {
-            file_count += 1;
-            total_size += meta.len();
+
+        match walker.classify(&path)? {
+            WalkEntry::Directory => {
+                stats.dirs += 1;
+                add_directory_stats(&path, walker, stats)?;
+            }
+            WalkEntry::Revisited => {}
+            WalkEntry::File => {
+                let len = fs::metadata(&path).context("metadata() failed")?.len();
+                stats.files += 1;
+                stats.size += len;
+                if stats.largest.as_ref().is_none_or(|(_, max)| len > *max) {
+                    stats.largest = Some((path, len));
+                }
+            }
+            WalkEntry::Symlink => {
+                stats.files += 1;
+                stats.size += entry.metadata().context("metadata() failed")?.len();
+            }
         }
     }
-
-    Ok((total_size, file_count, dir_count))
+    Ok(())
 }
 
-/// 5) Create a new file (touch).
+/// 5) Create a new file, either empty (touch) or filled from a template or pasted text.
 fn create_file(app_state: &mut AppState) -> Result<()> {
     let filename = read_user_input("Enter name of file to create: ")?;
     let trimmed = filename.trim();
@@ -613,25 +1531,192 @@ fn create_file(app_state: &mut AppState) -> Result<()> {
         return Ok(());
     }
     let new_file_path = app_state.current_dir.join(trimmed);
-    match fs::OpenOptions::new()
+
+    let choice = read_user_input("Content: (e)mpty, (t)emplate, or (p)aste? [e]: ")?;
+    let (content, source) = match choice.trim().to_lowercase().as_str() {
+        "" | "e" | "empty" => (String::new(), "empty".to_string()),
+        "t" | "template" => {
+            let templates = load_templates();
+            app_state.show_lines(
+                std::iter::once(format!(
+                    "Templates (add your own to {}):",
+                    templates_dir_display()
+                ))
+                .chain(
+                    templates
+                        .iter()
+                        .enumerate()
+                        .map(|(i, template)| format!("  {}) {}", i + 1, template.name)),
+                ),
+            );
+            let pick = read_user_input("Template number: ")?;
+            match pick
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|i| templates.get(i))
+            {
+                Some(template) => (
+                    template.content.clone(),
+                    format!("template {}", template.name),
+                ),
+                None => {
+                    app_state
+                        .log_lines
+                        .push("Aborted: no such template.".to_string());
+                    return Ok(());
+                }
+            }
+        }
+        "p" | "paste" => (read_pasted_content()?, "pasted content".to_string()),
+        other => {
+            app_state
+                .log_lines
+                .push(format!("Aborted: unknown content choice {other:?}."));
+            return Ok(());
+        }
+    };
+
+    if app_state.dry_run {
+        app_state.log_dry_run(format!("create file {:?} ({source})", new_file_path));
+        return Ok(());
+    }
+    let created = fs::OpenOptions::new()
         .write(true)
         .create_new(true)
         .open(&new_file_path)
-    {
+        .and_then(|mut file| file.write_all(with_line_endings(&content).as_bytes()));
+    match created {
         Ok(_) => {
+            // A script from a template or paste should be runnable right away
+            #[cfg(unix)]
+            if content.starts_with("#!") {
+                use std::os::unix::fs::PermissionsExt;
+                let _ = fs::set_permissions(&new_file_path, fs::Permissions::from_mode(0o755));
+            }
             app_state
                 .log_lines
-                .push(format!("File created at {:?}", new_file_path));
+                .push(format!("File created at {:?} ({source})", new_file_path));
+            app_state.record_operation(
+                "create file",
+                &format!("{:?}", new_file_path),
+                &format!("ok ({source})"),
+            );
         }
         Err(e) => {
             app_state
                 .log_lines
                 .push(format!("Could not create file: {}", e));
+            app_state.record_operation(
+                "create file",
+                &format!("{:?}", new_file_path),
+                &format!("error: {e}"),
+            );
         }
     }
     Ok(())
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// File Templates
+////////////////////////////////////////////////////////////////////////////////
+
+/// Directory (in the home directory) holding user templates, one file per template.
+const TEMPLATES_DIR: &str = ".file_commander_templates";
+
+/// Templates offered out of the box; a user template with the same name replaces one.
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "README.md",
+        "# Project Title\n\nA short description of the project.\n\n## Usage\n\n```bash\n\n```\n\n## License\n\nMIT\n",
+    ),
+    (
+        ".gitignore",
+        "# Build output\n/target\n/build\n/dist\n\n# Editors and OS files\n.vscode/\n.idea/\n*.swp\n.DS_Store\nThumbs.db\n\n# Environment\n.env\n",
+    ),
+    (
+        "shell script",
+        "#!/usr/bin/env bash\nset -euo pipefail\n\n",
+    ),
+];
+
+struct FileTemplate {
+    name: String,
+    content: String,
+}
+
+fn templates_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(TEMPLATES_DIR))
+}
+
+fn templates_dir_display() -> String {
+    templates_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|| format!("~/{TEMPLATES_DIR}"))
+}
+
+/// Built-in templates followed by the user's own, sorted by name. Unreadable or
+/// non-UTF-8 files in the templates directory are skipped.
+fn load_templates() -> Vec<FileTemplate> {
+    let mut templates: Vec<FileTemplate> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, content)| FileTemplate {
+            name: name.to_string(),
+            content: content.to_string(),
+        })
+        .collect();
+
+    let mut user: Vec<FileTemplate> = templates_dir()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            Some(FileTemplate {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                content,
+            })
+        })
+        .collect();
+    user.sort_by(|a, b| a.name.cmp(&b.name));
+
+    templates.retain(|builtin| !user.iter().any(|t| t.name == builtin.name));
+    templates.extend(user);
+    templates
+}
+
+/// Reads lines from stdin until a line containing only `.` (or end of input).
+fn read_pasted_content() -> Result<String> {
+    print!(
+        "Paste the content, then finish with a line containing only '.':{}",
+        LINE_ENDING
+    );
+    io::stdout().flush()?;
+
+    let mut content = String::new();
+    for line in io::stdin().lines() {
+        let line = line?;
+        if line.trim_end_matches('\r') == "." {
+            break;
+        }
+        content.push_str(&line);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+/// Converts any mix of `\n` and `\r\n` to the platform `LINE_ENDING`.
+fn with_line_endings(content: &str) -> String {
+    let mut out = content.lines().collect::<Vec<_>>().join(LINE_ENDING);
+    if content.ends_with('\n') {
+        out.push_str(LINE_ENDING);
+    }
+    out
+}
+
 /// 6) Create a new directory (mkdir).
 fn create_directory(app_state: &mut AppState) -> Result<()> {
     let name = read_user_input("Enter name of directory to create: ")?;
@@ -643,25 +1728,120 @@ fn create_directory(app_state: &mut AppState) -> Result<()> {
         return Ok(());
     }
     let new_dir_path = app_state.current_dir.join(trimmed);
+    if app_state.dry_run {
+        app_state.log_dry_run(format!("create directory {:?}", new_dir_path));
+        return Ok(());
+    }
     match fs::create_dir(&new_dir_path) {
         Ok(_) => {
             app_state
                 .log_lines
                 .push(format!("Directory created at {:?}", new_dir_path));
+            app_state.record_operation("create directory", &format!("{:?}", new_dir_path), "ok");
         }
         Err(e) => {
             app_state
                 .log_lines
                 .push(format!("Could not create directory: {}", e));
+            app_state.record_operation(
+                "create directory",
+                &format!("{:?}", new_dir_path),
+                &format!("error: {e}"),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 13) Create a symbolic link (ln -s).
+fn create_symlink_interactive(app_state: &mut AppState) -> Result<()> {
+    let target = read_user_input("Enter link target (file/directory the link points to): ")?;
+    let link_name = read_user_input("Enter name of the link to create: ")?;
+    let target = target.trim();
+    let link_name = link_name.trim();
+    if target.is_empty() || link_name.is_empty() {
+        app_state
+            .log_lines
+            .push("Aborted: target and link name are both required.".to_string());
+        return Ok(());
+    }
+
+    let target_path = PathBuf::from(target);
+    let link_path = app_state.current_dir.join(link_name);
+    if link_path.symlink_metadata().is_ok() {
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} already exists.", link_path));
+        return Ok(());
+    }
+
+    // Relative targets are resolved from the directory containing the link.
+    let resolved_target = link_path
+        .parent()
+        .map(|p| p.join(&target_path))
+        .unwrap_or_else(|| target_path.clone());
+    if !resolved_target.exists() {
+        app_state.log_lines.push(format!(
+            "Warning: target {:?} does not exist; the link will be broken.",
+            resolved_target
+        ));
+    }
+
+    let paths = format!("{:?} -> {:?}", link_path, target_path);
+    if app_state.dry_run {
+        app_state.log_dry_run(format!("create symlink {}", paths));
+        return Ok(());
+    }
+    match make_symlink(&target_path, &link_path, resolved_target.is_dir()) {
+        Ok(_) => {
+            app_state
+                .log_lines
+                .push(format!("Symlink created: {}", describe_symlink(&link_path)));
+            app_state.record_operation("symlink", &paths, "ok");
+        }
+        Err(e) => {
+            app_state
+                .log_lines
+                .push(format!("Could not create symlink: {}", e));
+            app_state.record_operation("symlink", &paths, &format!("error: {e}"));
         }
     }
     Ok(())
 }
 
+#[cfg(unix)]
+fn make_symlink(target: &Path, link: &Path, _target_is_dir: bool) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn make_symlink(target: &Path, link: &Path, target_is_dir: bool) -> io::Result<()> {
+    if target_is_dir {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+/// Formats a symlink as `name -> target`, flagging links whose target is missing.
+fn describe_symlink(link: &Path) -> String {
+    let name = link.file_name().unwrap_or_default().to_string_lossy();
+    match fs::read_link(link) {
+        // `metadata` follows the link, so it fails when the target is gone.
+        Ok(target) if fs::metadata(link).is_ok() => {
+            format!("{} -> {}", name, target.display())
+        }
+        Ok(target) => format!("{} -> {} [broken]", name, target.display()),
+        Err(e) => format!("{} -> ? ({})", name, e),
+    }
+}
+
 /// 7) Copy file/directory (cp).
 fn copy_interactive(app_state: &mut AppState) -> Result<()> {
-    let source = read_user_input("Enter source file/directory: ")?;
-    let destination = read_user_input("Enter destination path: ")?;
+    // Relative paths are resolved by the OS, i.e. against the process directory
+    let base_dir = std::env::current_dir().context("Failed to get current directory")?;
+    let source = read_path_with_completion("Enter source file/directory: ", &base_dir)?;
+    let destination = read_path_with_completion("Enter destination path: ", &base_dir)?;
 
     let source_path = PathBuf::from(source.trim());
     let destination_path = PathBuf::from(destination.trim());
@@ -673,43 +1853,363 @@ fn copy_interactive(app_state: &mut AppState) -> Result<()> {
         return Ok(());
     }
 
+    let paths = format!("{:?} -> {:?}", source_path, destination_path);
+    if app_state.dry_run {
+        let what = dry_run_copy_summary(&source_path, app_state.follow_symlinks);
+        app_state.log_dry_run(format!("copy {} {}", what, paths));
+        return Ok(());
+    }
+    // Undoing a copy removes the destination, which is only safe if the copy created it
+    let destination_existed = destination_path.symlink_metadata().is_ok();
+    let undo = if destination_existed {
+        Operation::Irreversible {
+            description: format!("copy into the existing {:?}", destination_path),
+        }
+    } else {
+        Operation::Copy {
+            created: destination_path.clone(),
+        }
+    };
     if source_path.is_file() {
+        if destination_path.is_file()
+            && !matches_yes(&read_user_input(&format!(
+                "Destination {:?} exists, overwrite? (y/n): ",
+                destination_path
+            ))?)
+        {
+            app_state.log_lines.push(format!(
+                "Copy canceled; {:?} was left unchanged.",
+                destination_path
+            ));
+            return Ok(());
+        }
         match fs::copy(&source_path, &destination_path) {
-            Ok(_) => app_state
-                .log_lines
-                .push("File copied successfully.".to_string()),
-            Err(e) => app_state.log_lines.push(format!("File copy failed: {}", e)),
+            Ok(_) => {
+                app_state
+                    .log_lines
+                    .push("File copied successfully.".to_string());
+                app_state.record_operation("copy", &paths, "ok");
+                app_state.push_undo(undo);
+            }
+            Err(e) => {
+                app_state.log_lines.push(format!("File copy failed: {}", e));
+                app_state.record_operation("copy", &paths, &format!("error: {e}"));
+            }
         }
     } else {
-        copy_directory_recursive(&source_path, &destination_path)?;
-        app_state
-            .log_lines
-            .push("Directory copied successfully.".to_string());
+        let mode = if destination_existed {
+            let choice = read_user_input(
+                "Copy (a)ll files (asking before overwriting), or (u)pdate only files that are newer than the destination's? [a]: ",
+            )?;
+            if choice.trim().eq_ignore_ascii_case("u") {
+                let compare_size = matches_yes(&read_user_input(
+                    "Also re-copy files whose size differs, even if the destination is newer? (y/n): ",
+                )?);
+                CopyMode::Update { compare_size }
+            } else {
+                CopyMode::All
+            }
+        } else {
+            CopyMode::All
+        };
+
+        // One metadata-only pass up front, so progress can be shown against a total
+        let mut walker = DirWalker::new(&source_path, app_state.follow_symlinks);
+        let total = compute_directory_stats(&source_path, &mut walker)
+            .map(|stats| CopyStats {
+                files: stats.files,
+                bytes: stats.size,
+                ..Default::default()
+            })
+            .unwrap_or_default();
+        let mut last_report = CopyStats::default();
+        let log_lines = &mut app_state.log_lines;
+        let mut report = |done: CopyStats| {
+            let (files, bytes) = done.handled();
+            let (last_files, last_bytes) = last_report.handled();
+            let due = files - last_files >= COPY_PROGRESS_FILES
+                || (total.bytes > 0 && (bytes - last_bytes) * 20 >= total.bytes);
+            if due {
+                last_report = done;
+                let line = copy_progress_line(done, total);
+                // The screen isn't redrawn until the copy returns, so show it in place too
+                let _ = execute!(io::stdout(), MoveToColumn(0), Clear(ClearType::CurrentLine));
+                print!("{line}");
+                let _ = io::stdout().flush();
+                log_lines.push(line);
+            }
+        };
+
+        // Files that already exist are only replaced with the user's consent;
+        // "all" answers apply to the rest of the copy
+        let mut remembered: Option<Conflict> = None;
+        let mut ask = |dest: &Path| -> Result<Conflict> {
+            if let Some(choice) = remembered {
+                return Ok(choice);
+            }
+            print!("{}", LINE_ENDING);
+            loop {
+                let answer = read_user_input(&format!(
+                    "{:?} exists. Overwrite? (y)es, (n)o, overwrite (a)ll, (s)kip all: ",
+                    dest
+                ))?;
+                match answer.trim().to_lowercase().as_str() {
+                    "y" | "yes" => return Ok(Conflict::Overwrite),
+                    "n" | "no" => return Ok(Conflict::Skip),
+                    "a" => return Ok(*remembered.insert(Conflict::Overwrite)),
+                    "s" => return Ok(*remembered.insert(Conflict::Skip)),
+                    _ => {}
+                }
+            }
+        };
+
+        let mut walker = DirWalker::new(&source_path, app_state.follow_symlinks);
+        let result = copy_directory_recursive(
+            &source_path,
+            &destination_path,
+            &mut walker,
+            mode,
+            &mut ask,
+            &mut report,
+        );
+        print!("{}", LINE_ENDING);
+        match result {
+            Ok(stats) => {
+                let mut summary = format!("{} file(s), {}", stats.files, format_size(stats.bytes));
+                if destination_existed {
+                    summary.push_str(&format!(
+                        " copied ({} overwritten); {} {}file(s) ({}) skipped",
+                        stats.overwritten,
+                        stats.skipped,
+                        if mode == CopyMode::All {
+                            "existing "
+                        } else {
+                            "up-to-date "
+                        },
+                        format_size(stats.skipped_bytes)
+                    ));
+                }
+                app_state
+                    .log_lines
+                    .push(format!("Directory copied successfully: {}.", summary));
+                app_state.record_operation("copy", &paths, &format!("ok ({summary})"));
+                app_state.push_undo(undo);
+            }
+            Err(e) => {
+                app_state
+                    .log_lines
+                    .push(format!("Directory copy failed: {:#}", e));
+                app_state.record_operation("copy", &paths, &format!("error: {e:#}"));
+            }
+        }
     }
 
     Ok(())
 }
 
-/// Recursively copy a directory and its contents.
-fn copy_directory_recursive(source: &Path, dest: &Path) -> Result<()> {
+/// Describes a copy source for dry-run output, e.g. `directory (1.2 MiB in 14 file(s))`.
+fn dry_run_copy_summary(source: &Path, follow_symlinks: bool) -> String {
+    if source.is_file() {
+        return match fs::metadata(source) {
+            Ok(meta) => format!("file ({})", format_size(meta.len())),
+            Err(_) => "file".to_string(),
+        };
+    }
+    let mut walker = DirWalker::new(source, follow_symlinks);
+    match compute_directory_stats(source, &mut walker) {
+        Ok(stats) => format!(
+            "directory ({} in {} file(s))",
+            format_size(stats.size),
+            stats.files
+        ),
+        Err(_) => "directory".to_string(),
+    }
+}
+
+/// A progress line is logged after this many files, or every 5% of the bytes.
+const COPY_PROGRESS_FILES: u64 = 50;
+
+/// Which files `copy_directory_recursive` copies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CopyMode {
+    /// Every file; whether existing ones are replaced is up to `on_conflict`
+    All,
+    /// Like `rsync --update`: skip files whose destination is at least as new,
+    /// and with `compare_size` also the same size
+    Update { compare_size: bool },
+}
+
+/// Files (symlinks included) and bytes copied by `copy_directory_recursive`,
+/// plus those skipped as up to date. Counted the same way as
+/// `compute_directory_stats`, so the two can be compared.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct CopyStats {
+    files: u64,
+    bytes: u64,
+    skipped: u64,
+    skipped_bytes: u64,
+    /// Copied files that replaced one already at the destination
+    overwritten: u64,
+}
+
+impl CopyStats {
+    /// Files and bytes dealt with so far, copied or skipped.
+    fn handled(&self) -> (u64, u64) {
+        (self.files + self.skipped, self.bytes + self.skipped_bytes)
+    }
+}
+
+/// Whether `dest` can be left alone in update mode: it exists and was modified
+/// no earlier than `source`, and with `compare_size` also has the same length.
+fn is_up_to_date(source: &fs::Metadata, dest: &Path, compare_size: bool) -> bool {
+    let Ok(dest) = fs::symlink_metadata(dest) else {
+        return false;
+    };
+    let newer = match (source.modified(), dest.modified()) {
+        (Ok(source), Ok(dest)) => dest >= source,
+        _ => false,
+    };
+    newer && (!compare_size || dest.len() == source.len())
+}
+
+/// e.g. `Copying: 150/1200 file(s), 45.1 MiB/310.0 MiB (14%)`
+fn copy_progress_line(done: CopyStats, total: CopyStats) -> String {
+    let (files, bytes) = done.handled();
+    let percent = bytes
+        .saturating_mul(100)
+        .checked_div(total.bytes)
+        .or_else(|| (files * 100).checked_div(total.files))
+        .unwrap_or(100);
+    format!(
+        "Copying: {}/{} file(s), {}/{} ({}%)",
+        files,
+        total.files,
+        format_size(bytes),
+        format_size(total.bytes),
+        percent.min(100)
+    )
+}
+
+/// What to do with a file that already exists at the copy destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Conflict {
+    Overwrite,
+    Skip,
+}
+
+/// A conflict handler for copies that should simply replace existing files.
+fn overwrite_existing(_dest: &Path) -> Result<Conflict> {
+    Ok(Conflict::Overwrite)
+}
+
+/// Recursively copy a directory and its contents. Unfollowed symlinks are
+/// recreated as links; directories already copied once (cycles) are skipped.
+/// A destination inside the source tree is skipped, so the copy can't recurse
+/// into itself. In `CopyMode::All`, `on_conflict` decides for each file that
+/// already exists at the destination. `on_progress` is called with the running
+/// totals after every file.
+fn copy_directory_recursive(
+    source: &Path,
+    dest: &Path,
+    walker: &mut DirWalker,
+    mode: CopyMode,
+    on_conflict: &mut dyn FnMut(&Path) -> Result<Conflict>,
+    on_progress: &mut dyn FnMut(CopyStats),
+) -> Result<CopyStats> {
     fs::create_dir_all(dest)?;
-    for entry in fs::read_dir(source)? {
-        let entry = entry?;
-        let path = entry.path();
-        let dest_path = dest.join(entry.file_name());
-        if path.is_dir() {
-            copy_directory_recursive(&path, &dest_path)?;
-        } else {
-            fs::copy(&path, &dest_path)?;
+    // When copying into the source's own subtree, the copy must not be copied again
+    walker.mark_visited(dest)?;
+    let mut copier = TreeCopier {
+        walker,
+        mode,
+        on_conflict,
+        on_progress,
+        stats: CopyStats::default(),
+    };
+    copier.copy_tree(source, dest)?;
+    Ok(copier.stats)
+}
+
+/// State threaded through a recursive directory copy.
+struct TreeCopier<'a> {
+    walker: &'a mut DirWalker,
+    mode: CopyMode,
+    on_conflict: &'a mut dyn FnMut(&Path) -> Result<Conflict>,
+    on_progress: &'a mut dyn FnMut(CopyStats),
+    stats: CopyStats,
+}
+
+impl TreeCopier<'_> {
+    fn copy_tree(&mut self, source: &Path, dest: &Path) -> Result<()> {
+        fs::create_dir_all(dest)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            let path = entry.path();
+            let dest_path = dest.join(entry.file_name());
+            match self.walker.classify(&path)? {
+                WalkEntry::Directory => self.copy_tree(&path, &dest_path)?,
+                WalkEntry::Revisited => {}
+                WalkEntry::File => {
+                    let meta = fs::metadata(&path)?;
+                    if self.keep_existing(&dest_path, Some(&meta))? {
+                        self.stats.skipped += 1;
+                        self.stats.skipped_bytes += meta.len();
+                    } else {
+                        self.stats.bytes += fs::copy(&path, &dest_path)?;
+                        self.stats.files += 1;
+                    }
+                    (self.on_progress)(self.stats);
+                }
+                WalkEntry::Symlink => {
+                    let len = entry.metadata()?.len();
+                    if self.keep_existing(&dest_path, None)? {
+                        self.stats.skipped += 1;
+                        self.stats.skipped_bytes += len;
+                    } else {
+                        // A link can't be created over an existing entry
+                        if fs::symlink_metadata(&dest_path).is_ok_and(|m| !m.is_dir()) {
+                            fs::remove_file(&dest_path)?;
+                        }
+                        let target = fs::read_link(&path)?;
+                        make_symlink(&target, &dest_path, path.is_dir())?;
+                        self.stats.bytes += len;
+                        self.stats.files += 1;
+                    }
+                    (self.on_progress)(self.stats);
+                }
+            }
         }
+        Ok(())
+    }
+
+    /// Whether an entry already at `dest` stays as it is instead of being
+    /// replaced. `source` is the metadata of the file being copied, or `None`
+    /// for a symlink. Replacements are counted as overwritten.
+    fn keep_existing(&mut self, dest: &Path, source: Option<&fs::Metadata>) -> Result<bool> {
+        if fs::symlink_metadata(dest).is_err() {
+            return Ok(false);
+        }
+        let keep = match (self.mode, source) {
+            (CopyMode::Update { compare_size }, Some(source)) => {
+                is_up_to_date(source, dest, compare_size)
+            }
+            // In update mode an existing entry is never replaced by a link
+            (CopyMode::Update { .. }, None) => true,
+            (CopyMode::All, _) => (self.on_conflict)(dest)? == Conflict::Skip,
+        };
+        if !keep {
+            self.stats.overwritten += 1;
+        }
+        Ok(keep)
     }
-    Ok(())
 }
 
 /// 8) Move/rename file/directory (mv).
 fn move_or_rename_interactive(app_state: &mut AppState) -> Result<()> {
-    let source = read_user_input("Enter source file/directory: ")?;
-    let dest = read_user_input("Enter new path/filename: ")?;
+    // Relative paths are resolved by the OS, i.e. against the process directory
+    let base_dir = std::env::current_dir().context("Failed to get current directory")?;
+    let source = read_path_with_completion("Enter source file/directory: ", &base_dir)?;
+    let dest = read_path_with_completion("Enter new path/filename: ", &base_dir)?;
 
     let source_path = PathBuf::from(source.trim());
     let dest_path = PathBuf::from(dest.trim());
@@ -721,13 +2221,28 @@ fn move_or_rename_interactive(app_state: &mut AppState) -> Result<()> {
         return Ok(());
     }
 
+    let paths = format!("{:?} -> {:?}", source_path, dest_path);
+    if app_state.dry_run {
+        app_state.log_dry_run(format!("move {}", paths));
+        return Ok(());
+    }
     match fs::rename(&source_path, &dest_path) {
-        Ok(_) => app_state
-            .log_lines
-            .push("Move/rename succeeded.".to_string()),
-        Err(e) => app_state
-            .log_lines
-            .push(format!("Move/rename failed: {}", e)),
+        Ok(_) => {
+            app_state
+                .log_lines
+                .push("Move/rename succeeded.".to_string());
+            app_state.record_operation("move", &paths, "ok");
+            app_state.push_undo(Operation::Move {
+                from: source_path,
+                to: dest_path,
+            });
+        }
+        Err(e) => {
+            app_state
+                .log_lines
+                .push(format!("Move/rename failed: {}", e));
+            app_state.record_operation("move", &paths, &format!("error: {e}"));
+        }
     }
     Ok(())
 }
@@ -744,26 +2259,29 @@ fn delete_interactive(app_state: &mut AppState) -> Result<()> {
         return Ok(());
     }
 
+    // Show what is about to go, so a stray path doesn't silently wipe a large tree
+    let summary = match describe_delete_target(&target_path) {
+        Ok((size, files)) => DeleteSummary::new(size, files),
+        Err(e) => DeleteSummary {
+            text: format!("size unknown: {e}"),
+            large: false,
+        },
+    };
+    if app_state.dry_run {
+        app_state.log_dry_run(format!("delete {:?} ({})", target_path, summary.text));
+        return Ok(());
+    }
     let confirm = read_user_input(&format!(
-        "Are you sure you want to delete {:?}? (y/n): ",
-        target_path
+        "Are you sure you want to delete {:?} ({})? (y/n): ",
+        target_path,
+        summary.styled()
     ))?;
     if matches_yes(&confirm) {
-        if target_path.is_dir() {
-            match fs::remove_dir_all(&target_path) {
-                Ok(_) => app_state.log_lines.push("Directory deleted.".to_string()),
-                Err(e) => app_state
-                    .log_lines
-                    .push(format!("Failed to delete directory: {}", e)),
-            }
-        } else {
-            match fs::remove_file(&target_path) {
-                Ok(_) => app_state.log_lines.push("File deleted.".to_string()),
-                Err(e) => app_state
-                    .log_lines
-                    .push(format!("Failed to delete file: {}", e)),
-            }
-        }
+        // Moving to the trash is the default, so a mistake can be undone
+        let permanent = matches_yes(&read_user_input(
+            "Permanent delete? (y/n, default n moves it to the trash): ",
+        )?);
+        delete_path(app_state, &target_path, permanent);
     } else {
         app_state
             .log_lines
@@ -772,6 +2290,112 @@ fn delete_interactive(app_state: &mut AppState) -> Result<()> {
     Ok(())
 }
 
+/// Moves `target_path` to the trash (or removes it for good), logging the
+/// outcome and recording how to undo it. Returns whether it is gone.
+fn delete_path(app_state: &mut AppState, target_path: &Path, permanent: bool) -> bool {
+    let paths = format!("{:?}", target_path);
+    // The trash records absolute paths, so resolve it while it still exists
+    let original = absolute_path(target_path);
+    let (message, outcome) = if permanent {
+        let (message, outcome) = remove_permanently(target_path);
+        (message, format!("permanent: {outcome}"))
+    } else {
+        match trash::delete(target_path) {
+            Ok(()) => {
+                app_state.push_undo(Operation::Trash {
+                    item_id: find_trash_item(&original),
+                    original,
+                });
+                ("Moved to trash.".to_string(), "trash: ok".to_string())
+            }
+            Err(e) => {
+                // e.g. network mounts, or no trash on this platform
+                app_state.log_lines.push(format!(
+                    "Warning: could not move to trash ({e}); deleting permanently, this cannot be undone."
+                ));
+                let (message, outcome) = remove_permanently(target_path);
+                (message, format!("permanent (trash failed: {e}): {outcome}"))
+            }
+        }
+    };
+    if outcome.starts_with("permanent") && !target_path.exists() {
+        app_state.push_undo(Operation::Irreversible {
+            description: format!("permanent delete of {:?}", target_path),
+        });
+    }
+    app_state.log_lines.push(message);
+    app_state.record_operation("delete", &paths, &outcome);
+    target_path.symlink_metadata().is_err()
+}
+
+/// Irreversibly removes a file or directory tree, returning the log message and outcome.
+fn remove_permanently(path: &Path) -> (String, String) {
+    if path.is_dir() {
+        match fs::remove_dir_all(path) {
+            Ok(_) => (
+                "Directory permanently deleted.".to_string(),
+                "ok".to_string(),
+            ),
+            Err(e) => (
+                format!("Failed to delete directory: {}", e),
+                format!("error: {e}"),
+            ),
+        }
+    } else {
+        match fs::remove_file(path) {
+            Ok(_) => ("File permanently deleted.".to_string(), "ok".to_string()),
+            Err(e) => (
+                format!("Failed to delete file: {}", e),
+                format!("error: {e}"),
+            ),
+        }
+    }
+}
+
+/// Deletions at or above either threshold are highlighted in the confirmation prompt.
+const LARGE_DELETE_BYTES: u64 = 100 * 1024 * 1024;
+const LARGE_DELETE_FILES: u64 = 1000;
+
+/// e.g. `1.2 MiB in 14 file(s)`, marked when it reaches the large-deletion thresholds.
+struct DeleteSummary {
+    text: String,
+    large: bool,
+}
+
+impl DeleteSummary {
+    fn new(size: u64, files: u64) -> Self {
+        let large = size >= LARGE_DELETE_BYTES || files >= LARGE_DELETE_FILES;
+        let mut text = format!("{} in {} file(s)", format_size(size), files);
+        if large {
+            text.push_str(" - LARGE DELETION");
+        }
+        Self { text, large }
+    }
+
+    /// The text for a terminal prompt, in red when the deletion is large.
+    /// The log pane gets the plain `text`.
+    fn styled(&self) -> String {
+        if self.large {
+            self.text.as_str().red().bold().to_string()
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// Total size and file count of a delete target (a single file counts as one).
+fn describe_delete_target(path: &Path) -> Result<(u64, u64)> {
+    let meta = fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        // `remove_dir_all` never follows links, so neither does the count
+        let mut walker = DirWalker::new(path, false);
+        let stats = compute_directory_stats(path, &mut walker)?;
+        Ok((stats.size, stats.files))
+    } else {
+        Ok((meta.len(), 1))
+    }
+}
+
 /// 10) Duplicate file/directory quickly by adding `_copy` or similar suffix.
 fn duplicate_interactive(app_state: &mut AppState) -> Result<()> {
     let source = read_user_input("Enter file/directory to duplicate: ")?;
@@ -802,14 +2426,42 @@ fn duplicate_interactive(app_state: &mut AppState) -> Result<()> {
     };
     duplicate_path.set_file_name(new_name);
 
-    if source_path.is_dir() {
-        copy_directory_recursive(&source_path, &duplicate_path)?;
+    let paths = format!("{:?} -> {:?}", source_path, duplicate_path);
+    if app_state.dry_run {
+        let what = dry_run_copy_summary(&source_path, app_state.follow_symlinks);
+        app_state.log_dry_run(format!("duplicate {} {}", what, paths));
+        return Ok(());
+    }
+    let result = if source_path.is_dir() {
+        let mut walker = DirWalker::new(&source_path, app_state.follow_symlinks);
+        copy_directory_recursive(
+            &source_path,
+            &duplicate_path,
+            &mut walker,
+            CopyMode::All,
+            &mut overwrite_existing,
+            &mut |_| {},
+        )
+        .map(|_| ())
     } else {
-        fs::copy(&source_path, &duplicate_path)?;
+        fs::copy(&source_path, &duplicate_path)
+            .map(|_| ())
+            .map_err(Into::into)
+    };
+    if let Err(e) = result {
+        app_state
+            .log_lines
+            .push(format!("Duplicate failed: {:#}", e));
+        app_state.record_operation("duplicate", &paths, &format!("error: {e:#}"));
+        return Ok(());
     }
     app_state
         .log_lines
         .push(format!("Duplicate created at {:?}", duplicate_path));
+    app_state.record_operation("duplicate", &paths, "ok");
+    app_state.push_undo(Operation::Copy {
+        created: duplicate_path,
+    });
 
     Ok(())
 }
@@ -830,69 +2482,204 @@ fn organize_files_interactive(app_state: &mut AppState) -> Result<()> {
     }
 
     let method_str = read_user_input(
-        "Organization Methods:\n  1) By Extension\n  2) By Date\n  3) By Size\nSelect a method (1/2/3): ",
+        "Organization Methods:\n  1) By Extension\n  2) By Date\n  3) By Size\n  4) By Type (file contents)\nSelect a method (1/2/3/4): ",
     )?;
 
-    let dry_run_str = read_user_input("Dry Run? (y/n): ")?;
-    let dry_run = matches_yes(&dry_run_str);
+    // Global dry-run mode skips the question
+    let dry_run = app_state.dry_run || matches_yes(&read_user_input("Dry Run? (y/n): ")?);
+
+    let exclude_input = read_user_input(
+        "Exclude patterns (comma-separated globs, e.g. .git, node_modules, *.lock; blank for none): ",
+    )?;
+    let exclude = match ExcludeGlobs::parse(&exclude_input) {
+        Ok(exclude) => exclude,
+        Err(e) => {
+            app_state.log_lines.push(format!("Error: {:#}", e));
+            return Ok(());
+        }
+    };
 
-    let files = collect_files(&input_dir)?;
+    let mut walker = DirWalker::new(&input_dir, app_state.follow_symlinks);
+    let mut skipped = Vec::new();
+    let files =
+        collect_files_excluding(&input_dir, &input_dir, &mut walker, &exclude, &mut skipped)?;
+    app_state.log_lines.extend(
+        skipped
+            .iter()
+            .map(|(path, pattern)| format!("{:?} [skipped: matched {}]", path, pattern)),
+    );
 
-    match method_str.trim() {
-        "1" => {
-            for e in &files {
-                organize_by_extension(e, &input_dir, dry_run, app_state)?;
-            }
+    type Organizer = Box<dyn Fn(&fs::DirEntry, &Path, bool, &mut AppState) -> Result<()>>;
+    let (organize, done): (Organizer, &str) = match method_str.trim() {
+        "1" => (Box::new(organize_by_extension), "Organized by extension!"),
+        "2" => (Box::new(organize_by_date), "Organized by date!"),
+        "3" => {
+            let thresholds = read_user_input(
+                "Size thresholds (comma-separated, e.g. 500K, 2G; blank for 1 MiB, 100 MiB): ",
+            )?;
+            let labels = read_user_input(
+                "Bucket labels, one more than the thresholds (comma-separated; blank for defaults): ",
+            )?;
+            let buckets = match SizeBuckets::parse(&thresholds, &labels) {
+                Ok(buckets) => buckets,
+                Err(e) => {
+                    app_state.log_lines.push(format!("Error: {:#}", e));
+                    return Ok(());
+                }
+            };
             app_state
                 .log_lines
-                .push("Organized by extension!".to_string());
-        }
-        "2" => {
-            for e in &files {
-                organize_by_date(e, &input_dir, dry_run, app_state)?;
-            }
-            app_state.log_lines.push("Organized by date!".to_string());
-        }
-        "3" => {
-            for e in &files {
-                organize_by_size(e, &input_dir, dry_run, app_state)?;
-            }
-            app_state.log_lines.push("Organized by size!".to_string());
+                .push(format!("Size buckets: {}", buckets.describe()));
+            let organize = move |entry: &fs::DirEntry, root: &Path, dry_run, app_state: &mut _| {
+                organize_by_size(entry, root, dry_run, app_state, &buckets)
+            };
+            (Box::new(organize), "Organized by size!")
         }
+        "4" => (Box::new(organize_by_type), "Organized by type!"),
         _ => {
             app_state
                 .log_lines
                 .push("Invalid method chosen. Returning to main menu.".to_string());
+            return Ok(());
         }
-    }
+    };
+
+    // All the moves are undone together, even if organizing stopped partway
+    app_state.begin_undo_group();
+    let result = files
+        .iter()
+        .try_for_each(|e| organize(e, &input_dir, dry_run, app_state));
+    app_state.end_undo_group(format!("organize of {:?}", input_dir));
+    result?;
+    app_state.log_lines.push(done.to_string());
 
     Ok(())
 }
 
 /// Recursively collects files (not directories) from the given directory.
-fn collect_files(dir: &Path) -> Result<Vec<fs::DirEntry>> {
+/// Unfollowed symlinks are collected as entries of their own.
+fn collect_files(dir: &Path, walker: &mut DirWalker) -> Result<Vec<fs::DirEntry>> {
+    collect_files_excluding(dir, dir, walker, &ExcludeGlobs::default(), &mut Vec::new())
+}
+
+/// `collect_files` below `root`, leaving out entries that match `exclude`.
+/// A matching directory is pruned without being read. Each excluded path is
+/// added to `skipped` together with the pattern it matched.
+fn collect_files_excluding(
+    root: &Path,
+    dir: &Path,
+    walker: &mut DirWalker,
+    exclude: &ExcludeGlobs,
+    skipped: &mut Vec<(PathBuf, String)>,
+) -> Result<Vec<fs::DirEntry>> {
     let mut files = Vec::new();
-    for entry in fs::read_dir(dir).context("read_dir failed")? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            files.extend(collect_files(&path)?);
-        } else {
+    visit_files(
+        root,
+        dir,
+        walker,
+        exclude,
+        skipped,
+        &mut |entry| {
             files.push(entry);
-        }
-    }
+            Ok(())
+        },
+        &mut |_, e| Err(e).context("read_dir failed"),
+    )?;
     Ok(files)
 }
 
-fn organize_by_extension(
-    entry: &fs::DirEntry,
-    root_dir: &Path,
-    dry_run: bool,
-    app_state: &mut AppState,
+/// Walks like `collect_files_excluding`, handing each file to `visit` as
+/// soon as it is found instead of collecting them first. A directory that
+/// can't be read goes to `on_unreadable`, which decides whether the walk
+/// carries on (`Ok`) or stops.
+fn visit_files(
+    root: &Path,
+    dir: &Path,
+    walker: &mut DirWalker,
+    exclude: &ExcludeGlobs,
+    skipped: &mut Vec<(PathBuf, String)>,
+    visit: &mut dyn FnMut(fs::DirEntry) -> Result<()>,
+    on_unreadable: &mut dyn FnMut(&Path, io::Error) -> Result<()>,
 ) -> Result<()> {
-    let path = entry.path();
-    if let Some(ext_os) = path.extension() {
-        let extension = ext_os.to_string_lossy();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => return on_unreadable(dir, e),
+    };
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                on_unreadable(dir, e)?;
+                continue;
+            }
+        };
+        let path = entry.path();
+        if let Some(pattern) = exclude.matched(root, &path) {
+            skipped.push((path, pattern.to_string()));
+            continue;
+        }
+        match walker.classify(&path)? {
+            WalkEntry::Directory => {
+                visit_files(root, &path, walker, exclude, skipped, visit, on_unreadable)?
+            }
+            WalkEntry::Revisited => {}
+            WalkEntry::File | WalkEntry::Symlink => visit(entry)?,
+        }
+    }
+    Ok(())
+}
+
+/// Glob patterns for entries the organizer leaves alone. Each pattern is
+/// tried against the entry's name and its path relative to the organized
+/// directory, so `node_modules`, `*.lock`, and `docs/*.md` all work.
+#[derive(Default)]
+struct ExcludeGlobs {
+    patterns: Vec<String>,
+    set: GlobSet,
+}
+
+impl ExcludeGlobs {
+    /// Parses a comma-separated list like `.git, node_modules/, *.lock`.
+    fn parse(input: &str) -> Result<Self> {
+        let patterns: Vec<String> = input
+            .split(',')
+            .map(|p| p.trim().trim_end_matches('/'))
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect();
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &patterns {
+            builder
+                .add(Glob::new(pattern).with_context(|| format!("invalid pattern {:?}", pattern))?);
+        }
+        Ok(Self {
+            set: builder.build()?,
+            patterns,
+        })
+    }
+
+    /// The first pattern (in the order given) matching `path`, if any.
+    fn matched(&self, root: &Path, path: &Path) -> Option<&str> {
+        if self.patterns.is_empty() {
+            return None;
+        }
+        let mut hits = self.set.matches(path.strip_prefix(root).unwrap_or(path));
+        if let Some(name) = path.file_name() {
+            hits.extend(self.set.matches(name));
+        }
+        hits.into_iter().min().map(|i| self.patterns[i].as_str())
+    }
+}
+
+fn organize_by_extension(
+    entry: &fs::DirEntry,
+    root_dir: &Path,
+    dry_run: bool,
+    app_state: &mut AppState,
+) -> Result<()> {
+    let path = entry.path();
+    if let Some(ext_os) = path.extension() {
+        let extension = ext_os.to_string_lossy();
         let target_dir = root_dir.join("by_extension").join(extension.to_lowercase());
         move_file_or_dry_run(&path, &target_dir, dry_run, app_state)?;
     } else {
@@ -918,29 +2705,158 @@ fn organize_by_date(
     Ok(())
 }
 
+/// Size cutoffs for organizing by size: a file goes to the first bucket whose
+/// threshold it is below, or to the last bucket if it is below none of them.
+#[derive(Debug, PartialEq)]
+struct SizeBuckets {
+    /// Strictly increasing, in bytes
+    thresholds: Vec<u64>,
+    /// One more than `thresholds`
+    labels: Vec<String>,
+}
+
+impl Default for SizeBuckets {
+    fn default() -> Self {
+        Self {
+            // 1 MiB and 100 MiB, the same units `parse_size_threshold` uses
+            thresholds: vec![1024 * 1024, 100 * 1024 * 1024],
+            labels: ["small", "medium", "large"].map(String::from).to_vec(),
+        }
+    }
+}
+
+impl SizeBuckets {
+    /// Parses thresholds like `500K, 2G` (see `parse_size_threshold`) and
+    /// matching labels. Blank thresholds keep the defaults; blank labels
+    /// become `small`/`medium`/`large` for two thresholds, or names such as
+    /// `under_500K`, `500K_to_2G`, and `over_2G` otherwise.
+    fn parse(thresholds: &str, labels: &str) -> Result<Self> {
+        let split = |input: &str| -> Vec<String> {
+            input
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        };
+        let sizes = split(thresholds);
+        let labels = split(labels);
+        if sizes.is_empty() {
+            let default = Self::default();
+            if labels.is_empty() {
+                return Ok(default);
+            }
+            return Self::new(default.thresholds, labels);
+        }
+
+        let thresholds = sizes
+            .iter()
+            .map(|s| parse_size_threshold(s).ok_or_else(|| anyhow!("invalid size {:?}", s)))
+            .collect::<Result<Vec<u64>>>()?;
+        if let Some(pair) = thresholds.windows(2).find(|pair| pair[0] >= pair[1]) {
+            bail!(
+                "thresholds must be strictly increasing, but {} is not below {}",
+                format_size(pair[0]),
+                format_size(pair[1])
+            );
+        }
+        let labels = if !labels.is_empty() {
+            labels
+        } else if sizes.len() == 2 {
+            Self::default().labels
+        } else {
+            let mut names = vec![format!("under_{}", sizes[0])];
+            names.extend(
+                sizes
+                    .windows(2)
+                    .map(|pair| format!("{}_to_{}", pair[0], pair[1])),
+            );
+            names.push(format!("over_{}", sizes[sizes.len() - 1]));
+            names
+        };
+        Self::new(thresholds, labels)
+    }
+
+    fn new(thresholds: Vec<u64>, labels: Vec<String>) -> Result<Self> {
+        if labels.len() != thresholds.len() + 1 {
+            bail!(
+                "{} threshold(s) need {} labels, but {} were given",
+                thresholds.len(),
+                thresholds.len() + 1,
+                labels.len()
+            );
+        }
+        if let Some(label) = labels
+            .iter()
+            .find(|l| l.contains(['/', '\\']) || *l == "." || *l == "..")
+        {
+            bail!("{:?} can't be used as a folder name", label);
+        }
+        if let Some(label) = labels
+            .iter()
+            .enumerate()
+            .find_map(|(i, l)| labels[..i].contains(l).then_some(l))
+        {
+            bail!("label {:?} is used twice", label);
+        }
+        Ok(Self { thresholds, labels })
+    }
+
+    /// The label for a file of `size` bytes.
+    fn label(&self, size: u64) -> &str {
+        let index = self
+            .thresholds
+            .iter()
+            .position(|&limit| size < limit)
+            .unwrap_or(self.thresholds.len());
+        &self.labels[index]
+    }
+
+    /// E.g. `tiny < 500.0 KiB <= mid < 2.0 GiB <= huge`.
+    fn describe(&self) -> String {
+        let mut text = self.labels[0].clone();
+        for (limit, label) in self.thresholds.iter().zip(&self.labels[1..]) {
+            text.push_str(&format!(" < {} <= {}", format_size(*limit), label));
+        }
+        text
+    }
+}
+
 fn organize_by_size(
     entry: &fs::DirEntry,
     root_dir: &Path,
     dry_run: bool,
     app_state: &mut AppState,
+    buckets: &SizeBuckets,
 ) -> Result<()> {
     let path = entry.path();
     let metadata = fs::metadata(&path)?;
-    let file_size = metadata.len();
-
-    let size_label = if file_size < 1_000_000 {
-        "small"
-    } else if file_size < 100_000_000 {
-        "medium"
-    } else {
-        "large"
-    };
+    let size_label = buckets.label(metadata.len());
 
     let target_dir = root_dir.join("by_size").join(size_label);
     move_file_or_dry_run(&path, &target_dir, dry_run, app_state)?;
     Ok(())
 }
 
+/// Sorts by detected content type rather than extension, so a PNG named
+/// `.jpg` still lands in `images` and a renamed archive in `archives`.
+fn organize_by_type(
+    entry: &fs::DirEntry,
+    root_dir: &Path,
+    dry_run: bool,
+    app_state: &mut AppState,
+) -> Result<()> {
+    let path = entry.path();
+    let detected = detect_file_type(&path);
+    // Only `infer` matches carry an extension; anything it couldn't sniff (plain
+    // text, unknown binaries) is bucketed by its file name instead
+    if detected.extension.is_none() {
+        return organize_by_extension(entry, root_dir, dry_run, app_state);
+    }
+    let target_dir = root_dir.join("by_type").join(detected.category);
+    move_file_or_dry_run(&path, &target_dir, dry_run, app_state)?;
+    Ok(())
+}
+
 /// Move file to target dir, or log a dry-run message only.
 fn move_file_or_dry_run(
     path: &Path,
@@ -949,43 +2865,2770 @@ fn move_file_or_dry_run(
     app_state: &mut AppState,
 ) -> Result<()> {
     if !dry_run {
-        fs::create_dir_all(target_dir)?;
-        let target_path = target_dir.join(
-            path.file_name()
-                .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No filename found"))?,
+        let name = path
+            .file_name()
+            .ok_or_else(|| io::Error::other("No filename found"))?;
+        let Some(target_path) = move_into_dir(path, target_dir)? else {
+            return Ok(());
+        };
+        app_state.record_operation(
+            "organize",
+            &format!("{:?} -> {:?}", path, target_path),
+            "ok",
         );
-        fs::rename(path, &target_path)?;
+        let renamed = target_path.file_name().filter(|n| *n != name);
+        app_state.log_lines.push(match renamed {
+            Some(new_name) => format!(
+                "Moved {:?} to {:?} as {:?} (name already taken)",
+                name, target_dir, new_name
+            ),
+            None => format!("Moved {:?} to {:?}", name, target_dir),
+        });
+        app_state.push_undo(Operation::Move {
+            from: path.to_path_buf(),
+            to: target_path,
+        });
+    } else {
         app_state.log_lines.push(format!(
-            "Moved {:?} to {:?}",
+            "[DRY RUN] Would move {:?} to {:?}",
             path.file_name().unwrap(),
             target_dir
         ));
+    }
+    Ok(())
+}
+
+/// Moves `path` into `target_dir` without replacing anything already there.
+/// When the name is taken, a numeric suffix is added before the extension
+/// (`notes.txt` becomes `notes_1.txt`, then `notes_2.txt`, ...). Returns where
+/// the file ended up, or `None` if it is already in `target_dir`.
+fn move_into_dir(path: &Path, target_dir: &Path) -> Result<Option<PathBuf>> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::other("No filename found"))?;
+    fs::create_dir_all(target_dir)?;
+    let mut target_path = target_dir.join(name);
+    if target_path == path {
+        return Ok(None);
+    }
+    let stem = Path::new(name)
+        .file_stem()
+        .unwrap_or(name)
+        .to_string_lossy();
+    let extension = Path::new(name)
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let mut n = 1;
+    while fs::symlink_metadata(&target_path).is_ok() {
+        target_path = target_dir.join(format!("{}_{}{}", stem, n, extension));
+        n += 1;
+    }
+    fs::rename(path, &target_path)?;
+    Ok(Some(target_path))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Batch Rename
+////////////////////////////////////////////////////////////////////////////////
+
+/// A rename rule applied to every selected file name.
+enum RenameRule {
+    /// Replace every occurrence of `find` with `replace` in the file name
+    FindReplace { find: String, replace: String },
+    /// Add text before the name and/or before the extension
+    PrefixSuffix { prefix: String, suffix: String },
+    /// Replace the stem with a pattern whose `#` run becomes a zero-padded counter
+    Numbering { pattern: String, start: usize },
+}
+
+impl RenameRule {
+    /// Computes the new file name for the `index`-th selected file.
+    fn apply(&self, name: &str, index: usize) -> String {
+        let (stem, ext) = split_extension(name);
+        match self {
+            RenameRule::FindReplace { find, replace } => name.replace(find.as_str(), replace),
+            RenameRule::PrefixSuffix { prefix, suffix } => {
+                format!("{prefix}{stem}{suffix}{ext}")
+            }
+            RenameRule::Numbering { pattern, start } => {
+                format!("{}{ext}", expand_number_pattern(pattern, start + index))
+            }
+        }
+    }
+}
+
+/// Splits `name` into stem and extension (the extension keeps its leading dot).
+/// Dotfiles like `.bashrc` are treated as having no extension.
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(i) if i > 0 => name.split_at(i),
+        _ => (name, ""),
+    }
+}
+
+/// Expands the first run of `#` in `pattern` to `number`, zero-padded to the
+/// run's width (`photo_###` -> `photo_007`). Without `#`, the number is appended.
+fn expand_number_pattern(pattern: &str, number: usize) -> String {
+    match pattern.find('#') {
+        Some(start) => {
+            let width = pattern[start..].chars().take_while(|&c| c == '#').count();
+            format!(
+                "{}{:0width$}{}",
+                &pattern[..start],
+                number,
+                &pattern[start + width..],
+                width = width
+            )
+        }
+        None => format!("{pattern}{number}"),
+    }
+}
+
+/// 14) Batch rename files in the current directory selected by a glob.
+fn batch_rename_interactive(app_state: &mut AppState) -> Result<()> {
+    app_state.log_lines.push("=== Batch Rename ===".to_string());
+    let glob_str = read_user_input("Select files by glob in the current directory (e.g. *.jpg): ")?;
+    let pattern = match Glob::new(glob_str.trim()) {
+        Ok(p) => p.compile_matcher(),
+        Err(e) => {
+            app_state
+                .log_lines
+                .push(format!("Invalid glob pattern: {}", e));
+            return Ok(());
+        }
+    };
+
+    let mut names: Vec<String> = fs::read_dir(&app_state.current_dir)
+        .context("read_dir failed")?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| pattern.is_match(name))
+        .collect();
+    names.sort();
+
+    if names.is_empty() {
+        app_state
+            .log_lines
+            .push(format!("No files match {:?}.", pattern.glob().glob()));
+        return Ok(());
+    }
+
+    let rule_str = read_user_input(
+        "Rename rules:\n  1) Find/replace\n  2) Add prefix/suffix\n  3) Sequential numbering (e.g. photo_###)\nSelect a rule (1/2/3): ",
+    )?;
+    let rule = match rule_str.trim() {
+        "1" => {
+            let find = read_user_input("Text to find: ")?;
+            let replace = read_user_input("Replace with: ")?;
+            let find = find.trim_end_matches(['\r', '\n']).to_string();
+            if find.is_empty() {
+                app_state
+                    .log_lines
+                    .push("Aborted: nothing to find.".to_string());
+                return Ok(());
+            }
+            RenameRule::FindReplace {
+                find,
+                replace: replace.trim_end_matches(['\r', '\n']).to_string(),
+            }
+        }
+        "2" => {
+            let prefix = read_user_input("Prefix (blank for none): ")?;
+            let suffix = read_user_input("Suffix, added before the extension (blank for none): ")?;
+            RenameRule::PrefixSuffix {
+                prefix: prefix.trim_end_matches(['\r', '\n']).to_string(),
+                suffix: suffix.trim_end_matches(['\r', '\n']).to_string(),
+            }
+        }
+        "3" => {
+            let pattern = read_user_input("Name pattern, # marks the number (e.g. photo_###): ")?;
+            let start = read_user_input("Start at (default 1): ")?;
+            let pattern = pattern.trim().to_string();
+            if pattern.is_empty() {
+                app_state
+                    .log_lines
+                    .push("Aborted: no pattern provided.".to_string());
+                return Ok(());
+            }
+            RenameRule::Numbering {
+                pattern,
+                start: start.trim().parse().unwrap_or(1),
+            }
+        }
+        _ => {
+            app_state
+                .log_lines
+                .push("Invalid rule chosen. Returning to main menu.".to_string());
+            return Ok(());
+        }
+    };
+
+    // Build the plan, skipping names the rule leaves unchanged
+    let plan: Vec<(String, String)> = names
+        .iter()
+        .enumerate()
+        .map(|(i, old)| (old.clone(), rule.apply(old, i)))
+        .filter(|(old, new)| old != new)
+        .collect();
+
+    if plan.is_empty() {
+        app_state
+            .log_lines
+            .push("The rule does not change any of the selected names.".to_string());
+        return Ok(());
+    }
+
+    // Before/after preview table
+    let width = plan
+        .iter()
+        .map(|(old, _)| old.chars().count())
+        .max()
+        .unwrap_or(0);
+    app_state.log_lines.push(format!(
+        "  {:<width$}    {}",
+        "BEFORE",
+        "AFTER",
+        width = width
+    ));
+    for (old, new) in &plan {
+        app_state
+            .log_lines
+            .push(format!("  {:<width$} -> {}", old, new, width = width));
+    }
+
+    if let Some(problem) = find_rename_collision(&app_state.current_dir, &plan) {
+        app_state
+            .log_lines
+            .push(format!("Aborted, nothing renamed: {}", problem));
+        return Ok(());
+    }
+
+    if app_state.dry_run {
+        app_state.log_dry_run(format!("rename the {} file(s) above", plan.len()));
+        return Ok(());
+    }
+
+    let confirm = read_user_input(&format!("Rename {} file(s)? (y/n): ", plan.len()))?;
+    if !matches_yes(&confirm) {
+        app_state
+            .log_lines
+            .push("Batch rename canceled.".to_string());
+        return Ok(());
+    }
+
+    let mut renamed = 0;
+    app_state.begin_undo_group();
+    for (old, new) in &plan {
+        let from = app_state.current_dir.join(old);
+        let to = app_state.current_dir.join(new);
+        let paths = format!("{:?} -> {:?}", from, to);
+        match fs::rename(&from, &to) {
+            Ok(_) => {
+                renamed += 1;
+                app_state.record_operation("batch rename", &paths, "ok");
+                app_state.push_undo(Operation::Move { from, to });
+            }
+            Err(e) => {
+                app_state
+                    .log_lines
+                    .push(format!("Failed to rename {:?}: {}", old, e));
+                app_state.record_operation("batch rename", &paths, &format!("error: {e}"));
+                break;
+            }
+        }
+    }
+    app_state.end_undo_group(format!("batch rename of {} file(s)", renamed));
+    app_state.log_lines.push(format!(
+        "Batch rename finished: {} of {} file(s) renamed.",
+        renamed,
+        plan.len()
+    ));
+    Ok(())
+}
+
+/// Returns a description of the first collision in a rename plan: an invalid
+/// name, two files mapping to the same name, or a target that already exists.
+fn find_rename_collision(dir: &Path, plan: &[(String, String)]) -> Option<String> {
+    let mut seen = std::collections::HashSet::new();
+    for (old, new) in plan {
+        if new.is_empty() || new.contains('/') || new.contains(std::path::MAIN_SEPARATOR) {
+            return Some(format!("{:?} would get the invalid name {:?}.", old, new));
+        }
+        if !seen.insert(new.as_str()) {
+            return Some(format!("more than one file would be named {:?}.", new));
+        }
+        if dir.join(new).exists() {
+            return Some(format!(
+                "{:?} would overwrite the existing file {:?}.",
+                old, new
+            ));
+        }
+    }
+    None
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Permissions (chmod)
+////////////////////////////////////////////////////////////////////////////////
+
+/// 16) View and change Unix permissions (chmod), optionally recursively.
+#[cfg(unix)]
+fn chmod_interactive(app_state: &mut AppState) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let input = read_user_input("Enter file/directory to change permissions: ")?;
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        app_state
+            .log_lines
+            .push("No path specified. chmod canceled.".to_string());
+        return Ok(());
+    }
+    let target = app_state.current_dir.join(trimmed);
+    let meta = match fs::metadata(&target) {
+        Ok(meta) => meta,
+        Err(e) => {
+            app_state
+                .log_lines
+                .push(format!("Error: cannot read {:?}: {}", target, e));
+            return Ok(());
+        }
+    };
+
+    let current = meta.permissions().mode() & 0o7777;
+    app_state.log_lines.push(format!(
+        "Current mode of {:?}: {} ({:04o})",
+        target,
+        symbolic_mode(current),
+        current
+    ));
+
+    let input = read_user_input(&format!(
+        "Current mode is {} ({:04o}). Enter new octal mode (e.g. 755), or blank to cancel: ",
+        symbolic_mode(current),
+        current
+    ))?;
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        app_state.log_lines.push("chmod canceled.".to_string());
+        return Ok(());
+    }
+    let Some(mode) = parse_octal_mode(trimmed) else {
+        app_state.log_lines.push(format!(
+            "Error: {:?} is not a valid octal mode (0000-7777).",
+            trimmed
+        ));
+        return Ok(());
+    };
+
+    let recursive = meta.is_dir()
+        && matches_yes(&read_user_input(&format!(
+            "Apply {} ({:04o}) recursively to everything inside {:?}? (y/n): ",
+            symbolic_mode(mode),
+            mode,
+            target
+        ))?);
+
+    if app_state.dry_run {
+        app_state.log_dry_run(format!(
+            "set {:?} to {} ({:04o}){}",
+            target,
+            symbolic_mode(mode),
+            mode,
+            if recursive { " recursively" } else { "" }
+        ));
+        return Ok(());
+    }
+
+    let mut changed = 0;
+    let mut errors = Vec::new();
+    set_mode(&target, mode, recursive, &mut changed, &mut errors);
+
+    let paths = format!(
+        "{:?} mode={:04o}{}",
+        target,
+        mode,
+        if recursive { " -R" } else { "" }
+    );
+    if errors.is_empty() {
+        app_state.log_lines.push(format!(
+            "Set mode {} ({:04o}) on {} item(s).",
+            symbolic_mode(mode),
+            mode,
+            changed
+        ));
+        app_state.record_operation("chmod", &paths, "ok");
     } else {
         app_state.log_lines.push(format!(
-            "[DRY RUN] Would move {:?} to {:?}",
-            path.file_name().unwrap(),
-            target_dir
+            "Set mode on {} item(s); {} failed:",
+            changed,
+            errors.len()
         ));
+        app_state
+            .log_lines
+            .extend(errors.iter().map(|e| format!("  {}", e)));
+        app_state.record_operation(
+            "chmod",
+            &paths,
+            &format!(
+                "error: {} of {} failed",
+                errors.len(),
+                changed + errors.len()
+            ),
+        );
     }
     Ok(())
 }
 
+#[cfg(not(unix))]
+fn chmod_interactive(app_state: &mut AppState) -> Result<()> {
+    app_state
+        .log_lines
+        .push("Permission editing is only supported on Unix.".to_string());
+    Ok(())
+}
+
+/// Applies `mode` to `path` and, when `recursive`, to everything below it.
+/// Symlinks inside the tree are skipped so chmod never escapes it.
+#[cfg(unix)]
+fn set_mode(
+    path: &Path,
+    mode: u32,
+    recursive: bool,
+    changed: &mut usize,
+    errors: &mut Vec<String>,
+) {
+    use std::os::unix::fs::PermissionsExt;
+
+    match fs::set_permissions(path, fs::Permissions::from_mode(mode)) {
+        Ok(()) => *changed += 1,
+        Err(e) => errors.push(format!("{:?}: {}", path, e)),
+    }
+    if !recursive || !path.is_dir() {
+        return;
+    }
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                let child = entry.path();
+                if entry.file_type().is_ok_and(|t| !t.is_symlink()) {
+                    set_mode(&child, mode, true, changed, errors);
+                }
+            }
+        }
+        Err(e) => errors.push(format!("{:?}: {}", path, e)),
+    }
+}
+
+/// Parses an octal mode such as `755` or `0644`.
+#[cfg(unix)]
+fn parse_octal_mode(input: &str) -> Option<u32> {
+    if input.len() > 4 || !input.chars().all(|c| ('0'..='7').contains(&c)) {
+        return None;
+    }
+    u32::from_str_radix(input, 8).ok()
+}
+
+/// Renders permission bits like `ls -l`, e.g. `rwxr-xr-x` (with s/S/t/T for special bits).
+#[cfg(unix)]
+fn symbolic_mode(mode: u32) -> String {
+    let mut out = String::with_capacity(9);
+    // (read, write, execute, special bit, special char) for user, group, other
+    let classes = [
+        (0o400, 0o200, 0o100, 0o4000, 's'),
+        (0o040, 0o020, 0o010, 0o2000, 's'),
+        (0o004, 0o002, 0o001, 0o1000, 't'),
+    ];
+    for (r, w, x, special, special_char) in classes {
+        out.push(if mode & r != 0 { 'r' } else { '-' });
+        out.push(if mode & w != 0 { 'w' } else { '-' });
+        out.push(match (mode & x != 0, mode & special != 0) {
+            (true, true) => special_char,
+            (false, true) => special_char.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    out
+}
+
 ////////////////////////////////////////////////////////////////////////////////
-// Misc Helpers
+// Text Editor
 ////////////////////////////////////////////////////////////////////////////////
 
-/// Helper to interpret "y"/"yes" input as true, everything else as false.
-fn matches_yes(input: &str) -> bool {
-    let s = input.trim().to_lowercase();
-    s == "y" || s == "yes"
+/// Files larger than this are refused by the built-in editor.
+const MAX_EDIT_BYTES: u64 = 1024 * 1024;
+
+/// UTF-8 byte-order mark, kept on save when the original file had one.
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// Buffer and cursor state for the built-in text editor.
+struct TextEditor {
+    path: PathBuf,
+    lines: Vec<String>,
+    /// Cursor line and column (the column counts characters, not bytes)
+    row: usize,
+    col: usize,
+    /// First visible line and column
+    top: usize,
+    left: usize,
+    /// Text rows visible in the last draw, used for paging
+    page_height: usize,
+    /// Whether the file started with a UTF-8 byte-order mark
+    bom: bool,
+    /// Whether the file ended with a line break
+    trailing_newline: bool,
+    modified: bool,
+    /// Set by Esc on a modified buffer; a second Esc discards the changes
+    confirm_discard: bool,
+    /// Whether the buffer was written at least once this session
+    saved: bool,
+    /// In dry-run mode, saving only pretends to write the file
+    dry_run: bool,
+    status: String,
 }
 
-/// A blocking function to read user input from stdin.
-fn read_user_input(prompt_msg: &str) -> Result<String> {
-    print!("{prompt_msg}{}", LINE_ENDING);
-    io::stdout().flush()?;
+impl TextEditor {
+    /// Loads a file, refusing anything too large, binary, or not UTF-8.
+    fn open(path: &Path, dry_run: bool) -> Result<Self> {
+        let meta = fs::metadata(path).with_context(|| format!("Cannot read {:?}", path))?;
+        if !meta.is_file() {
+            bail!("{:?} is not a regular file", path);
+        }
+        if meta.len() > MAX_EDIT_BYTES {
+            bail!(
+                "{:?} is too large to edit ({}, limit {})",
+                path,
+                format_size(meta.len()),
+                format_size(MAX_EDIT_BYTES)
+            );
+        }
 
-    let mut buf = String::new();
-    io::stdin().read_line(&mut buf)?;
-    Ok(buf)
+        let bytes = fs::read(path).with_context(|| format!("Cannot read {:?}", path))?;
+        if bytes.contains(&0) {
+            bail!("{:?} looks like a binary file", path);
+        }
+        let (bom, body) = match bytes.strip_prefix(UTF8_BOM) {
+            Some(rest) => (true, rest),
+            None => (false, &bytes[..]),
+        };
+        let text =
+            std::str::from_utf8(body).map_err(|_| anyhow!("{:?} is not valid UTF-8 text", path))?;
+
+        let mut lines: Vec<String> = text.lines().map(str::to_string).collect();
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            lines,
+            row: 0,
+            col: 0,
+            top: 0,
+            left: 0,
+            page_height: 1,
+            bom,
+            trailing_newline: text.ends_with('\n'),
+            modified: false,
+            confirm_discard: false,
+            saved: false,
+            dry_run,
+            status: if dry_run {
+                "DRY RUN: Ctrl+S won't write the file | Esc: close | arrows/PgUp/PgDn/Home/End: move"
+            } else {
+                "Ctrl+S: save | Esc: close | arrows/PgUp/PgDn/Home/End: move"
+            }
+            .to_string(),
+        })
+    }
+
+    /// Writes the buffer back using the platform line ending, keeping the
+    /// original BOM and trailing line break.
+    fn save(&mut self) -> Result<()> {
+        let mut out = Vec::new();
+        if self.bom {
+            out.extend_from_slice(UTF8_BOM);
+        }
+        out.extend_from_slice(self.lines.join(LINE_ENDING).as_bytes());
+        if self.trailing_newline {
+            out.extend_from_slice(LINE_ENDING.as_bytes());
+        }
+        if !self.dry_run {
+            fs::write(&self.path, out).with_context(|| format!("Cannot write {:?}", self.path))?;
+        }
+        self.modified = false;
+        self.saved = true;
+        Ok(())
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// Byte offset of the cursor column in the current line.
+    fn byte_col(&self, col: usize) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices().nth(col).map_or(line.len(), |(i, _)| i)
+    }
+
+    fn insert_char(&mut self, c: char) {
+        let at = self.byte_col(self.col);
+        self.lines[self.row].insert(at, c);
+        self.col += 1;
+        self.modified = true;
+    }
+
+    fn insert_newline(&mut self) {
+        let at = self.byte_col(self.col);
+        let rest = self.lines[self.row].split_off(at);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+        self.modified = true;
+    }
+
+    fn backspace(&mut self) {
+        if self.col > 0 {
+            let at = self.byte_col(self.col - 1);
+            self.lines[self.row].remove(at);
+            self.col -= 1;
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&line);
+        } else {
+            return;
+        }
+        self.modified = true;
+    }
+
+    fn delete(&mut self) {
+        if self.col < self.line_len(self.row) {
+            let at = self.byte_col(self.col);
+            self.lines[self.row].remove(at);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        } else {
+            return;
+        }
+        self.modified = true;
+    }
+
+    /// Moves the cursor vertically, clamping the column to the new line.
+    fn move_rows(&mut self, delta: isize) {
+        let last = self.lines.len() as isize - 1;
+        self.row = (self.row as isize + delta).clamp(0, last) as usize;
+        self.col = self.col.min(self.line_len(self.row));
+    }
+
+    fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+        }
+    }
+
+    fn move_right(&mut self) {
+        if self.col < self.line_len(self.row) {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    /// Scrolls so the cursor stays inside a `height` x `width` viewport.
+    fn scroll_to_cursor(&mut self, height: usize, width: usize) {
+        self.page_height = height.max(1);
+        if self.row < self.top {
+            self.top = self.row;
+        } else if self.row >= self.top + self.page_height {
+            self.top = self.row + 1 - self.page_height;
+        }
+        let width = width.max(1);
+        if self.col < self.left {
+            self.left = self.col;
+        } else if self.col >= self.left + width {
+            self.left = self.col + 1 - width;
+        }
+    }
+
+    /// Applies one key press. Returns `true` when the editor should close.
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        let confirm_discard = std::mem::take(&mut self.confirm_discard);
+        match (code, modifiers) {
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                self.status = match self.save() {
+                    Ok(()) if self.dry_run => format!(
+                        "[DRY RUN] Would save {} line(s); the file is unchanged.",
+                        self.lines.len()
+                    ),
+                    Ok(()) => format!("Saved {} line(s).", self.lines.len()),
+                    Err(e) => format!("Save failed: {:#}", e),
+                };
+            }
+            (KeyCode::Esc, _) => {
+                if !self.modified || confirm_discard {
+                    return true;
+                }
+                self.confirm_discard = true;
+                self.status =
+                    "Unsaved changes! Ctrl+S to save, or Esc again to discard.".to_string();
+            }
+            (KeyCode::Char(c), m) if !m.contains(KeyModifiers::CONTROL) => self.insert_char(c),
+            (KeyCode::Tab, _) => (0..4).for_each(|_| self.insert_char(' ')),
+            (KeyCode::Enter, _) => self.insert_newline(),
+            (KeyCode::Backspace, _) => self.backspace(),
+            (KeyCode::Delete, _) => self.delete(),
+            (KeyCode::Left, _) => self.move_left(),
+            (KeyCode::Right, _) => self.move_right(),
+            (KeyCode::Up, _) => self.move_rows(-1),
+            (KeyCode::Down, _) => self.move_rows(1),
+            (KeyCode::PageUp, _) => self.move_rows(-(self.page_height as isize)),
+            (KeyCode::PageDown, _) => self.move_rows(self.page_height as isize),
+            (KeyCode::Home, _) => self.col = 0,
+            (KeyCode::End, _) => self.col = self.line_len(self.row),
+            _ => {}
+        }
+        false
+    }
+}
+
+/// 15) Edit a small text file in the built-in editor.
+fn edit_file_interactive(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app_state: &mut AppState,
+) -> Result<()> {
+    let input = read_user_input("Enter file to edit: ")?;
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        app_state
+            .log_lines
+            .push("No file specified. Edit canceled.".to_string());
+        return Ok(());
+    }
+
+    let mut editor = match TextEditor::open(&app_state.current_dir.join(trimmed), app_state.dry_run)
+    {
+        Ok(editor) => editor,
+        Err(e) => {
+            app_state.log_lines.push(format!("Error: {:#}", e));
+            return Ok(());
+        }
+    };
+    run_text_editor(terminal, &mut editor)?;
+
+    let paths = format!("{:?}", editor.path);
+    if editor.saved && editor.dry_run {
+        app_state.log_dry_run(format!(
+            "save {} ({} lines)",
+            editor.path.display(),
+            editor.lines.len()
+        ));
+    } else if editor.saved {
+        app_state.log_lines.push(format!(
+            "Saved {} ({} lines).",
+            editor.path.display(),
+            editor.lines.len()
+        ));
+        app_state.record_operation("edit", &paths, "ok");
+    } else {
+        app_state
+            .log_lines
+            .push(format!("Closed {} without saving.", editor.path.display()));
+    }
+    Ok(())
+}
+
+/// Full-screen editor loop; returns when the user closes the editor.
+fn run_text_editor(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    editor: &mut TextEditor,
+) -> Result<()> {
+    loop {
+        terminal.draw(|frame| draw_text_editor(frame, editor))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && editor.handle_key(key.code, key.modifiers) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn draw_text_editor(frame: &mut Frame, editor: &mut TextEditor) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(frame.area());
+
+    let text_area = chunks[0];
+    let height = text_area.height.saturating_sub(2) as usize;
+    let width = text_area.width.saturating_sub(2) as usize;
+    editor.scroll_to_cursor(height, width);
+
+    let lines: Vec<Line> = editor
+        .lines
+        .iter()
+        .skip(editor.top)
+        .take(height)
+        .map(|line| {
+            Line::from(
+                line.chars()
+                    .skip(editor.left)
+                    .take(width)
+                    .collect::<String>(),
+            )
+        })
+        .collect();
+    let title = format!(
+        " Edit: {}{} (line {}/{}, col {}) ",
+        editor.path.display(),
+        if editor.modified { " [modified]" } else { "" },
+        editor.row + 1,
+        editor.lines.len(),
+        editor.col + 1
+    );
+    let text = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(text, text_area);
+
+    let status_color = if editor.confirm_discard {
+        Color::Red
+    } else {
+        Color::Yellow
+    };
+    let status = Paragraph::new(editor.status.as_str())
+        .style(Style::default().fg(status_color))
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(status, chunks[1]);
+
+    frame.set_cursor_position((
+        text_area.x + 1 + (editor.col - editor.left) as u16,
+        text_area.y + 1 + (editor.row - editor.top) as u16,
+    ));
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Open with Default Application
+////////////////////////////////////////////////////////////////////////////////
+
+/// How long to wait for the launcher to report failure before assuming the
+/// file was handed off successfully.
+const OPEN_LAUNCH_GRACE: Duration = Duration::from_millis(750);
+
+/// 17) Open a file with the OS default handler (`xdg-open`, `open`, or `start`).
+fn open_with_default_app(app_state: &mut AppState) -> Result<()> {
+    let input = read_user_input("Enter file to open: ")?;
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        app_state
+            .log_lines
+            .push("No file specified. Open canceled.".to_string());
+        return Ok(());
+    }
+
+    let path = app_state.current_dir.join(trimmed);
+    if !path.exists() {
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} does not exist.", path));
+        return Ok(());
+    }
+
+    match launch_default_app(&path) {
+        Ok(()) => app_state.log_lines.push(format!(
+            "Opened {} with the default application.",
+            path.display()
+        )),
+        Err(e) => app_state
+            .log_lines
+            .push(format!("Could not open {}: {:#}", path.display(), e)),
+    }
+    Ok(())
+}
+
+/// Starts the platform launcher for `path`. Launchers normally exit at once,
+/// so a quick non-zero exit (e.g. `xdg-open` finding no handler) is reported
+/// as an error; one still running after the grace period is left alone.
+fn launch_default_app(path: &Path) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut c = std::process::Command::new("cmd");
+        // The empty string is `start`'s window title, so paths with spaces work
+        c.args(["/C", "start", ""]).arg(path);
+        c
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut c = std::process::Command::new("open");
+        c.arg(path);
+        c
+    };
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut c = std::process::Command::new("xdg-open");
+        c.arg(path);
+        c
+    };
+
+    // Keep the launcher's chatter from scribbling over the TUI
+    let mut child = command
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .with_context(|| format!("{:?} is not available", command.get_program()))?;
+
+    let deadline = std::time::Instant::now() + OPEN_LAUNCH_GRACE;
+    while std::time::Instant::now() < deadline {
+        if let Some(status) = child.try_wait()? {
+            if status.success() {
+                return Ok(());
+            }
+            bail!("no application is registered for this file type ({status})");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    // Reap the launcher whenever it does exit
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Empty Directories (cleanup)
+////////////////////////////////////////////////////////////////////////////////
+
+/// 19) Find empty directories under a path, list them, and offer to delete them.
+fn empty_directories_interactive(app_state: &mut AppState) -> Result<()> {
+    let path = read_user_input(&format!(
+        "Enter directory to search for empty directories (default: {}): ",
+        app_state.current_dir.display()
+    ))?;
+    let root = if path.trim().is_empty() {
+        app_state.current_dir.clone()
+    } else {
+        app_state.current_dir.join(path.trim())
+    };
+
+    if !root.is_dir() {
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} is not a valid directory.", root));
+        return Ok(());
+    }
+
+    // Symlinks are never followed: a link counts as content, so nothing
+    // outside the chosen tree decides what gets deleted
+    let mut walker = DirWalker::new(&root, false);
+    let mut empty = Vec::new();
+    find_empty_directories(&root, &mut walker, &mut empty)?;
+
+    if empty.is_empty() {
+        app_state
+            .log_lines
+            .push(format!("No empty directories found under {:?}.", root));
+        return Ok(());
+    }
+
+    // Sorted, a parent comes right before its children; deleting in reverse
+    // order therefore removes children first
+    empty.sort();
+    let header = format!(
+        "=== Empty directories under {} ({}) ===",
+        root.display(),
+        empty.len()
+    );
+    app_state.show_lines(
+        std::iter::once(header).chain(empty.iter().map(|dir| format!("  {}", dir.display()))),
+    );
+
+    if app_state.dry_run {
+        app_state.log_dry_run(format!("delete {} empty director(ies)", empty.len()));
+        return Ok(());
+    }
+    let confirm = read_user_input(&format!(
+        "Delete these {} empty director(ies)? (y/n): ",
+        empty.len()
+    ))?;
+    if !matches_yes(&confirm) {
+        app_state
+            .log_lines
+            .push("Empty directory cleanup canceled.".to_string());
+        return Ok(());
+    }
+
+    let mut removed = 0;
+    for dir in empty.iter().rev() {
+        // `remove_dir` refuses non-empty directories, so anything created
+        // since the scan is left alone
+        match fs::remove_dir(dir) {
+            Ok(_) => removed += 1,
+            Err(e) => app_state
+                .log_lines
+                .push(format!("Failed to delete {:?}: {}", dir, e)),
+        }
+    }
+    if removed > 0 {
+        app_state.push_undo(Operation::Irreversible {
+            description: format!("deletion of {} empty director(ies)", removed),
+        });
+    }
+    app_state.log_lines.push(format!(
+        "Deleted {} of {} empty director(ies).",
+        removed,
+        empty.len()
+    ));
+    app_state.record_operation(
+        "delete empty dirs",
+        &format!("{:?}", root),
+        &format!("removed {} of {}", removed, empty.len()),
+    );
+    Ok(())
+}
+
+/// Collects every directory below `dir` that holds no files, only (possibly
+/// nested) empty directories, and returns whether `dir` itself is empty.
+/// `dir` is never added to `found`.
+fn find_empty_directories(
+    dir: &Path,
+    walker: &mut DirWalker,
+    found: &mut Vec<PathBuf>,
+) -> Result<bool> {
+    let mut is_empty = true;
+    for entry in fs::read_dir(dir).with_context(|| format!("read_dir failed for {:?}", dir))? {
+        let path = entry?.path();
+        match walker.classify(&path)? {
+            WalkEntry::Directory => {
+                if find_empty_directories(&path, walker, found)? {
+                    found.push(path);
+                } else {
+                    is_empty = false;
+                }
+            }
+            // Files, links, and anything already walked keep the directory
+            WalkEntry::Revisited | WalkEntry::File | WalkEntry::Symlink => is_empty = false,
+        }
+    }
+    Ok(is_empty)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Large Files (cleanup)
+////////////////////////////////////////////////////////////////////////////////
+
+/// 20) List files above a size threshold, largest first, and offer to delete or move some.
+fn large_files_interactive(app_state: &mut AppState) -> Result<()> {
+    let path = read_user_input(&format!(
+        "Enter directory to search for large files (default: {}): ",
+        app_state.current_dir.display()
+    ))?;
+    let root = if path.trim().is_empty() {
+        app_state.current_dir.clone()
+    } else {
+        app_state.current_dir.join(path.trim())
+    };
+    if !root.is_dir() {
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} is not a valid directory.", root));
+        return Ok(());
+    }
+
+    let threshold_input = read_user_input(
+        "Minimum size (e.g. 100M, 1.5G, 500K; plain numbers are MiB, default 100M): ",
+    )?;
+    let threshold = if threshold_input.trim().is_empty() {
+        100 * 1024 * 1024
+    } else {
+        match parse_size_threshold(&threshold_input) {
+            Some(bytes) => bytes,
+            None => {
+                app_state.log_lines.push(format!(
+                    "Error: {:?} is not a valid size.",
+                    threshold_input.trim()
+                ));
+                return Ok(());
+            }
+        }
+    };
+
+    let mut walker = DirWalker::new(&root, app_state.follow_symlinks);
+    let large = find_large_files(&root, &mut walker, threshold)?;
+    if large.is_empty() {
+        app_state.log_lines.push(format!(
+            "No files of {} or more under {:?}.",
+            format_size(threshold),
+            root
+        ));
+        return Ok(());
+    }
+
+    let total: u64 = large.iter().map(|(_, size)| size).sum();
+    let header = format!(
+        "=== {} file(s) of {} or more under {} ({} total) ===",
+        large.len(),
+        format_size(threshold),
+        root.display(),
+        format_size(total)
+    );
+    app_state.show_lines(std::iter::once(header).chain(large.iter().enumerate().map(
+        |(i, (path, size))| {
+            format!(
+                "{:>4}) {:>10}  {}",
+                i + 1,
+                format_size(*size),
+                path.display()
+            )
+        },
+    )));
+
+    let selection =
+        read_user_input("Select files to clean up (e.g. 1,3-5 or 'all'; blank to finish): ")?;
+    if selection.trim().is_empty() {
+        return Ok(());
+    }
+    let Some(indices) = parse_selection(&selection, large.len()) else {
+        app_state.log_lines.push(format!(
+            "Error: {:?} is not a valid selection (numbers 1-{}).",
+            selection.trim(),
+            large.len()
+        ));
+        return Ok(());
+    };
+    let chosen: Vec<&(PathBuf, u64)> = indices.iter().map(|&i| &large[i]).collect();
+    let chosen_size: u64 = chosen.iter().map(|(_, size)| size).sum();
+
+    let action = read_user_input("Action: d) Delete  m) Move to a directory  (d/m): ")?;
+    match action.trim().to_lowercase().as_str() {
+        "d" => {
+            if app_state.dry_run {
+                app_state.log_dry_run(format!(
+                    "delete {} large file(s) ({})",
+                    chosen.len(),
+                    format_size(chosen_size)
+                ));
+                return Ok(());
+            }
+            let confirm = read_user_input(&format!(
+                "Delete {} file(s), freeing {}? (y/n): ",
+                chosen.len(),
+                format_size(chosen_size)
+            ))?;
+            if !matches_yes(&confirm) {
+                app_state
+                    .log_lines
+                    .push("Large file cleanup canceled.".to_string());
+                return Ok(());
+            }
+            let permanent = matches_yes(&read_user_input(
+                "Permanent delete? (y/n, default n moves them to the trash): ",
+            )?);
+            app_state.begin_undo_group();
+            let mut deleted = 0;
+            for (path, _) in chosen {
+                app_state.log_lines.push(format!("Deleting {:?}", path));
+                if delete_path(app_state, path, permanent) {
+                    deleted += 1;
+                }
+            }
+            app_state.end_undo_group(format!("delete of {} large file(s)", deleted));
+        }
+        "m" => {
+            let dest = read_user_input("Move to directory: ")?;
+            let dest_dir = app_state.current_dir.join(dest.trim());
+            if !dest_dir.is_dir() {
+                app_state
+                    .log_lines
+                    .push(format!("Error: {:?} is not a valid directory.", dest_dir));
+                return Ok(());
+            }
+            if app_state.dry_run {
+                app_state.log_dry_run(format!(
+                    "move {} large file(s) ({}) to {:?}",
+                    chosen.len(),
+                    format_size(chosen_size),
+                    dest_dir
+                ));
+                return Ok(());
+            }
+            let confirm = read_user_input(&format!(
+                "Move {} file(s) ({}) to {:?}? (y/n): ",
+                chosen.len(),
+                format_size(chosen_size),
+                dest_dir
+            ))?;
+            if !matches_yes(&confirm) {
+                app_state
+                    .log_lines
+                    .push("Large file cleanup canceled.".to_string());
+                return Ok(());
+            }
+            app_state.begin_undo_group();
+            for (path, _) in chosen {
+                let target = dest_dir.join(path.file_name().unwrap_or_default());
+                let paths = format!("{:?} -> {:?}", path, target);
+                let result = if target.exists() {
+                    Err(anyhow!("{:?} already exists", target))
+                } else {
+                    move_file(path, &target)
+                };
+                match result {
+                    Ok(_) => {
+                        app_state.log_lines.push(format!("Moved {}", paths));
+                        app_state.record_operation("move", &paths, "ok");
+                        app_state.push_undo(Operation::Move {
+                            from: path.clone(),
+                            to: target,
+                        });
+                    }
+                    Err(e) => {
+                        app_state
+                            .log_lines
+                            .push(format!("Failed to move {:?}: {:#}", path, e));
+                        app_state.record_operation("move", &paths, &format!("error: {e:#}"));
+                    }
+                }
+            }
+            app_state.end_undo_group(format!("move of large files to {:?}", dest_dir));
+        }
+        _ => app_state
+            .log_lines
+            .push("Invalid action. Nothing was changed.".to_string()),
+    }
+    Ok(())
+}
+
+/// Every regular file under `dir` of at least `min_bytes`, largest first.
+/// Symlinks are skipped: removing one frees no space.
+fn find_large_files(
+    dir: &Path,
+    walker: &mut DirWalker,
+    min_bytes: u64,
+) -> Result<Vec<(PathBuf, u64)>> {
+    let mut large: Vec<(PathBuf, u64)> = collect_files(dir, walker)?
+        .into_iter()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let meta = fs::symlink_metadata(&path).ok()?;
+            (meta.is_file() && meta.len() >= min_bytes).then_some((path, meta.len()))
+        })
+        .collect();
+    large.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(large)
+}
+
+/// Parses sizes like `100M`, `1.5G`, `500KiB`, or `2048B` (binary units, to
+/// match `format_size`). A bare number is taken as MiB.
+fn parse_size_threshold(input: &str) -> Option<u64> {
+    let input = input.trim().to_ascii_lowercase();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let value: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim() {
+        "" | "m" | "mb" | "mib" => 1024 * 1024,
+        "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "g" | "gb" | "gib" => 1024 * 1024 * 1024,
+        "t" | "tb" | "tib" => 1024 * 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    Some((value * multiplier as f64) as u64)
+}
+
+/// Parses a selection like `1,3-5` or `all` into zero-based indices below
+/// `count`, in ascending order without duplicates.
+fn parse_selection(input: &str, count: usize) -> Option<Vec<usize>> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("all") {
+        return Some((0..count).collect());
+    }
+    let mut indices = Vec::new();
+    for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((a, b)) => (
+                a.trim().parse::<usize>().ok()?,
+                b.trim().parse::<usize>().ok()?,
+            ),
+            None => {
+                let n = part.parse::<usize>().ok()?;
+                (n, n)
+            }
+        };
+        if start == 0 || start > end || end > count {
+            return None;
+        }
+        indices.extend(start - 1..end);
+    }
+    indices.sort_unstable();
+    indices.dedup();
+    (!indices.is_empty()).then_some(indices)
+}
+
+/// Renames `from` to `to`, falling back to copy-and-delete when they are on
+/// different filesystems (where a rename can't work). Directories are copied
+/// recursively, with symlinks inside recreated rather than followed.
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    match fs::rename(from, to) {
+        Ok(()) => return Ok(()),
+        // Any other failure (e.g. moving a directory into itself) would fail
+        // or destroy data the same way with a copy, so it is reported as is
+        Err(e) if e.kind() != io::ErrorKind::CrossesDevices => {
+            return Err(e).with_context(|| format!("moving {:?} to {:?} failed", from, to));
+        }
+        Err(_) => {}
+    }
+    if from.symlink_metadata().is_ok_and(|meta| meta.is_dir()) {
+        let mut walker = DirWalker::new(from, false);
+        copy_directory_recursive(
+            from,
+            to,
+            &mut walker,
+            CopyMode::All,
+            &mut overwrite_existing,
+            &mut |_| {},
+        )
+        .with_context(|| format!("copy to {:?} failed", to))?;
+        return fs::remove_dir_all(from)
+            .with_context(|| format!("removing {:?} after copy failed", from));
+    }
+    fs::copy(from, to).with_context(|| format!("copy to {:?} failed", to))?;
+    fs::remove_file(from).with_context(|| format!("removing {:?} after copy failed", from))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Find Files (by name)
+////////////////////////////////////////////////////////////////////////////////
+
+/// What `find_files` looks for in a file name.
+enum NamePattern {
+    /// Part of the name, e.g. `report`; stored lowercased unless case-sensitive
+    Substring { text: String, case_sensitive: bool },
+    /// A glob such as `*.rs` or `src/**/*.toml`; a pattern containing `/` is
+    /// matched against the path relative to the search root
+    Glob { matcher: GlobMatcher, by_path: bool },
+}
+
+impl NamePattern {
+    /// Input with `*`, `?`, or `[` is a glob; anything else a substring.
+    fn parse(input: &str, case_sensitive: bool) -> Result<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            bail!("no search text given");
+        }
+        if input.contains(['*', '?', '[']) {
+            let glob = GlobBuilder::new(input)
+                .case_insensitive(!case_sensitive)
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("invalid pattern {:?}", input))?;
+            return Ok(NamePattern::Glob {
+                matcher: glob.compile_matcher(),
+                by_path: input.contains('/'),
+            });
+        }
+        let text = if case_sensitive {
+            input.to_string()
+        } else {
+            input.to_lowercase()
+        };
+        Ok(NamePattern::Substring {
+            text,
+            case_sensitive,
+        })
+    }
+
+    /// Whether the file at `relative` (to the search root) matches.
+    fn matches(&self, relative: &Path) -> bool {
+        let name = relative.file_name().unwrap_or_default();
+        match self {
+            NamePattern::Substring {
+                text,
+                case_sensitive: true,
+            } => name.to_string_lossy().contains(text.as_str()),
+            NamePattern::Substring { text, .. } => name
+                .to_string_lossy()
+                .to_lowercase()
+                .contains(text.as_str()),
+            NamePattern::Glob { matcher, by_path } => {
+                matcher.is_match(if *by_path { relative } else { Path::new(name) })
+            }
+        }
+    }
+}
+
+/// Walks `root` and calls `on_match` with the path (relative to `root`) of
+/// each matching file as soon as it is found. Returns the number of matches.
+fn find_files(
+    root: &Path,
+    walker: &mut DirWalker,
+    pattern: &NamePattern,
+    unreadable: &mut Vec<(PathBuf, io::Error)>,
+    on_match: &mut dyn FnMut(&Path),
+) -> Result<usize> {
+    let mut found = 0;
+    visit_files(
+        root,
+        root,
+        walker,
+        &ExcludeGlobs::default(),
+        &mut Vec::new(),
+        &mut |entry| {
+            let path = entry.path();
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if pattern.matches(relative) {
+                found += 1;
+                on_match(relative);
+            }
+            Ok(())
+        },
+        // One unreadable directory shouldn't end the whole search
+        &mut |dir, e| {
+            unreadable.push((dir.to_path_buf(), e));
+            Ok(())
+        },
+    )?;
+    Ok(found)
+}
+
+/// 21) Finds files under the current directory by name, logging each hit.
+fn find_files_interactive(app_state: &mut AppState) -> Result<()> {
+    let input = read_user_input("Find files named (text, or a glob like *.rs or src/**/*.toml): ")?;
+    let case_sensitive = matches_yes(&read_user_input("Case-sensitive? (y/N): ")?);
+    let pattern = match NamePattern::parse(&input, case_sensitive) {
+        Ok(pattern) => pattern,
+        Err(e) => {
+            app_state.log_lines.push(format!("Error: {:#}", e));
+            return Ok(());
+        }
+    };
+
+    let root = app_state.current_dir.clone();
+    app_state.log_lines.push(format!(
+        "=== Files matching {:?} under {} ===",
+        input.trim(),
+        root.display()
+    ));
+    let mut walker = DirWalker::new(&root, app_state.follow_symlinks);
+    let mut unreadable = Vec::new();
+    let log_lines = &mut app_state.log_lines;
+    let result = find_files(
+        &root,
+        &mut walker,
+        &pattern,
+        &mut unreadable,
+        &mut |relative| {
+            let line = format!("  {}", relative.display());
+            // The screen isn't redrawn until the search returns, so show hits as they come
+            let _ = execute!(io::stdout(), MoveToColumn(0));
+            print!("{line}{}", LINE_ENDING);
+            let _ = io::stdout().flush();
+            log_lines.push(line);
+        },
+    );
+    for (dir, e) in unreadable {
+        app_state
+            .log_lines
+            .push(format!("Warning: could not read {}: {}", dir.display(), e));
+    }
+    match result {
+        Ok(0) => app_state
+            .log_lines
+            .push("No matching files found.".to_string()),
+        Ok(found) => app_state
+            .log_lines
+            .push(format!("{} matching file(s).", found)),
+        Err(e) => app_state.log_lines.push(format!("Search stopped: {:#}", e)),
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// File Preview
+////////////////////////////////////////////////////////////////////////////////
+
+/// Lines shown by a preview.
+const PREVIEW_LINES: usize = 40;
+/// A preview never reads more than this much of a file.
+const PREVIEW_MAX_BYTES: u64 = 64 * 1024;
+/// A NUL byte in this many leading bytes marks a file as binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// The start of a file, as shown by `preview_file`.
+#[derive(Debug, PartialEq)]
+enum Preview {
+    Binary,
+    /// The first lines, and whether the file goes on past them
+    Text {
+        lines: Vec<String>,
+        truncated: bool,
+    },
+}
+
+/// Reads at most `PREVIEW_MAX_BYTES` of `path` and returns its first
+/// `max_lines` lines, or `Preview::Binary` if it contains NUL bytes early on.
+/// Tabs are expanded so the lines render cleanly in the log.
+fn read_preview(path: &Path, max_lines: usize) -> io::Result<Preview> {
+    let mut buf = Vec::new();
+    fs::File::open(path)?
+        .take(PREVIEW_MAX_BYTES + 1)
+        .read_to_end(&mut buf)?;
+    if buf[..buf.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+        return Ok(Preview::Binary);
+    }
+    let cut_short = buf.len() as u64 > PREVIEW_MAX_BYTES;
+    buf.truncate(PREVIEW_MAX_BYTES as usize);
+    let text = String::from_utf8_lossy(&buf);
+    let mut lines = text.lines();
+    let preview: Vec<String> = lines
+        .by_ref()
+        .take(max_lines)
+        .map(|line| line.replace('\t', "    "))
+        .collect();
+    Ok(Preview::Text {
+        truncated: cut_short || lines.next().is_some(),
+        lines: preview,
+    })
+}
+
+/// Logs the first lines of `path` with line numbers and scrolls the log to
+/// the start of the preview. Binary files are refused.
+fn preview_file(app_state: &mut AppState, path: &Path) {
+    let size = fs::metadata(path)
+        .map(|m| format_size(m.len()))
+        .unwrap_or_default();
+    match read_preview(path, PREVIEW_LINES) {
+        Ok(Preview::Binary) => app_state.log_lines.push(format!(
+            "{:?} looks like a binary file ({}); not previewing it.",
+            path, size
+        )),
+        Ok(Preview::Text { lines, truncated }) => {
+            let start = app_state.log_lines.len();
+            app_state
+                .log_lines
+                .push(format!("=== Preview of {} ({}) ===", path.display(), size));
+            if lines.is_empty() {
+                app_state.log_lines.push("(empty file)".to_string());
+            }
+            app_state.log_lines.extend(
+                lines
+                    .iter()
+                    .enumerate()
+                    .map(|(i, line)| format!("{:>4} | {}", i + 1, line)),
+            );
+            if truncated {
+                app_state
+                    .log_lines
+                    .push(format!("... (only the first {} lines shown)", lines.len()));
+            }
+            let last_page = app_state
+                .log_lines
+                .len()
+                .saturating_sub(app_state.log_page_rows);
+            app_state.log_scroll = (start < last_page).then_some(start);
+        }
+        Err(e) => app_state
+            .log_lines
+            .push(format!("Failed to preview {:?}: {}", path, e)),
+    }
+}
+
+/// Prompts for a file and previews it; bound to 'p' in the main loop.
+/// Selection mode's 'p' calls `preview_file` on the highlighted entry instead.
+fn preview_file_interactive(app_state: &mut AppState) -> Result<()> {
+    let input = read_path_with_completion(
+        "Enter file to preview (Tab completes): ",
+        &app_state.current_dir,
+    )?;
+    if input.trim().is_empty() {
+        return Ok(());
+    }
+    // Relative paths are taken from the current directory, as completed
+    let path = app_state.current_dir.join(input.trim());
+    if path.is_dir() {
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} is a directory.", path));
+        return Ok(());
+    }
+    preview_file(app_state, &path);
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Multi-Select (batch copy/move/delete)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Entries of `dir` for selection mode: directories first, then by name.
+fn selectable_entries(dir: &Path, show_hidden: bool) -> Result<Vec<PathBuf>> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("read_dir {:?} failed", dir))?
+        .flatten()
+        .filter(|e| show_hidden || !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| e.path())
+        .collect();
+    entries.sort_by_key(|p| (!p.is_dir(), p.file_name().map(|n| n.to_ascii_lowercase())));
+    Ok(entries)
+}
+
+/// Switches the middle pane between the menu and the entries of the current directory.
+fn toggle_select_mode(app_state: &mut AppState) -> Result<()> {
+    app_state.select_mode = !app_state.select_mode;
+    if app_state.select_mode {
+        app_state.select_index = 0;
+        reload_selection_list(app_state)?;
+        app_state.log_lines.push(
+            "Selection mode: Space marks an entry, c/m/x copies, moves, or deletes the marked ones, Esc returns to the menu."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Re-reads the current directory into the selection list, and unmarks paths
+/// that no longer exist.
+fn reload_selection_list(app_state: &mut AppState) -> Result<()> {
+    app_state.select_entries = selectable_entries(&app_state.current_dir, app_state.show_hidden)?;
+    app_state.select_index = app_state
+        .select_index
+        .min(app_state.select_entries.len().saturating_sub(1));
+    app_state.selected.retain(|p| p.symlink_metadata().is_ok());
+    Ok(())
+}
+
+/// Changes directory from selection mode. Marks are absolute paths, so they
+/// carry over and entries from several directories can be collected.
+fn enter_selection_directory(app_state: &mut AppState, dir: &Path) -> Result<()> {
+    enter_directory(app_state, dir)?;
+    if app_state.watch_mode {
+        start_watching(app_state);
+    }
+    app_state.select_index = 0;
+    reload_selection_list(app_state)
+}
+
+/// Handles a key in selection mode. Returns `false` for keys left to the main
+/// loop (quit, log scrolling, the toggles, ...).
+fn select_mode_key(app_state: &mut AppState, key: event::KeyEvent) -> Result<bool> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return Ok(false);
+    }
+    let last = app_state.select_entries.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Up => app_state.select_index = app_state.select_index.saturating_sub(1),
+        KeyCode::Down => app_state.select_index = (app_state.select_index + 1).min(last),
+        KeyCode::Char(' ') => {
+            if let Some(path) = app_state.select_entries.get(app_state.select_index) {
+                if !app_state.selected.remove(path) {
+                    app_state.selected.insert(path.clone());
+                }
+                app_state.select_index = (app_state.select_index + 1).min(last);
+            }
+        }
+        KeyCode::Char('a') => {
+            // Marks every entry shown, or unmarks them if they all are already
+            let all = app_state
+                .select_entries
+                .iter()
+                .all(|p| app_state.selected.contains(p));
+            for path in &app_state.select_entries {
+                if all {
+                    app_state.selected.remove(path);
+                } else {
+                    app_state.selected.insert(path.clone());
+                }
+            }
+        }
+        KeyCode::Enter => {
+            let dir = app_state
+                .select_entries
+                .get(app_state.select_index)
+                .filter(|p| p.is_dir())
+                .cloned();
+            if let Some(dir) = dir {
+                enter_selection_directory(app_state, &dir)?;
+            }
+        }
+        KeyCode::Backspace => {
+            if let Some(parent) = app_state.current_dir.parent().map(Path::to_path_buf) {
+                enter_selection_directory(app_state, &parent)?;
+            }
+        }
+        KeyCode::Char('p') => {
+            let file = app_state
+                .select_entries
+                .get(app_state.select_index)
+                .filter(|p| !p.is_dir())
+                .cloned();
+            if let Some(file) = file {
+                preview_file(app_state, &file);
+            }
+        }
+        KeyCode::Char('c') => transfer_selected(app_state, Transfer::Copy)?,
+        KeyCode::Char('m') => transfer_selected(app_state, Transfer::Move)?,
+        KeyCode::Char('x') | KeyCode::Delete => delete_selected(app_state)?,
+        KeyCode::Esc | KeyCode::Char('s') => app_state.select_mode = false,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Draws the selection list in the middle pane; marked entries are shown in
+/// bold cyan with a `[x]`.
+fn draw_selection_list(frame: &mut Frame, area: Rect, app_state: &AppState) {
+    let items: Vec<ListItem> = app_state
+        .select_entries
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let marked = app_state.selected.contains(path);
+            let mut name = path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if path.is_dir() {
+                name.push('/');
+            }
+            let mut style = if i == app_state.select_index {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else if marked {
+                Style::default().fg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            if marked {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            let check = if marked { "[x]" } else { "[ ]" };
+            ListItem::new(Line::from(Span::styled(format!("{check} {name}"), style)))
+        })
+        .collect();
+
+    let title = format!(
+        " Select [{} marked] Space: mark  a: all  c/m/x: copy/move/delete  Enter/Backspace: open/up  Esc: menu ",
+        app_state.selected.len()
+    );
+    let list = if items.is_empty() {
+        List::new(vec![ListItem::new("(empty directory)")])
+    } else {
+        List::new(items)
+    };
+    let list = list.block(Block::default().borders(Borders::ALL).title(title));
+    let mut list_state = ListState::default().with_selected(Some(app_state.select_index));
+    frame.render_stateful_widget(list, area, &mut list_state);
+}
+
+/// The marked paths, sorted so batches run (and log) in a predictable order.
+fn selected_paths(app_state: &AppState) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = app_state.selected.iter().cloned().collect();
+    paths.sort();
+    paths
+}
+
+/// What `transfer_selected` does with each marked entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transfer {
+    Copy,
+    Move,
+}
+
+impl Transfer {
+    fn verb(self) -> &'static str {
+        match self {
+            Transfer::Copy => "copy",
+            Transfer::Move => "move",
+        }
+    }
+
+    fn past_tense(self) -> &'static str {
+        match self {
+            Transfer::Copy => "Copied",
+            Transfer::Move => "Moved",
+        }
+    }
+}
+
+/// Copies or moves `source` to `target`, returning how to undo it. An existing
+/// `target` is never overwritten.
+fn transfer_item(
+    source: &Path,
+    target: &Path,
+    transfer: Transfer,
+    follow_symlinks: bool,
+) -> Result<Operation> {
+    if target.symlink_metadata().is_ok() {
+        bail!("{:?} already exists", target);
+    }
+    if source.is_dir() {
+        let target_parent = target.parent().unwrap_or(Path::new("."));
+        if fs::canonicalize(target_parent)?.starts_with(fs::canonicalize(source)?) {
+            bail!("{:?} is inside {:?}", target, source);
+        }
+    }
+    match transfer {
+        Transfer::Copy => {
+            if source.is_dir() {
+                let mut walker = DirWalker::new(source, follow_symlinks);
+                copy_directory_recursive(
+                    source,
+                    target,
+                    &mut walker,
+                    CopyMode::All,
+                    &mut overwrite_existing,
+                    &mut |_| {},
+                )?;
+            } else {
+                fs::copy(source, target).with_context(|| format!("copy to {:?} failed", target))?;
+            }
+            Ok(Operation::Copy {
+                created: target.to_path_buf(),
+            })
+        }
+        Transfer::Move => {
+            move_file(source, target)?;
+            Ok(Operation::Move {
+                from: source.to_path_buf(),
+                to: target.to_path_buf(),
+            })
+        }
+    }
+}
+
+/// Copies or moves every marked entry into one directory, as a single undo
+/// step. Entries that fail stay marked so the batch can be retried.
+fn transfer_selected(app_state: &mut AppState, transfer: Transfer) -> Result<()> {
+    let sources = selected_paths(app_state);
+    if sources.is_empty() {
+        app_state
+            .log_lines
+            .push("Nothing is selected. Press 's' and mark entries with Space first.".to_string());
+        return Ok(());
+    }
+    let verb = transfer.verb();
+    let input = read_path_with_completion(
+        &format!(
+            "{} {} selected item(s) into directory (Tab completes): ",
+            if transfer == Transfer::Copy {
+                "Copy"
+            } else {
+                "Move"
+            },
+            sources.len()
+        ),
+        &app_state.current_dir,
+    )?;
+    if input.trim().is_empty() {
+        app_state
+            .log_lines
+            .push("No destination provided. Aborting.".to_string());
+        return Ok(());
+    }
+    let dest_dir = app_state.current_dir.join(input.trim());
+    if !dest_dir.is_dir() {
+        app_state
+            .log_lines
+            .push(format!("Error: {:?} is not a valid directory.", dest_dir));
+        return Ok(());
+    }
+    if app_state.dry_run {
+        app_state.log_dry_run(format!(
+            "{} {} selected item(s) into {:?}",
+            verb,
+            sources.len(),
+            dest_dir
+        ));
+        return Ok(());
+    }
+
+    app_state.begin_undo_group();
+    let mut done = 0;
+    for source in &sources {
+        let target = dest_dir.join(source.file_name().unwrap_or_default());
+        let paths = format!("{:?} -> {:?}", source, target);
+        match transfer_item(source, &target, transfer, app_state.follow_symlinks) {
+            Ok(operation) => {
+                done += 1;
+                app_state
+                    .log_lines
+                    .push(format!("{} {}", transfer.past_tense(), paths));
+                app_state.record_operation(verb, &paths, "ok");
+                app_state.push_undo(operation);
+                app_state.selected.remove(source);
+            }
+            Err(e) => {
+                app_state
+                    .log_lines
+                    .push(format!("Failed to {} {:?}: {:#}", verb, source, e));
+                app_state.record_operation(verb, &paths, &format!("error: {e:#}"));
+            }
+        }
+    }
+    app_state.end_undo_group(format!(
+        "{} of {} selected item(s) into {:?}",
+        verb, done, dest_dir
+    ));
+    app_state.log_lines.push(format!(
+        "Batch {}: {} of {} item(s) done.",
+        verb,
+        done,
+        sources.len()
+    ));
+    if app_state.select_mode {
+        reload_selection_list(app_state)?;
+    }
+    Ok(())
+}
+
+/// Deletes every marked entry after one confirmation, as a single undo step.
+fn delete_selected(app_state: &mut AppState) -> Result<()> {
+    let targets = selected_paths(app_state);
+    if targets.is_empty() {
+        app_state
+            .log_lines
+            .push("Nothing is selected. Press 's' and mark entries with Space first.".to_string());
+        return Ok(());
+    }
+    let (size, files) = targets
+        .iter()
+        .filter_map(|p| describe_delete_target(p).ok())
+        .fold((0, 0), |(size, files), (s, f)| (size + s, files + f));
+    let summary = DeleteSummary::new(size, files);
+    if app_state.dry_run {
+        app_state.log_dry_run(format!(
+            "delete {} selected item(s) ({})",
+            targets.len(),
+            summary.text
+        ));
+        return Ok(());
+    }
+    let confirm = read_user_input(&format!(
+        "Are you sure you want to delete {} selected item(s) ({})? (y/n): ",
+        targets.len(),
+        summary.styled()
+    ))?;
+    if !matches_yes(&confirm) {
+        app_state
+            .log_lines
+            .push("Delete action canceled.".to_string());
+        return Ok(());
+    }
+    let permanent = matches_yes(&read_user_input(
+        "Permanent delete? (y/n, default n moves them to the trash): ",
+    )?);
+
+    app_state.begin_undo_group();
+    let mut deleted = 0;
+    for target in &targets {
+        if delete_path(app_state, target, permanent) {
+            deleted += 1;
+            app_state.selected.remove(target);
+        }
+    }
+    app_state.end_undo_group(format!("delete of {} selected item(s)", deleted));
+    app_state.log_lines.push(format!(
+        "Batch delete: {} of {} item(s) deleted.",
+        deleted,
+        targets.len()
+    ));
+    if app_state.select_mode {
+        reload_selection_list(app_state)?;
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Clipboard
+////////////////////////////////////////////////////////////////////////////////
+
+/// Copies the absolute path of a file (or the current directory) to the
+/// system clipboard. Without a clipboard the path is logged instead.
+fn copy_path_to_clipboard(app_state: &mut AppState) -> Result<()> {
+    let input = read_user_input(&format!(
+        "Enter file to copy the path of (default: {}): ",
+        app_state.current_dir.display()
+    ))?;
+    let target = if input.trim().is_empty() {
+        app_state.current_dir.clone()
+    } else {
+        app_state.current_dir.join(input.trim())
+    };
+
+    let path = match fs::canonicalize(&target) {
+        Ok(p) => p,
+        Err(e) => {
+            app_state
+                .log_lines
+                .push(format!("Error: cannot resolve {:?}: {}", target, e));
+            return Ok(());
+        }
+    };
+    let text = path.display().to_string();
+
+    let copied = match app_state.clipboard.as_mut() {
+        Some(clipboard) => clipboard.set_text(text.clone()),
+        None => arboard::Clipboard::new().and_then(|mut clipboard| {
+            clipboard.set_text(text.clone())?;
+            app_state.clipboard = Some(clipboard);
+            Ok(())
+        }),
+    };
+    match copied {
+        Ok(_) => app_state
+            .log_lines
+            .push(format!("Copied to clipboard: {}", text)),
+        Err(e) => {
+            app_state
+                .log_lines
+                .push(format!("Clipboard unavailable ({}). Path: {}", e, text));
+        }
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Undo
+////////////////////////////////////////////////////////////////////////////////
+
+/// Only this many of the most recent operations can be undone.
+const UNDO_LIMIT: usize = 100;
+
+/// A completed file operation, with what is needed to reverse it.
+#[derive(Debug)]
+enum Operation {
+    /// `from` was moved or renamed to `to`; undone by moving it back
+    Move { from: PathBuf, to: PathBuf },
+    /// `created` was made by a copy or duplicate; undone by moving it to the trash
+    Copy { created: PathBuf },
+    /// `original` was moved to the trash. `item_id` identifies it there, if the
+    /// platform lets us look it up.
+    Trash {
+        original: PathBuf,
+        item_id: Option<std::ffi::OsString>,
+    },
+    /// A permanent delete: kept only to report that it can't be undone
+    Irreversible { description: String },
+    /// Several operations run as one action (organize, batch rename), undone together
+    Batch {
+        description: String,
+        operations: Vec<Operation>,
+    },
+}
+
+impl Operation {
+    fn describe(&self) -> String {
+        match self {
+            Operation::Move { from, to } => format!("move of {:?} to {:?}", from, to),
+            Operation::Copy { created } => format!("copy to {:?}", created),
+            Operation::Trash { original, .. } => format!("delete (to trash) of {:?}", original),
+            Operation::Irreversible { description } => description.clone(),
+            Operation::Batch {
+                description,
+                operations,
+            } => format!("{} ({} step(s))", description, operations.len()),
+        }
+    }
+
+    /// Reverses the operation. A batch is undone newest first and keeps going
+    /// past failures, which are returned together.
+    fn undo(&self) -> Result<()> {
+        match self {
+            Operation::Move { from, to } => {
+                if from.symlink_metadata().is_ok() {
+                    bail!("{:?} exists again, not overwriting it", from);
+                }
+                if to.is_dir() {
+                    fs::rename(to, from)
+                        .with_context(|| format!("moving {:?} back to {:?} failed", to, from))
+                } else {
+                    move_file(to, from)
+                }
+            }
+            Operation::Copy { created } => trash::delete(created)
+                .with_context(|| format!("moving the copy {:?} to the trash failed", created)),
+            Operation::Trash { original, item_id } => match item_id {
+                Some(id) => restore_from_trash(id),
+                None => bail!(
+                    "{:?} couldn't be found in the trash; restore it with your file manager",
+                    original
+                ),
+            },
+            Operation::Irreversible { description } => {
+                bail!("the {} was permanent and can't be undone", description)
+            }
+            Operation::Batch { operations, .. } => {
+                let errors: Vec<String> = operations
+                    .iter()
+                    .rev()
+                    .filter_map(|op| op.undo().err().map(|e| format!("{e:#}")))
+                    .collect();
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    bail!(
+                        "{} of {} step(s) failed: {}",
+                        errors.len(),
+                        operations.len(),
+                        errors.join("; ")
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// 22) Pops the most recent operation and reverses it (also bound to 'u').
+fn undo_last_operation(app_state: &mut AppState) -> Result<()> {
+    let Some(operation) = app_state.undo_stack.pop() else {
+        app_state.log_lines.push("Nothing to undo.".to_string());
+        return Ok(());
+    };
+    let description = operation.describe();
+    if let Operation::Irreversible { .. } = operation {
+        app_state
+            .log_lines
+            .push(format!("Cannot undo: the {} was permanent.", description));
+        return Ok(());
+    }
+    if app_state.dry_run {
+        app_state.log_dry_run(format!("undo the {}", description));
+        app_state.undo_stack.push(operation);
+        return Ok(());
+    }
+
+    let confirm = read_user_input(&format!("Undo the {}? (y/n): ", description))?;
+    if !matches_yes(&confirm) {
+        app_state.undo_stack.push(operation);
+        app_state.log_lines.push("Undo canceled.".to_string());
+        return Ok(());
+    }
+    match operation.undo() {
+        Ok(()) => {
+            app_state
+                .log_lines
+                .push(format!("Undid the {}.", description));
+            app_state.record_operation("undo", &description, "ok");
+        }
+        Err(e) => {
+            app_state.log_lines.push(format!("Undo failed: {:#}", e));
+            app_state.record_operation("undo", &description, &format!("error: {e:#}"));
+        }
+    }
+    Ok(())
+}
+
+/// Absolute form of `path` without resolving a final symlink (the trash
+/// stores the link itself, not its target).
+fn absolute_path(path: &Path) -> PathBuf {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    match (fs::canonicalize(parent), path.file_name()) {
+        (Ok(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// The trash entry most recently created for `original`, if the platform can list the trash.
+#[cfg(any(windows, target_os = "linux"))]
+fn find_trash_item(original: &Path) -> Option<std::ffi::OsString> {
+    trash::os_limited::list()
+        .ok()?
+        .into_iter()
+        .filter(|item| item.original_path() == original)
+        .max_by_key(|item| item.time_deleted)
+        .map(|item| item.id)
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn find_trash_item(_original: &Path) -> Option<std::ffi::OsString> {
+    None
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+fn restore_from_trash(id: &std::ffi::OsStr) -> Result<()> {
+    let item = trash::os_limited::list()
+        .context("Failed to list the trash")?
+        .into_iter()
+        .find(|item| item.id == id)
+        .context("the item is no longer in the trash")?;
+    trash::os_limited::restore_all([item]).context("Failed to restore from the trash")
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn restore_from_trash(_id: &std::ffi::OsStr) -> Result<()> {
+    bail!("restoring from the trash isn't supported on this platform")
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Operation Log (persisted audit trail)
+////////////////////////////////////////////////////////////////////////////////
+
+/// File name of the operation log in the home directory.
+const OPERATION_LOG_FILE: &str = ".file_commander.log";
+
+/// Once the log grows past this size it is rotated to `<name>.1`.
+const OPERATION_LOG_MAX_BYTES: u64 = 1024 * 1024;
+
+/// How many recent entries the "View operation log" action shows.
+const OPERATION_LOG_VIEW_LINES: usize = 50;
+
+fn operation_log_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not locate home directory")?;
+    Ok(home.join(OPERATION_LOG_FILE))
+}
+
+/// Appends `timestamp | action | paths | outcome` to the log, rotating it first
+/// when it has grown past `OPERATION_LOG_MAX_BYTES`.
+fn append_operation_log(action: &str, paths: &str, outcome: &str) -> Result<()> {
+    let path = operation_log_path()?;
+    if fs::metadata(&path).is_ok_and(|m| m.len() > OPERATION_LOG_MAX_BYTES) {
+        let mut rotated = path.clone().into_os_string();
+        rotated.push(".1");
+        fs::rename(&path, rotated).context("Failed to rotate operation log")?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {:?}", path))?;
+    writeln!(
+        file,
+        "{} | {} | {} | {}",
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        action,
+        paths,
+        outcome
+    )?;
+    Ok(())
+}
+
+/// 18) Show the most recent entries of the operation log.
+fn view_operation_log(app_state: &mut AppState) -> Result<()> {
+    let path = operation_log_path()?;
+    let contents = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            app_state
+                .log_lines
+                .push("The operation log is empty.".to_string());
+            return Ok(());
+        }
+        Err(e) => {
+            app_state
+                .log_lines
+                .push(format!("Could not read {:?}: {}", path, e));
+            return Ok(());
+        }
+    };
+
+    let lines: Vec<&str> = contents.lines().collect();
+    let start = lines.len().saturating_sub(OPERATION_LOG_VIEW_LINES);
+    app_state.log_lines.push(format!(
+        "=== Operation log ({} of {} entries, {:?}) ===",
+        lines.len() - start,
+        lines.len(),
+        path
+    ));
+    app_state
+        .log_lines
+        .extend(lines[start..].iter().map(|l| l.to_string()));
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Misc Helpers
+////////////////////////////////////////////////////////////////////////////////
+
+/// A file's type as detected from its contents (magic bytes).
+struct DetectedType {
+    /// MIME type, e.g. `image/png`
+    mime: &'static str,
+    /// Usual extension for the type, when the magic-byte table knows one
+    extension: Option<&'static str>,
+    /// Folder name used when organizing by type
+    category: &'static str,
+}
+
+impl DetectedType {
+    /// Whether `path`'s extension agrees with the detected type. Files without
+    /// an extension, or of a type with no known extension, always agree.
+    fn matches_extension(&self, path: &Path) -> bool {
+        let (Some(expected), Some(actual)) = (self.extension, path.extension()) else {
+            return true;
+        };
+        let actual = actual.to_string_lossy().to_lowercase();
+        // Common alternate spellings of the same format
+        let alias = match actual.as_str() {
+            "jpeg" | "jpe" => "jpg",
+            "tiff" => "tif",
+            "htm" => "html",
+            "mpeg" => "mpg",
+            other => other,
+        };
+        actual == expected || alias == expected
+    }
+}
+
+/// Detects a file's type from its first bytes via `infer`. Unrecognized
+/// files are reported as text when they look like UTF-8, otherwise as binary.
+fn detect_file_type(path: &Path) -> DetectedType {
+    let mut head = Vec::with_capacity(8192);
+    if let Ok(file) = fs::File::open(path) {
+        let _ = file.take(8192).read_to_end(&mut head);
+    }
+
+    if let Some(kind) = infer::get(&head) {
+        let category = match kind.matcher_type() {
+            infer::MatcherType::App => "applications",
+            infer::MatcherType::Archive => "archives",
+            infer::MatcherType::Audio => "audio",
+            infer::MatcherType::Book => "books",
+            infer::MatcherType::Doc => "documents",
+            infer::MatcherType::Font => "fonts",
+            infer::MatcherType::Image => "images",
+            infer::MatcherType::Text => "text",
+            infer::MatcherType::Video => "videos",
+            infer::MatcherType::Custom => "other",
+        };
+        return DetectedType {
+            mime: kind.mime_type(),
+            extension: Some(kind.extension()),
+            category,
+        };
+    }
+
+    // A cut-off multi-byte character at the end of the sample is still text
+    let looks_like_text = !head.contains(&0)
+        && match std::str::from_utf8(&head) {
+            Ok(_) => true,
+            Err(e) => e.error_len().is_none(),
+        };
+    if looks_like_text {
+        DetectedType {
+            mime: "text/plain",
+            extension: None,
+            category: "text",
+        }
+    } else {
+        DetectedType {
+            mime: "application/octet-stream",
+            extension: None,
+            category: "unknown",
+        }
+    }
+}
+
+/// How a recursive walk should treat one directory entry.
+#[derive(Debug, PartialEq, Eq)]
+enum WalkEntry {
+    /// A directory to descend into
+    Directory,
+    /// A directory whose canonical path was already walked (e.g. a symlink cycle)
+    Revisited,
+    /// A regular file, or a followed link to one
+    File,
+    /// A symlink that is not followed (or whose target is missing)
+    Symlink,
+}
+
+/// Shared state for recursive walks: whether symlinks are followed, and the
+/// canonical paths of directories already entered so cycles terminate.
+struct DirWalker {
+    follow_symlinks: bool,
+    visited: HashSet<PathBuf>,
+}
+
+impl DirWalker {
+    fn new(root: &Path, follow_symlinks: bool) -> Self {
+        let mut visited = HashSet::new();
+        if let Ok(canonical) = fs::canonicalize(root) {
+            visited.insert(canonical);
+        }
+        Self {
+            follow_symlinks,
+            visited,
+        }
+    }
+
+    /// Treats `dir` as already entered, so the walk skips it.
+    fn mark_visited(&mut self, dir: &Path) -> io::Result<()> {
+        self.visited.insert(fs::canonicalize(dir)?);
+        Ok(())
+    }
+
+    /// Classifies `path` using `symlink_metadata`, so links are only
+    /// traversed when following is enabled.
+    fn classify(&mut self, path: &Path) -> io::Result<WalkEntry> {
+        let meta = fs::symlink_metadata(path)?;
+        let is_dir = if meta.file_type().is_symlink() {
+            if !self.follow_symlinks {
+                return Ok(WalkEntry::Symlink);
+            }
+            match fs::metadata(path) {
+                Ok(target) => target.is_dir(),
+                Err(_) => return Ok(WalkEntry::Symlink),
+            }
+        } else {
+            meta.is_dir()
+        };
+
+        if !is_dir {
+            Ok(WalkEntry::File)
+        } else if self.visited.insert(fs::canonicalize(path)?) {
+            Ok(WalkEntry::Directory)
+        } else {
+            Ok(WalkEntry::Revisited)
+        }
+    }
+}
+
+/// Helper to interpret "y"/"yes" input as true, everything else as false.
+fn matches_yes(input: &str) -> bool {
+    let s = input.trim().to_lowercase();
+    s == "y" || s == "yes"
+}
+
+/// Formats a byte count using binary units, e.g. `1.5 MiB`.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// A blocking function to read user input from stdin.
+fn read_user_input(prompt_msg: &str) -> Result<String> {
+    print!("{prompt_msg}{}", LINE_ENDING);
+    io::stdout().flush()?;
+
+    let mut buf = String::new();
+    io::stdin().read_line(&mut buf)?;
+    Ok(buf)
+}
+
+/// Reads a path like `read_user_input`, but driven by key events so that Tab
+/// can complete it against the filesystem. Relative input is completed
+/// against `base_dir`. Repeated Tabs cycle through the candidates when more
+/// than one matches; Esc or Ctrl+C return an empty string.
+fn read_path_with_completion(prompt_msg: &str, base_dir: &Path) -> Result<String> {
+    print!("{prompt_msg}{}", LINE_ENDING);
+    let mut stdout = io::stdout();
+    let mut buf = String::new();
+    // Candidates and the index shown, while repeated Tabs cycle through them
+    let mut cycle: Option<(Vec<String>, usize)> = None;
+
+    loop {
+        execute!(stdout, MoveToColumn(0), Clear(ClearType::CurrentLine))?;
+        print!("> {buf}");
+        stdout.flush()?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => break,
+            KeyCode::Esc => {
+                buf.clear();
+                break;
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                buf.clear();
+                break;
+            }
+            KeyCode::Tab => {
+                if let Some((candidates, index)) = &mut cycle {
+                    *index = (*index + 1) % candidates.len();
+                    buf = candidates[*index].clone();
+                    continue;
+                }
+                let candidates = complete_path(&buf, base_dir);
+                match candidates.len() {
+                    // Nothing matches: ring the bell and leave the input alone
+                    0 => print!("\x07"),
+                    1 => buf = candidates[0].clone(),
+                    _ => {
+                        let common = common_prefix(&candidates);
+                        if common.len() > buf.len() {
+                            buf = common.to_string();
+                        } else {
+                            // Already as long as it gets: list the choices and
+                            // start cycling through them
+                            print!("{}", LINE_ENDING);
+                            let names: Vec<&str> = candidates
+                                .iter()
+                                .map(|c| c.trim_end_matches(is_path_separator))
+                                .map(|c| c.rsplit(is_path_separator).next().unwrap_or(c))
+                                .collect();
+                            print!("{}{}", names.join("  "), LINE_ENDING);
+                            buf = candidates[0].clone();
+                            cycle = Some((candidates, 0));
+                        }
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                buf.pop();
+                cycle = None;
+            }
+            KeyCode::Char(c) => {
+                buf.push(c);
+                cycle = None;
+            }
+            _ => {}
+        }
+    }
+    print!("{}", LINE_ENDING);
+    Ok(buf)
+}
+
+fn is_path_separator(c: char) -> bool {
+    c == '/' || c == std::path::MAIN_SEPARATOR
+}
+
+/// Possible completions of `input`: every entry of its parent directory whose
+/// name starts with the last path component, as full inputs. Directories get
+/// a trailing separator. Input ending in a separator lists all children;
+/// hidden entries are only offered when the component starts with a dot.
+fn complete_path(input: &str, base_dir: &Path) -> Vec<String> {
+    let (dir_part, partial) = match input.rfind(is_path_separator) {
+        Some(i) => input.split_at(i + 1),
+        None => ("", input),
+    };
+    // `join` keeps an absolute `dir_part` as it is
+    let dir = base_dir.join(dir_part);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut candidates: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.starts_with(partial) || (name.starts_with('.') && !partial.starts_with('.')) {
+                return None;
+            }
+            // Follows symlinks, so a link to a directory completes like one
+            let suffix = if entry.path().is_dir() {
+                std::path::MAIN_SEPARATOR.to_string()
+            } else {
+                String::new()
+            };
+            Some(format!("{dir_part}{name}{suffix}"))
+        })
+        .collect();
+    candidates.sort();
+    candidates
+}
+
+/// Longest prefix shared by all the strings (on a character boundary).
+fn common_prefix(strings: &[String]) -> &str {
+    let Some(first) = strings.first() else {
+        return "";
+    };
+    let mut end = first.len();
+    for other in &strings[1..] {
+        end = first
+            .char_indices()
+            .zip(other.chars())
+            .take_while(|((_, a), b)| a == b)
+            .map(|((i, a), _)| i + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+            .min(end);
+    }
+    &first[..end]
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::ops::Deref;
+
+    /// A scratch directory under the system temp dir, removed again when
+    /// dropped, so a failing assertion doesn't leave it behind.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("fc-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Deref for TempDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn symlink_cycle_terminates() {
+        let root = TempDir::new("cycle");
+        let inner = root.join("src").join("inner");
+        fs::create_dir_all(&inner).unwrap();
+        fs::write(inner.join("file.txt"), b"hello").unwrap();
+        std::os::unix::fs::symlink("..", inner.join("loop")).unwrap();
+        let src = root.join("src");
+
+        for follow in [false, true] {
+            let mut walker = DirWalker::new(&src, follow);
+            let DirectoryStats {
+                size,
+                files,
+                dirs,
+                largest,
+            } = compute_directory_stats(&src, &mut walker).unwrap();
+            assert_eq!(dirs, 1);
+            assert_eq!(largest, Some((inner.join("file.txt"), 5)));
+            // Unfollowed, the link itself is counted as a file
+            assert_eq!(files, if follow { 1 } else { 2 });
+            assert!(size >= 5);
+
+            let mut walker = DirWalker::new(&src, follow);
+            assert_eq!(
+                collect_files(&src, &mut walker).unwrap().len(),
+                files as usize
+            );
+
+            let dest = root.join(format!("copy-{follow}"));
+            let mut walker = DirWalker::new(&src, follow);
+            let copied = copy_directory_recursive(
+                &src,
+                &dest,
+                &mut walker,
+                CopyMode::All,
+                &mut overwrite_existing,
+                &mut |_| {},
+            )
+            .unwrap();
+            let (handled, handled_bytes) = copied.handled();
+            assert_eq!((copied.skipped, handled, handled_bytes), (0, files, size));
+            assert!(dest.join("inner").join("file.txt").is_file());
+            let link = dest.join("inner").join("loop");
+            assert_eq!(link.symlink_metadata().is_ok(), !follow);
+        }
+
+        // Copying a directory into its own subtree doesn't copy the copy
+        let nested = src.join("inner").join("backup");
+        let mut walker = DirWalker::new(&src, false);
+        let copied = copy_directory_recursive(
+            &src,
+            &nested,
+            &mut walker,
+            CopyMode::All,
+            &mut overwrite_existing,
+            &mut |_| {},
+        )
+        .unwrap();
+        assert_eq!(copied.files, 2);
+        assert!(nested.join("inner").join("file.txt").is_file());
+        assert!(!nested.join("inner").join("backup").exists());
+    }
+
+    #[test]
+    fn update_mode_copies_only_changed_files() {
+        let root = TempDir::new("sync");
+        let (src, dest) = (root.join("src"), root.join("dest"));
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("a.txt"), b"alpha").unwrap();
+        fs::write(src.join("sub").join("b.txt"), b"beta").unwrap();
+
+        let sync = |compare_size| {
+            let mut walker = DirWalker::new(&src, false);
+            let mode = CopyMode::Update { compare_size };
+            copy_directory_recursive(
+                &src,
+                &dest,
+                &mut walker,
+                mode,
+                &mut overwrite_existing,
+                &mut |_| {},
+            )
+            .unwrap()
+        };
+        let first = sync(false);
+        assert_eq!((first.files, first.skipped), (2, 0));
+        let second = sync(false);
+        assert_eq!(
+            (second.files, second.skipped, second.skipped_bytes),
+            (0, 2, 9)
+        );
+
+        // An outdated copy is replaced; a newer one only if sizes are compared
+        let a = fs::File::options()
+            .write(true)
+            .open(dest.join("a.txt"))
+            .unwrap();
+        a.set_modified(std::time::SystemTime::UNIX_EPOCH).unwrap();
+        fs::write(dest.join("sub").join("b.txt"), b"stale, but newer").unwrap();
+        let third = sync(false);
+        assert_eq!((third.files, third.bytes, third.skipped), (1, 5, 1));
+        let fourth = sync(true);
+        assert_eq!((fourth.files, fourth.bytes, fourth.skipped), (1, 4, 1));
+        assert_eq!(fs::read(dest.join("sub").join("b.txt")).unwrap(), b"beta");
+    }
+
+    #[test]
+    fn organize_keeps_files_with_the_same_name() {
+        let root = TempDir::new("organize-clash");
+        for dir in ["a", "b", "c"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("notes.txt"), dir).unwrap();
+        }
+        let target_dir = root.join("by_extension/txt");
+
+        let mut moved = Vec::new();
+        for dir in ["a", "b", "c"] {
+            let target = move_into_dir(&root.join(dir).join("notes.txt"), &target_dir).unwrap();
+            moved.push(target.unwrap());
+        }
+        assert_eq!(
+            moved,
+            ["notes.txt", "notes_1.txt", "notes_2.txt"].map(|name| target_dir.join(name))
+        );
+        let contents: Vec<String> = moved
+            .iter()
+            .map(|path| fs::read_to_string(path).unwrap())
+            .collect();
+        assert_eq!(contents, ["a", "b", "c"]);
+        // A file already in place is left alone rather than renamed
+        assert_eq!(move_into_dir(&moved[0], &target_dir).unwrap(), None);
+        assert!(moved[0].exists());
+    }
 }
@@ -7,17 +7,38 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use base64::Engine;
+use chrono::{DateTime, Local};
+use clap::{Parser, Subcommand};
+use mdns_sd::{ServiceDaemon, ServiceEvent};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    client::WebPkiServerVerifier,
+    pki_types::{CertificateDer, ServerName, UnixTime},
+    DigitallySignedStruct, SignatureScheme,
+};
+use serde::{Deserialize, Serialize};
+use ssh2::{CheckResult, HashType, KnownHostFileKind, MethodType, Session};
 use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
     io::{self, Write},
     net::ToSocketAddrs,
+    path::PathBuf,
     process::Command,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
 };
+use x509_parser::extensions::GeneralName;
 
 use crossterm::{
     cursor::MoveTo,
     event::{self, Event as CEvent, KeyCode},
     execute,
+    style::Stylize,
     terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
 };
 
@@ -25,12 +46,17 @@ use ratatui::{
     backend::CrosstermBackend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
-    Terminal,
+    widgets::{
+        Axis, Block, Borders, Chart, Dataset, GraphType, List, ListItem, Paragraph, Row, Table,
+    },
+    Frame, Terminal,
 };
 
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio::sync::{mpsc, Semaphore};
 use tokio::time::{timeout, Duration};
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -52,6 +78,54 @@ struct CliArgs {
     /// Example verbose flag
     #[arg(long, short, help = "Enable verbose mode")]
     verbose: bool,
+
+    /// Print subcommand results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Run one operation and print its result instead of starting the TUI
+    #[command(subcommand)]
+    command: Option<CliCommand>,
+}
+
+/// Non-interactive operations for scripts and CI. Results go to stdout.
+#[derive(Subcommand, Debug)]
+enum CliCommand {
+    /// Scan a range of TCP ports and grab service banners
+    Scan {
+        host: String,
+        #[arg(long, default_value_t = 1)]
+        start: u16,
+        #[arg(long, default_value_t = 1024)]
+        end: u16,
+        /// Only list open ports, skip banner grabbing
+        #[arg(long)]
+        no_banners: bool,
+    },
+    /// Ping a host (exits with status 1 if nothing answers)
+    Ping {
+        host: String,
+        #[arg(long, short, default_value_t = PING_DEFAULT_COUNT)]
+        count: u32,
+        /// ICMP payload size in bytes (system default when omitted)
+        #[arg(long, short)]
+        size: Option<u32>,
+    },
+    /// Resolve a hostname (exits with status 1 if no records are found)
+    Dns {
+        host: String,
+        /// Resolve over DNS-over-HTTPS: cloudflare, google, quad9, or an https:// URL
+        #[arg(long, value_name = "PROVIDER")]
+        doh: Option<String>,
+    },
+    /// Ping sweep a /24 subnet (`192.168.1.0/24`) or a host range of a base IP (`192.168.1`)
+    Sweep {
+        target: String,
+        #[arg(long, default_value_t = 1)]
+        start: u32,
+        #[arg(long, default_value_t = 254)]
+        end: u32,
+    },
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -89,6 +163,9 @@ async fn main() -> Result<()> {
     if args.verbose {
         print!("Verbose mode enabled...{}", LINE_ENDING);
     }
+    if let Some(command) = args.command {
+        return run_cli_command(command, args.json).await;
+    }
 
     // 2) Enable raw mode via RAII guard
     let _raw_guard = RawModeGuard::new().context("Failed to enable raw mode")?;
@@ -132,6 +209,181 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Non-Interactive Subcommands
+////////////////////////////////////////////////////////////////////////////////
+
+/// Runs one subcommand without the TUI and prints its result as text or JSON.
+async fn run_cli_command(command: CliCommand, json: bool) -> Result<()> {
+    match command {
+        CliCommand::Scan {
+            host,
+            start,
+            end,
+            no_banners,
+        } => {
+            if start > end {
+                anyhow::bail!("--start ({start}) must not be greater than --end ({end})");
+            }
+            let open_ports = scan_open_ports(&host, start, end).await;
+            let banners = if no_banners {
+                open_ports.iter().map(|&port| (port, None)).collect()
+            } else {
+                grab_banners(&host, &open_ports).await
+            };
+            if json {
+                let ports: Vec<_> = banners
+                    .iter()
+                    .map(|(port, banner)| serde_json::json!({ "port": port, "banner": banner }))
+                    .collect();
+                print_json(&serde_json::json!({
+                    "host": host,
+                    "start_port": start,
+                    "end_port": end,
+                    "open_ports": ports,
+                }))?;
+            } else {
+                for (port, banner) in banners {
+                    match banner {
+                        Some(banner) => print!("{port}\t{banner}{LINE_ENDING}"),
+                        None => print!("{port}{LINE_ENDING}"),
+                    }
+                }
+            }
+            Ok(())
+        }
+        CliCommand::Ping { host, count, size } => {
+            if let Some(size) = size {
+                check_ping_size(size).map_err(|e| anyhow::anyhow!(e))?;
+            }
+            let ping_args = get_ping_args(&host, count.max(1), size);
+            let output = tokio::process::Command::new("ping")
+                .args(&ping_args)
+                .output()
+                .await
+                .context("Failed to execute ping")?;
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let rtts: Vec<f64> = stdout.lines().filter_map(parse_ping_rtt).collect();
+            if json {
+                let sent = count.max(1);
+                print_json(&serde_json::json!({
+                    "host": host,
+                    "sent": sent,
+                    "payload_bytes": size,
+                    "received": rtts.len(),
+                    "loss_percent": 100.0 * (sent as f64 - rtts.len() as f64) / sent as f64,
+                    "rtt_ms": rtts,
+                }))?;
+            } else {
+                print!("{stdout}");
+                eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            }
+            if rtts.is_empty() {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        CliCommand::Dns { host, doh } => {
+            let answers = match &doh {
+                Some(provider) => {
+                    let endpoint = DOH_PROVIDERS
+                        .iter()
+                        .find(|(name, _)| name.eq_ignore_ascii_case(provider))
+                        .map(|(_, url)| url.to_string())
+                        .unwrap_or_else(|| provider.clone());
+                    if !endpoint.starts_with("https://") {
+                        anyhow::bail!(
+                            "Unknown DoH provider '{provider}' (use cloudflare, google, quad9, or an https:// URL)"
+                        );
+                    }
+                    let (answers, errors) = doh_resolve(&endpoint, &host).await?;
+                    for e in errors {
+                        eprint!("DoH query error: {:#}{}", e, LINE_ENDING);
+                    }
+                    answers
+                }
+                None => system_resolve(&host)?,
+            };
+            if json {
+                let records: Vec<_> = answers
+                    .iter()
+                    .map(|answer| {
+                        // The system resolver doesn't report TTLs
+                        let ttl = doh.as_ref().map(|_| answer.ttl);
+                        serde_json::json!({
+                            "type": dns_type_name(answer.record_type),
+                            "data": answer.data,
+                            "ttl": ttl,
+                        })
+                    })
+                    .collect();
+                print_json(&serde_json::json!({
+                    "host": host,
+                    "resolver": doh.as_deref().unwrap_or("system"),
+                    "records": records,
+                }))?;
+            } else {
+                for answer in &answers {
+                    print!(
+                        "{}\t{}{}",
+                        dns_type_name(answer.record_type),
+                        answer.data,
+                        LINE_ENDING
+                    );
+                }
+            }
+            if answers.is_empty() {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        CliCommand::Sweep { target, start, end } => {
+            let ips: Vec<String> = if target.contains('/') {
+                let base = parse_subnet_base(&target).map_err(anyhow::Error::msg)?;
+                (1..255).map(|i| increment_base_ip(base, i)).collect()
+            } else {
+                (start..=end).map(|id| format!("{target}.{id}")).collect()
+            };
+            let reachable = sweep_reachable(ips.into_iter()).await;
+            if json {
+                print_json(&serde_json::json!({
+                    "target": target,
+                    "reachable": reachable,
+                }))?;
+            } else {
+                for ip in reachable {
+                    print!("{ip}{LINE_ENDING}");
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Resolves through the OS resolver, shaped like DoH answers (without TTLs).
+fn system_resolve(host: &str) -> Result<Vec<DohAnswer>> {
+    let mut answers: Vec<DohAnswer> = Vec::new();
+    let addrs = format!("{host}:0")
+        .to_socket_addrs()
+        .with_context(|| format!("DNS lookup failed for {host}"))?;
+    for addr in addrs {
+        let ip = addr.ip();
+        if !answers.iter().any(|a| a.data == ip.to_string()) {
+            answers.push(DohAnswer {
+                record_type: if ip.is_ipv4() { 1 } else { 28 },
+                ttl: 0,
+                data: ip.to_string(),
+            });
+        }
+    }
+    Ok(answers)
+}
+
+fn print_json(value: &serde_json::Value) -> Result<()> {
+    print!("{}{}", serde_json::to_string_pretty(value)?, LINE_ENDING);
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Setup & Screen Clearing
 ////////////////////////////////////////////////////////////////////////////////
@@ -203,7 +455,19 @@ impl App {
                 "6) Subnet scanning",
                 "7) Firewall & VPN detection",
                 "8) Latency monitoring (continuous ping)",
-                "9) Traceroute",
+                "9) Traceroute (hostnames & locations)",
+                "10) mDNS / Bonjour service discovery",
+                "11) TLS certificate inspection",
+                "12) Multi-host ping (live table)",
+                "13) Saved scan profiles",
+                "14) Public & local IP addresses",
+                "15) Port monitor (alert on open/closed changes)",
+                "16) SSH host key fingerprints (known_hosts check)",
+                "17) Discover web servers on a subnet",
+                "18) Compare two saved scans (diff)",
+                "19) DNS cache (inspect / flush)",
+                "20) ARP / neighbor table (export)",
+                "21) Download speed test (live graph)",
                 "Q) Quit",
             ],
         }
@@ -291,21 +555,13 @@ async fn run_main_menu(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>
                         app.down();
                     }
                     KeyCode::Enter => {
-                        // Convert selected_index to a char for handle_menu_choice
-                        let choice = match app.selected_index {
-                            0 => '1',
-                            1 => '2',
-                            2 => '3',
-                            3 => '4',
-                            4 => '5',
-                            5 => '6',
-                            6 => '7',
-                            7 => '8',
-                            8 => '9',
-                            9 => 'q',
-                            _ => '?',
-                        };
-                        if !handle_menu_choice(choice).await? {
+                        // The menu key is the label text before ')', e.g. "10" or "Q"
+                        let choice = app.menu_items[app.selected_index]
+                            .split(')')
+                            .next()
+                            .unwrap_or("?")
+                            .to_string();
+                        if !handle_menu_choice(&choice, terminal).await? {
                             // If we got false => user wants to quit
                             return Ok(());
                         }
@@ -326,24 +582,39 @@ async fn run_main_menu(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Returns `Ok(true)` to continue, or `Ok(false)` if the user chose to quit.
-async fn handle_menu_choice(choice: char) -> Result<bool> {
+async fn handle_menu_choice(
+    choice: &str,
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<bool> {
     match choice {
-        '1' => ping_host_menu().await,
-        '2' => dns_lookup_menu().await,
-        '3' => port_scan_menu().await,
-        '4' => ping_sweep_menu().await,
-        '5' => {
+        "1" => ping_host_menu().await,
+        "2" => dns_lookup_menu().await,
+        "3" => port_scan_menu().await,
+        "4" => ping_sweep_menu().await,
+        "5" => {
             list_network_interfaces();
             wait_for_keypress().await;
         }
-        '6' => subnet_scan_menu().await,
-        '7' => {
+        "6" => subnet_scan_menu().await,
+        "7" => {
             detect_firewall_and_vpn();
             wait_for_keypress().await;
         }
-        '8' => latency_monitoring_menu().await,
-        '9' => traceroute_menu().await,
-        'q' | 'Q' => {
+        "8" => latency_monitoring_menu(terminal).await?,
+        "9" => traceroute_menu().await,
+        "10" => mdns_discovery_menu(terminal).await?,
+        "11" => tls_inspect_menu().await,
+        "12" => multi_ping_menu(terminal).await?,
+        "13" => scan_profiles_menu().await,
+        "14" => show_ip_addresses().await,
+        "15" => port_monitor_menu(terminal).await?,
+        "16" => ssh_fingerprint_menu().await,
+        "17" => web_discovery_menu().await,
+        "18" => compare_scans_menu().await,
+        "19" => dns_cache_menu().await,
+        "20" => neighbor_table_menu(terminal).await?,
+        "21" => speed_test_menu(terminal).await?,
+        "q" | "Q" => {
             exit_app();
             return Ok(false);
         }
@@ -367,8 +638,50 @@ async fn ping_host_menu() {
         return;
     }
 
+    let count_input = get_user_input(&format!(
+        "Number of pings (blank for {PING_DEFAULT_COUNT}):"
+    ));
+    let count = if count_input.is_empty() {
+        PING_DEFAULT_COUNT
+    } else {
+        match count_input.parse::<u32>() {
+            Ok(count) if count > 0 => count,
+            _ => {
+                print!("The count must be a whole number of at least 1.{LINE_ENDING}");
+                wait_for_keypress().await;
+                return;
+            }
+        }
+    };
+
+    let size_input =
+        get_user_input("Payload size in bytes (blank for the system default, max 65500):");
+    let size = if size_input.is_empty() {
+        None
+    } else {
+        match size_input
+            .parse::<u32>()
+            .map_err(|_| "The size must be a whole number of bytes.".to_string())
+            .and_then(|size| check_ping_size(size).map(|()| size))
+        {
+            Ok(size) => Some(size),
+            Err(message) => {
+                print!("{message}{LINE_ENDING}");
+                wait_for_keypress().await;
+                return;
+            }
+        }
+    };
+    if size.is_some_and(|size| size > PING_UNFRAGMENTED_PAYLOAD) {
+        print!(
+            "Note: payloads over {PING_UNFRAGMENTED_PAYLOAD} bytes don't fit a standard 1500-byte MTU and will be fragmented (or dropped where fragmentation isn't allowed).{LINE_ENDING}"
+        );
+    }
+
     print!("Pinging {} ...{}", host, LINE_ENDING);
-    let output = Command::new("ping").args(get_ping_args(&host)).output();
+    let output = Command::new("ping")
+        .args(get_ping_args(&host, count, size))
+        .output();
     match output {
         Ok(o) => {
             if !o.stdout.is_empty() {
@@ -387,12 +700,41 @@ async fn ping_host_menu() {
     wait_for_keypress().await;
 }
 
-/// Returns OS-specific ping arguments (e.g., 4 times).
-fn get_ping_args(host: &str) -> Vec<String> {
-    if cfg!(target_os = "windows") {
-        vec!["-n".to_string(), "4".to_string(), host.to_string()]
+/// Pings sent by the ping menu and subcommand unless told otherwise, and by sweeps.
+const PING_DEFAULT_COUNT: u32 = 4;
+
+/// Largest ICMP payload accepted: the IPv4 maximum is 65507 bytes, and
+/// Windows `ping -l` stops at 65500.
+const PING_MAX_SIZE: u32 = 65500;
+
+/// Largest payload that fits a 1500-byte Ethernet MTU without fragmenting
+/// (1500 minus the 20-byte IPv4 and 8-byte ICMP headers).
+const PING_UNFRAGMENTED_PAYLOAD: u32 = 1472;
+
+/// Builds the `ping` arguments for `count` echo requests, with an optional
+/// payload size (`-c N -s SIZE` on Unix, `-n N -l SIZE` on Windows).
+fn get_ping_args(host: &str, count: u32, size: Option<u32>) -> Vec<String> {
+    let (count_flag, size_flag) = if cfg!(target_os = "windows") {
+        ("-n", "-l")
+    } else {
+        ("-c", "-s")
+    };
+    let mut args = vec![count_flag.to_string(), count.to_string()];
+    if let Some(size) = size {
+        args.extend([size_flag.to_string(), size.to_string()]);
+    }
+    args.push(host.to_string());
+    args
+}
+
+/// Rejects payload sizes that can't be sent in a single IPv4 packet.
+fn check_ping_size(size: u32) -> Result<(), String> {
+    if size > PING_MAX_SIZE {
+        Err(format!(
+            "Payload size {size} is too large; the maximum is {PING_MAX_SIZE} bytes."
+        ))
     } else {
-        vec!["-c".to_string(), "4".to_string(), host.to_string()]
+        Ok(())
     }
 }
 
@@ -404,22 +746,22 @@ async fn dns_lookup_menu() {
         return;
     }
 
-    print!("Resolving DNS for {} ...{}", host, LINE_ENDING);
-    let socket_str = format!("{host}:0");
-    match socket_str.to_socket_addrs() {
-        Ok(addrs) => {
-            let v: Vec<_> = addrs.collect();
-            if v.is_empty() {
-                print!("No DNS records found for {}{}", host, LINE_ENDING);
-            } else {
-                print!("Resolved addresses:{}", LINE_ENDING);
-                for (i, addr) in v.iter().enumerate() {
-                    print!("  {}. {}{}", i + 1, addr, LINE_ENDING);
-                }
+    let resolver = get_user_input(
+        "Resolver: 1) System resolver (default)  2) DNS-over-HTTPS (bypasses local DNS)",
+    );
+    if resolver == "2" {
+        doh_lookup(&host).await;
+    } else {
+        print!("Resolving DNS for {} ...{}", host, LINE_ENDING);
+        let socket_str = format!("{host}:0");
+        match socket_str.to_socket_addrs() {
+            Ok(addrs) => {
+                let records: Vec<String> = addrs.map(|addr| addr.ip().to_string()).collect();
+                print_dns_records(&host, &records);
+            }
+            Err(e) => {
+                print!("DNS lookup error: {}{}", e, LINE_ENDING);
             }
-        }
-        Err(e) => {
-            print!("DNS lookup error: {}{}", e, LINE_ENDING);
         }
     }
 
@@ -427,376 +769,3624 @@ async fn dns_lookup_menu() {
     wait_for_keypress().await;
 }
 
-async fn port_scan_menu() {
-    let host = get_user_input("Enter host/IP to port-scan:");
-    if host.is_empty() {
-        print!("No host specified.{}", LINE_ENDING);
-        wait_for_keypress().await;
-        return;
+/// Prints resolved records as a numbered list (shared by every resolver).
+fn print_dns_records(host: &str, records: &[String]) {
+    if records.is_empty() {
+        print!("No DNS records found for {}{}", host, LINE_ENDING);
+    } else {
+        print!("Resolved addresses:{}", LINE_ENDING);
+        for (i, record) in records.iter().enumerate() {
+            print!("  {}. {}{}", i + 1, record, LINE_ENDING);
+        }
     }
+}
 
-    let start_port_str = get_user_input("Enter start port:");
-    let end_port_str = get_user_input("Enter end port:");
-    let start_port = start_port_str.parse().unwrap_or(1);
-    let end_port = end_port_str.parse().unwrap_or(1024);
+////////////////////////////////////////////////////////////////////////////////
+// Local DNS Resolver Cache
+////////////////////////////////////////////////////////////////////////////////
 
-    print!("Scanning TCP ports on {host} from {start_port} to {end_port}...{LINE_ENDING}");
+/// Longest cache listing printed before the rest is summarized (`ipconfig /displaydns`
+/// can run to thousands of lines).
+const DNS_CACHE_PREVIEW_LINES: usize = 60;
 
-    let mut tasks = Vec::new();
-    for port in start_port..=end_port {
-        let host_clone = host.clone();
-        tasks.push(tokio::spawn(
-            async move { scan_port(&host_clone, port).await },
-        ));
+/// Commands that show the OS resolver cache, most preferred first; the first one
+/// installed is used.
+fn dns_cache_inspect_commands() -> &'static [&'static [&'static str]] {
+    if cfg!(target_os = "windows") {
+        &[&["ipconfig", "/displaydns"]]
+    } else if cfg!(target_os = "macos") {
+        &[&["dscacheutil", "-statistics"]]
+    } else {
+        &[
+            &["resolvectl", "statistics"],
+            &["systemd-resolve", "--statistics"],
+        ]
     }
+}
 
-    let mut open_ports = Vec::new();
-    for t in tasks {
-        if let Ok(Some(port)) = t.await {
-            open_ports.push(port);
+/// Commands that flush the OS resolver cache; every installed group is run in
+/// turn, using the first command of each group that exists.
+fn dns_cache_flush_commands() -> &'static [&'static [&'static [&'static str]]] {
+    if cfg!(target_os = "windows") {
+        &[&[&["ipconfig", "/flushdns"]]]
+    } else if cfg!(target_os = "macos") {
+        // Recent macOS versions also need mDNSResponder to drop its cache
+        &[
+            &[&["dscacheutil", "-flushcache"]],
+            &[&["killall", "-HUP", "mDNSResponder"]],
+        ]
+    } else {
+        &[&[
+            &["resolvectl", "flush-caches"],
+            &["systemd-resolve", "--flush-caches"],
+        ]]
+    }
+}
+
+/// Runs the first of `candidates` (program followed by its arguments) that is
+/// installed. Returns the command line with its output, or `None` if none exist.
+fn run_first_available(candidates: &[&[&str]]) -> Result<Option<(String, std::process::Output)>> {
+    for command in candidates {
+        let Some((program, args)) = command.split_first() else {
+            continue;
+        };
+        match Command::new(program).args(args).output() {
+            Ok(output) => return Ok(Some((command.join(" "), output))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e).with_context(|| format!("Failed to run `{program}`")),
         }
     }
+    Ok(None)
+}
 
-    if open_ports.is_empty() {
+/// Prints a command's output (up to `max_lines` lines) and whether it succeeded.
+fn print_command_output(command_line: &str, output: &std::process::Output, max_lines: usize) {
+    print!("$ {command_line}{LINE_ENDING}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    for line in lines.iter().take(max_lines) {
+        print!("{line}{LINE_ENDING}");
+    }
+    if lines.len() > max_lines {
         print!(
-            "No open TCP ports found in the specified range.{}",
-            LINE_ENDING
+            "... {} more line(s) not shown{LINE_ENDING}",
+            lines.len() - max_lines
         );
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    for line in stderr.lines() {
+        print!("{line}{LINE_ENDING}");
+    }
+    if output.status.success() {
+        print!("{}{LINE_ENDING}", "Done.".green());
     } else {
-        print!("Open TCP ports: {:?}{}", open_ports, LINE_ENDING);
+        print!(
+            "{}{LINE_ENDING}",
+            format!("`{command_line}` failed ({}).", output.status).red()
+        );
+        print!(
+            "Flushing or inspecting the cache may need administrator/root privileges.{LINE_ENDING}"
+        );
     }
+}
 
-    print!("Press any key to return to main menu...{}", LINE_ENDING);
+/// Shows the OS resolver cache and optionally flushes it, e.g. after a DNS
+/// record has changed but this machine still resolves the old address.
+async fn dns_cache_menu() {
+    print!("Local DNS cache{LINE_ENDING}");
+    match run_first_available(dns_cache_inspect_commands()) {
+        Ok(Some((command_line, output))) => {
+            print_command_output(&command_line, &output, DNS_CACHE_PREVIEW_LINES)
+        }
+        Ok(None) => print!(
+            "No DNS cache tool found ({}). This system may not run a caching resolver.{LINE_ENDING}",
+            dns_cache_inspect_commands()
+                .iter()
+                .map(|c| c[0])
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Err(e) => print!("{e:#}{LINE_ENDING}"),
+    }
+    print!("{LINE_ENDING}");
+
+    let flush = matches!(
+        get_user_input("Flush the DNS cache now? (y/N):")
+            .to_lowercase()
+            .as_str(),
+        "y" | "yes"
+    );
+    if flush {
+        for group in dns_cache_flush_commands() {
+            match run_first_available(group) {
+                Ok(Some((command_line, output))) => {
+                    print_command_output(&command_line, &output, DNS_CACHE_PREVIEW_LINES)
+                }
+                Ok(None) => print!(
+                    "`{}` is not available on this system; skipped.{LINE_ENDING}",
+                    group[0].join(" ")
+                ),
+                Err(e) => print!("{e:#}{LINE_ENDING}"),
+            }
+        }
+    }
+
+    print!("Press any key to return to main menu...{LINE_ENDING}");
     wait_for_keypress().await;
 }
 
-/// Attempt to connect to a (host, port). Returns `Some(port)` if open, else `None`.
-async fn scan_port(host: &str, port: u16) -> Option<u16> {
-    let addr = format!("{host}:{port}");
-    match timeout(Duration::from_millis(500), TcpStream::connect(&addr)).await {
-        Ok(Ok(_)) => Some(port), // Connected => open
-        _ => None,               // Timed out or error => closed/filtered
-    }
+////////////////////////////////////////////////////////////////////////////////
+// ARP / Neighbor Table
+////////////////////////////////////////////////////////////////////////////////
+
+/// One entry of the system's ARP (IPv4) or neighbor (IPv6) table.
+#[derive(Debug, Clone, Serialize)]
+struct Neighbor {
+    ip: String,
+    /// Lowercase, colon-separated; empty while the address is unresolved
+    mac: String,
+    interface: String,
+    /// e.g. `REACHABLE`, `STALE`, `INCOMPLETE` (Linux) or `dynamic`/`static` (Windows)
+    state: String,
 }
 
-async fn ping_sweep_menu() {
-    let base_ip = get_user_input("Enter base IPv4 (e.g. 192.168.1):");
-    if base_ip.is_empty() {
-        print!("No base IP specified.{}", LINE_ENDING);
-        wait_for_keypress().await;
-        return;
-    }
+/// Reads the neighbor table without sending any traffic: `ip neigh` on Linux
+/// (or `/proc/net/arp` without iproute2), `arp -a` elsewhere. Returns where
+/// the entries came from along with them, sorted by address.
+fn read_neighbor_table() -> Result<(String, Vec<Neighbor>)> {
+    let (source, mut neighbors) = if cfg!(target_os = "linux") {
+        match run_first_available(&[&["ip", "neigh", "show"]])? {
+            Some((command_line, output)) => (
+                command_line,
+                parse_ip_neigh(&String::from_utf8_lossy(&output.stdout)),
+            ),
+            None => {
+                let table =
+                    fs::read_to_string("/proc/net/arp").context("Failed to read /proc/net/arp")?;
+                ("/proc/net/arp".to_string(), parse_proc_net_arp(&table))
+            }
+        }
+    } else {
+        match run_first_available(&[&["arp", "-a"]])? {
+            Some((command_line, output)) => (
+                command_line,
+                parse_arp_a(&String::from_utf8_lossy(&output.stdout)),
+            ),
+            None => anyhow::bail!("`arp` is not available on this system"),
+        }
+    };
+    neighbors.sort_by_key(|n| {
+        (
+            n.ip.parse::<std::net::IpAddr>().ok(),
+            n.interface.clone(),
+            n.ip.clone(),
+        )
+    });
+    Ok((source, neighbors))
+}
 
-    let start_id_str = get_user_input("Enter start host ID (e.g. 1):");
-    let end_id_str = get_user_input("Enter end host ID (e.g. 10):");
-    let start_id = start_id_str.parse().unwrap_or(1);
-    let end_id = end_id_str.parse().unwrap_or(10);
+/// Parses `ip neigh` lines such as
+/// `192.168.1.1 dev eth0 lladdr aa:bb:cc:dd:ee:ff REACHABLE` or
+/// `192.168.1.7 dev eth0 FAILED`.
+fn parse_ip_neigh(output: &str) -> Vec<Neighbor> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let ip = fields.first()?;
+            let after = |key: &str| {
+                fields
+                    .iter()
+                    .position(|f| *f == key)
+                    .and_then(|i| fields.get(i + 1))
+                    .copied()
+            };
+            let state = fields
+                .last()
+                .filter(|f| f.chars().all(|c| c.is_ascii_uppercase()))
+                .copied()
+                .unwrap_or("");
+            Some(Neighbor {
+                ip: ip.to_string(),
+                mac: after("lladdr").map(normalize_mac).unwrap_or_default(),
+                interface: after("dev").unwrap_or("").to_string(),
+                state: state.to_string(),
+            })
+        })
+        .collect()
+}
 
-    print!("Performing ping sweep from {base_ip}.{start_id} to {base_ip}.{end_id}{LINE_ENDING}");
+/// Parses `/proc/net/arp`: a header line, then
+/// `IP address  HW type  Flags  HW address  Mask  Device` columns.
+fn parse_proc_net_arp(table: &str) -> Vec<Neighbor> {
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [ip, _, flags, mac, _, device] = fields[..] else {
+                return None;
+            };
+            // ATF_COM (0x2) = resolved, ATF_PERM (0x4) = static entry
+            let flags = u32::from_str_radix(flags.trim_start_matches("0x"), 16).unwrap_or(0);
+            let state = if flags & 0x4 != 0 {
+                "PERMANENT"
+            } else if flags & 0x2 != 0 {
+                "COMPLETE"
+            } else {
+                "INCOMPLETE"
+            };
+            Some(Neighbor {
+                ip: ip.to_string(),
+                mac: if flags & 0x2 != 0 {
+                    normalize_mac(mac)
+                } else {
+                    String::new()
+                },
+                interface: device.to_string(),
+                state: state.to_string(),
+            })
+        })
+        .collect()
+}
 
-    let mut tasks = Vec::new();
-    for id in start_id..=end_id {
-        let ip_string = format!("{base_ip}.{id}");
-        tasks.push(tokio::spawn(async move {
-            if is_reachable(&ip_string).await {
-                Some(ip_string)
+/// Parses `arp -a` from Windows (`Interface:` sections of
+/// `address  physical-address  type` rows) and macOS/BSD
+/// (`? (192.168.1.1) at aa:bb:cc:dd:ee:ff on en0 ifscope [ethernet]`).
+fn parse_arp_a(output: &str) -> Vec<Neighbor> {
+    let mut neighbors = Vec::new();
+    let mut interface = String::new();
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if let Some(rest) = line.trim_start().strip_prefix("Interface:") {
+            interface = rest.split_whitespace().next().unwrap_or("").to_string();
+        } else if let Some(at) = fields.iter().position(|f| *f == "at") {
+            let Some(ip) = fields
+                .get(at.wrapping_sub(1))
+                .map(|f| f.trim_matches(|c| c == '(' || c == ')'))
+            else {
+                continue;
+            };
+            let mac = fields.get(at + 1).copied().unwrap_or("");
+            let on = fields.iter().position(|f| *f == "on");
+            let state = if mac.starts_with('(') {
+                "incomplete"
+            } else if fields.contains(&"permanent") {
+                "permanent"
             } else {
-                None
+                "dynamic"
+            };
+            neighbors.push(Neighbor {
+                ip: ip.to_string(),
+                mac: if mac.starts_with('(') {
+                    String::new()
+                } else {
+                    normalize_mac(mac)
+                },
+                interface: on
+                    .and_then(|i| fields.get(i + 1))
+                    .unwrap_or(&"")
+                    .to_string(),
+                state: state.to_string(),
+            });
+        } else if let [ip, mac, state] = fields[..] {
+            if ip.parse::<std::net::IpAddr>().is_ok() {
+                neighbors.push(Neighbor {
+                    ip: ip.to_string(),
+                    mac: normalize_mac(mac),
+                    interface: interface.clone(),
+                    state: state.to_string(),
+                });
             }
-        }));
-    }
-
-    let mut reachable = Vec::new();
-    for t in tasks {
-        if let Ok(Some(ip)) = t.await {
-            reachable.push(ip);
         }
     }
+    neighbors
+}
 
-    if reachable.is_empty() {
-        print!("No hosts responded to ping in that range.{}", LINE_ENDING);
+/// `AA-BB-CC-0-E-FF` or `aa:bb:cc:0:e:ff` -> `aa:bb:cc:00:0e:ff`.
+fn normalize_mac(mac: &str) -> String {
+    mac.split([':', '-'])
+        .map(|octet| format!("{:0>2}", octet.to_ascii_lowercase()))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Writes the table as JSON when `path` ends in `.json`, otherwise as CSV.
+fn export_neighbors(path: &str, neighbors: &[Neighbor]) -> Result<()> {
+    let contents = if path.to_ascii_lowercase().ends_with(".json") {
+        serde_json::to_string_pretty(neighbors)?
     } else {
-        print!("Hosts responding to ping:{}", LINE_ENDING);
-        for ip in reachable {
-            print!("  {ip}{}", LINE_ENDING);
+        let mut csv = String::from("ip,mac,interface,state\n");
+        for n in neighbors {
+            csv.push_str(&format!("{},{},{},{}\n", n.ip, n.mac, n.interface, n.state));
+        }
+        csv
+    };
+    fs::write(path, contents).with_context(|| format!("Failed to write {path}"))
+}
+
+/// Shows the neighbor table until Esc/q/Enter; `e` exports it and `r` re-reads it.
+async fn neighbor_table_menu(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<()> {
+    let (mut source, mut neighbors) = match read_neighbor_table() {
+        Ok(table) => table,
+        Err(e) => {
+            print!("Could not read the neighbor table: {e:#}{LINE_ENDING}");
+            print!("Press any key to return to main menu...{LINE_ENDING}");
+            wait_for_keypress().await;
+            return Ok(());
+        }
+    };
+    let mut status: Option<(String, Color)> = None;
+
+    clear_screen(terminal)?;
+    loop {
+        terminal.draw(|frame| draw_neighbor_table(frame, &source, &neighbors, status.as_ref()))?;
+
+        if let Ok(CEvent::Key(key_event)) = event::read() {
+            match key_event.code {
+                KeyCode::Char('e') => {
+                    clear_screen(terminal)?;
+                    let path = get_user_input(
+                        "Export to file (.json for JSON, anything else is CSV; blank = neighbors.csv):",
+                    );
+                    let path = if path.is_empty() {
+                        "neighbors.csv".to_string()
+                    } else {
+                        path
+                    };
+                    status = Some(match export_neighbors(&path, &neighbors) {
+                        Ok(()) => (
+                            format!("Exported {} entries to {path}", neighbors.len()),
+                            Color::Green,
+                        ),
+                        Err(e) => (format!("{e:#}"), Color::Red),
+                    });
+                    clear_screen(terminal)?;
+                }
+                KeyCode::Char('r') => {
+                    status = Some(match read_neighbor_table() {
+                        Ok(table) => {
+                            (source, neighbors) = table;
+                            ("Refreshed.".to_string(), Color::Green)
+                        }
+                        Err(e) => (format!("{e:#}"), Color::Red),
+                    });
+                }
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => break,
+                _ => {}
+            }
         }
     }
+    clear_screen(terminal)?;
+    Ok(())
+}
 
-    print!("Press any key to return to main menu...{}", LINE_ENDING);
-    wait_for_keypress().await;
+fn draw_neighbor_table(
+    frame: &mut Frame,
+    source: &str,
+    neighbors: &[Neighbor],
+    status: Option<&(String, Color)>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+        .split(frame.area());
+
+    let header = Row::new(vec!["IP Address", "MAC Address", "Interface", "State"]).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows: Vec<Row> = neighbors
+        .iter()
+        .map(|n| {
+            let color = match n.state.to_ascii_uppercase().as_str() {
+                "REACHABLE" | "COMPLETE" | "PERMANENT" | "STATIC" | "DYNAMIC" => Color::Green,
+                "STALE" | "DELAY" | "PROBE" => Color::Yellow,
+                "FAILED" | "INCOMPLETE" => Color::Red,
+                _ => Color::White,
+            };
+            let mac = if n.mac.is_empty() {
+                "(unresolved)".to_string()
+            } else {
+                n.mac.clone()
+            };
+            Row::new(vec![
+                n.ip.clone(),
+                mac,
+                n.interface.clone(),
+                n.state.clone(),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let title = format!(
+        " Neighbor Table ({} entries, from `{source}`) ",
+        neighbors.len()
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(40),
+            Constraint::Length(19),
+            Constraint::Length(16),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Cyan)),
+    )
+    .column_spacing(1);
+    frame.render_widget(table, chunks[0]);
+
+    let (text, color) = match status {
+        Some((message, color)) => (format!("{message} | e = export | r = refresh | Esc/q/Enter = back"), *color),
+        None if neighbors.is_empty() => (
+            "The table is empty (no recent traffic to other hosts). r = refresh | Esc/q/Enter = back".to_string(),
+            Color::White,
+        ),
+        None => (
+            "e = export (CSV/JSON) | r = refresh | Esc/q/Enter = back".to_string(),
+            Color::White,
+        ),
+    };
+    let footer = Paragraph::new(text)
+        .style(Style::default().fg(color))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, chunks[1]);
 }
 
-async fn is_reachable(ip: &str) -> bool {
-    let output = Command::new("ping").args(get_ping_args(ip)).output();
-    match output {
-        Ok(o) => {
-            let stdout = String::from_utf8_lossy(&o.stdout).to_lowercase();
-            // naive check: "0% packet loss" or " no loss" might indicate success
-            stdout.contains("0% packet loss") || stdout.contains(" no loss")
+////////////////////////////////////////////////////////////////////////////////
+// DNS-over-HTTPS (JSON API)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Well-known DoH providers that speak the `application/dns-json` format.
+const DOH_PROVIDERS: &[(&str, &str)] = &[
+    ("Cloudflare", "https://cloudflare-dns.com/dns-query"),
+    ("Google", "https://dns.google/resolve"),
+    ("Quad9", "https://dns.quad9.net:5053/dns-query"),
+];
+
+const DOH_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    #[serde(rename = "TTL")]
+    ttl: u32,
+    data: String,
+}
+
+async fn doh_lookup(host: &str) {
+    let mut prompt = String::from("DoH provider:");
+    for (i, (name, url)) in DOH_PROVIDERS.iter().enumerate() {
+        prompt.push_str(&format!("{}  {}) {} ({})", LINE_ENDING, i + 1, name, url));
+    }
+    prompt.push_str(&format!(
+        "{}  C) Custom URL{}Choice (default 1):",
+        LINE_ENDING, LINE_ENDING
+    ));
+    let choice = get_user_input(&prompt);
+
+    let endpoint = if choice.eq_ignore_ascii_case("c") {
+        let url = get_user_input("Enter DoH JSON endpoint URL (https://...):");
+        if !url.starts_with("https://") {
+            print!("DoH endpoint must be an https:// URL.{}", LINE_ENDING);
+            return;
         }
-        Err(_) => false,
+        url
+    } else {
+        let index = choice.parse::<usize>().unwrap_or(1).saturating_sub(1);
+        let (_, url) = DOH_PROVIDERS
+            .get(index)
+            .copied()
+            .unwrap_or(DOH_PROVIDERS[0]);
+        url.to_string()
+    };
+
+    print!(
+        "Resolving DNS for {} via {} ...{}",
+        host, endpoint, LINE_ENDING
+    );
+    let (answers, errors) = match doh_resolve(&endpoint, host).await {
+        Ok(result) => result,
+        Err(e) => {
+            print!("{:#}{}", e, LINE_ENDING);
+            return;
+        }
+    };
+    for e in errors {
+        print!("DoH query error: {:#}{}", e, LINE_ENDING);
     }
+    let records: Vec<String> = answers
+        .iter()
+        .map(|answer| {
+            format!(
+                "{} ({}, TTL {}s)",
+                answer.data,
+                dns_type_name(answer.record_type),
+                answer.ttl
+            )
+        })
+        .collect();
+    print_dns_records(host, &records);
 }
 
-fn list_network_interfaces() {
-    print!("Network Interfaces:{}", LINE_ENDING);
-    // Naive approach: shell out to ifconfig or ipconfig
-    if cfg!(target_os = "windows") {
-        let _ = Command::new("ipconfig").status();
-    } else {
-        let _ = Command::new("ifconfig").status();
-        // Or "ip addr show"
+/// Queries A and AAAA records in parallel. Returns the de-duplicated answers
+/// along with the error of each query that failed.
+async fn doh_resolve(endpoint: &str, host: &str) -> Result<(Vec<DohAnswer>, Vec<anyhow::Error>)> {
+    let client = reqwest::Client::builder()
+        .timeout(DOH_TIMEOUT)
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let (v4, v6) = tokio::join!(
+        doh_query(&client, endpoint, host, "A"),
+        doh_query(&client, endpoint, host, "AAAA"),
+    );
+    let mut answers: Vec<DohAnswer> = Vec::new();
+    let mut errors = Vec::new();
+    for result in [v4, v6] {
+        match result {
+            Ok(found) => {
+                for answer in found {
+                    // CNAMEs show up in both the A and AAAA answers
+                    if !answers
+                        .iter()
+                        .any(|a| a.data == answer.data && a.record_type == answer.record_type)
+                    {
+                        answers.push(answer);
+                    }
+                }
+            }
+            Err(e) => errors.push(e),
+        }
     }
+    Ok((answers, errors))
 }
 
-async fn subnet_scan_menu() {
-    let cidr_input = get_user_input("Enter subnet in CIDR notation (e.g., 192.168.1.0/24):");
-    if cidr_input.is_empty() {
-        print!("No subnet specified.{}", LINE_ENDING);
-        wait_for_keypress().await;
-        return;
+/// Sends one JSON DoH query and returns its answer section.
+async fn doh_query(
+    client: &reqwest::Client,
+    endpoint: &str,
+    host: &str,
+    record_type: &str,
+) -> Result<Vec<DohAnswer>> {
+    let url = reqwest::Url::parse_with_params(endpoint, &[("name", host), ("type", record_type)])
+        .context("invalid DoH endpoint URL")?;
+    let response: DohResponse = client
+        .get(url)
+        .header("accept", "application/dns-json")
+        .send()
+        .await
+        .context("request failed")?
+        .error_for_status()
+        .context("provider returned an error")?
+        .json()
+        .await
+        .context("invalid DoH JSON response")?;
+
+    // Status follows DNS RCODEs: 0 = NOERROR, 3 = NXDOMAIN, ...
+    match response.status {
+        0 => Ok(response.answer),
+        3 => Ok(Vec::new()),
+        code => Err(anyhow::anyhow!("{record_type} query failed (RCODE {code})")),
     }
+}
 
-    print!("Subnet scanning {cidr_input}{LINE_ENDING}");
+fn dns_type_name(record_type: u16) -> String {
+    match record_type {
+        1 => "A".to_string(),
+        5 => "CNAME".to_string(),
+        28 => "AAAA".to_string(),
+        other => format!("TYPE{other}"),
+    }
+}
 
-    let parts: Vec<&str> = cidr_input.split('/').collect();
-    if parts.len() != 2 {
-        print!("Invalid CIDR format.{}", LINE_ENDING);
+async fn port_scan_menu() {
+    let host = get_user_input("Enter host/IP to port-scan:");
+    if host.is_empty() {
+        print!("No host specified.{}", LINE_ENDING);
         wait_for_keypress().await;
         return;
     }
 
-    let base_ip_str = parts[0];
-    let cidr_bits: u8 = parts[1].parse().unwrap_or(24);
+    let start_port_str = get_user_input("Enter start port:");
+    let end_port_str = get_user_input("Enter end port:");
+    let scan = ScanParams::PortScan {
+        host,
+        start_port: start_port_str.parse().unwrap_or(1),
+        end_port: end_port_str.parse().unwrap_or(1024),
+    };
 
-    if cidr_bits != 24 {
+    scan.run().await;
+    offer_to_save_profile(scan);
+    print!("Press any key to return to main menu...{}", LINE_ENDING);
+    wait_for_keypress().await;
+}
+
+/// Scans and prints the open ports; returns each open port with its banner.
+async fn run_port_scan(host: &str, start_port: u16, end_port: u16) -> BTreeMap<String, String> {
+    print!("Scanning TCP ports on {host} from {start_port} to {end_port}...{LINE_ENDING}");
+
+    let mut findings = BTreeMap::new();
+    let open_ports = scan_open_ports(host, start_port, end_port).await;
+    if open_ports.is_empty() {
         print!(
-            "Only /24 subnets are supported in this demo.{}",
+            "No open TCP ports found in the specified range.{}",
             LINE_ENDING
         );
+    } else {
+        print!("Open TCP ports: {:?}{}", open_ports, LINE_ENDING);
+
+        // Fingerprint what is listening on each open port
+        print!("Grabbing service banners...{}", LINE_ENDING);
+        print!("{:<7}BANNER{}", "PORT", LINE_ENDING);
+        for (port, banner) in grab_banners(host, &open_ports).await {
+            let banner = banner.unwrap_or_else(|| "(no banner)".to_string());
+            print!("{:<7}{}{}", port, banner, LINE_ENDING);
+            findings.insert(port.to_string(), banner);
+        }
+    }
+    findings
+}
+
+/// Checks every port in the range concurrently and returns the open ones, sorted.
+async fn scan_open_ports(host: &str, start_port: u16, end_port: u16) -> Vec<u16> {
+    let mut tasks = Vec::new();
+    for port in start_port..=end_port {
+        let host_clone = host.to_string();
+        tasks.push(tokio::spawn(
+            async move { scan_port(&host_clone, port).await },
+        ));
+    }
+
+    let mut open_ports = Vec::new();
+    for t in tasks {
+        if let Ok(Some(port)) = t.await {
+            open_ports.push(port);
+        }
+    }
+    open_ports.sort_unstable();
+    open_ports
+}
+
+/// Grabs the banner of each open port concurrently, in the order given.
+async fn grab_banners(host: &str, ports: &[u16]) -> Vec<(u16, Option<String>)> {
+    let mut tasks = Vec::new();
+    for &port in ports {
+        let host_clone = host.to_string();
+        tasks.push(tokio::spawn(async move {
+            (port, grab_banner(&host_clone, port).await)
+        }));
+    }
+
+    let mut banners = Vec::new();
+    for t in tasks {
+        if let Ok(result) = t.await {
+            banners.push(result);
+        }
+    }
+    banners
+}
+
+/// Ports that usually speak HTTP and need a request before they say anything.
+const HTTP_PROBE_PORTS: &[u16] = &[80, 8000, 8008, 8080, 8888];
+
+/// Ports that expect a TLS handshake first (HTTPS, SMTPS, LDAPS, DNS over TLS,
+/// IMAPS, POP3S). A plaintext probe gets nothing useful back, so they are skipped.
+const TLS_PORTS: &[u16] = &[443, 465, 636, 853, 993, 995, 8443];
+
+/// How long to wait for a service to send its greeting or reply to a probe.
+const BANNER_READ_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Longest banner shown next to a port.
+const BANNER_MAX_CHARS: usize = 80;
+
+/// Connects to an open port and captures what the service says. Services that
+/// greet first (SSH, FTP, SMTP, ...) are just read; HTTP-ish ports get a
+/// minimal `HEAD` request. Returns `None` when nothing readable comes back,
+/// and for TLS ports.
+async fn grab_banner(host: &str, port: u16) -> Option<String> {
+    if TLS_PORTS.contains(&port) {
+        return None;
+    }
+    let addr = format!("{host}:{port}");
+    let mut stream = timeout(Duration::from_millis(500), TcpStream::connect(&addr))
+        .await
+        .ok()?
+        .ok()?;
+
+    if HTTP_PROBE_PORTS.contains(&port) {
+        let probe = format!("HEAD / HTTP/1.0\r\nHost: {host}\r\n\r\n");
+        stream.write_all(probe.as_bytes()).await.ok()?;
+    }
+
+    let mut buf = [0u8; 1024];
+    let n = timeout(BANNER_READ_TIMEOUT, stream.read(&mut buf))
+        .await
+        .ok()?
+        .ok()?;
+    summarize_banner(&String::from_utf8_lossy(&buf[..n]))
+}
+
+/// Reduces a raw response to one printable line. For HTTP responses the status
+/// line is combined with the `Server` header, which is the useful part.
+fn summarize_banner(raw: &str) -> Option<String> {
+    let mut lines = raw.lines().map(str::trim).filter(|l| !l.is_empty());
+    let first = lines.next()?;
+    let mut summary = if first.starts_with("HTTP/") {
+        match raw
+            .lines()
+            .find(|l| l.to_ascii_lowercase().starts_with("server:"))
+        {
+            Some(server) => format!("{} | {}", first, server.trim()),
+            None => first.to_string(),
+        }
+    } else {
+        first.to_string()
+    };
+
+    summary = summary
+        .chars()
+        .map(|c| if c.is_control() { '.' } else { c })
+        .collect();
+    if summary.chars().count() > BANNER_MAX_CHARS {
+        summary = summary
+            .chars()
+            .take(BANNER_MAX_CHARS - 3)
+            .collect::<String>()
+            + "...";
+    }
+    Some(summary)
+}
+
+/// Attempt to connect to a (host, port). Returns `Some(port)` if open, else `None`.
+async fn scan_port(host: &str, port: u16) -> Option<u16> {
+    let addr = format!("{host}:{port}");
+    match timeout(Duration::from_millis(500), TcpStream::connect(&addr)).await {
+        Ok(Ok(_)) => Some(port), // Connected => open
+        _ => None,               // Timed out or error => closed/filtered
+    }
+}
+
+async fn ping_sweep_menu() {
+    let base_ip = get_user_input("Enter base IPv4 (e.g. 192.168.1):");
+    if base_ip.is_empty() {
+        print!("No base IP specified.{}", LINE_ENDING);
         wait_for_keypress().await;
         return;
     }
 
+    let start_id_str = get_user_input("Enter start host ID (e.g. 1):");
+    let end_id_str = get_user_input("Enter end host ID (e.g. 10):");
+    let scan = ScanParams::PingSweep {
+        base_ip,
+        start_id: start_id_str.parse().unwrap_or(1),
+        end_id: end_id_str.parse().unwrap_or(10),
+    };
+
+    scan.run().await;
+    offer_to_save_profile(scan);
+    print!("Press any key to return to main menu...{}", LINE_ENDING);
+    wait_for_keypress().await;
+}
+
+/// Sweeps and prints the responding hosts; returns them as scan findings.
+async fn run_ping_sweep(base_ip: &str, start_id: u32, end_id: u32) -> BTreeMap<String, String> {
+    print!("Performing ping sweep from {base_ip}.{start_id} to {base_ip}.{end_id}{LINE_ENDING}");
+
+    let reachable = sweep_reachable((start_id..=end_id).map(|id| format!("{base_ip}.{id}"))).await;
+    if reachable.is_empty() {
+        print!("No hosts responded to ping in that range.{}", LINE_ENDING);
+    } else {
+        print!("Hosts responding to ping:{}", LINE_ENDING);
+        for ip in &reachable {
+            print!("  {ip}{}", LINE_ENDING);
+        }
+    }
+    host_findings(reachable)
+}
+
+/// Pings every address concurrently and returns the ones that answered, in input order.
+async fn sweep_reachable(ips: impl Iterator<Item = String>) -> Vec<String> {
     let mut tasks = Vec::new();
-    for i in 1..255 {
-        let ip_string = increment_base_ip(base_ip_str, i);
+    for ip_string in ips {
         tasks.push(tokio::spawn(async move {
             if is_reachable(&ip_string).await {
                 Some(ip_string)
             } else {
                 None
             }
-        }));
-    }
+        }));
+    }
+
+    let mut reachable = Vec::new();
+    for t in tasks {
+        if let Ok(Some(ip)) = t.await {
+            reachable.push(ip);
+        }
+    }
+    reachable
+}
+
+async fn is_reachable(ip: &str) -> bool {
+    let output = Command::new("ping")
+        .args(get_ping_args(ip, PING_DEFAULT_COUNT, None))
+        .output();
+    match output {
+        Ok(o) => {
+            let stdout = String::from_utf8_lossy(&o.stdout).to_lowercase();
+            // naive check: "0% packet loss" or " no loss" might indicate success
+            stdout.contains("0% packet loss") || stdout.contains(" no loss")
+        }
+        Err(_) => false,
+    }
+}
+
+fn list_network_interfaces() {
+    print!("Network Interfaces:{}", LINE_ENDING);
+    // Naive approach: shell out to ifconfig or ipconfig
+    if cfg!(target_os = "windows") {
+        let _ = Command::new("ipconfig").status();
+    } else {
+        let _ = Command::new("ifconfig").status();
+        // Or "ip addr show"
+    }
+}
+
+async fn subnet_scan_menu() {
+    let cidr_input = get_user_input("Enter subnet in CIDR notation (e.g., 192.168.1.0/24):");
+    if cidr_input.is_empty() {
+        print!("No subnet specified.{}", LINE_ENDING);
+        wait_for_keypress().await;
+        return;
+    }
+
+    if let Err(message) = parse_subnet_base(&cidr_input) {
+        print!("{message}{LINE_ENDING}");
+        wait_for_keypress().await;
+        return;
+    }
+
+    let scan = ScanParams::SubnetScan { cidr: cidr_input };
+    scan.run().await;
+    offer_to_save_profile(scan);
+    print!("Press any key to return to main menu...{}", LINE_ENDING);
+    wait_for_keypress().await;
+}
+
+/// Splits `a.b.c.d/24` into its base address. Only /24 subnets are supported.
+fn parse_subnet_base(cidr: &str) -> Result<&str, &'static str> {
+    let (base_ip, bits) = cidr.split_once('/').ok_or("Invalid CIDR format.")?;
+    if bits.parse::<u8>().unwrap_or(24) != 24 {
+        return Err("Only /24 subnets are supported in this demo.");
+    }
+    Ok(base_ip)
+}
+
+/// Scans and prints the responding hosts; `None` when the CIDR is invalid.
+async fn run_subnet_scan(cidr: &str) -> Option<BTreeMap<String, String>> {
+    print!("Subnet scanning {cidr}{LINE_ENDING}");
+    let base_ip_str = match parse_subnet_base(cidr) {
+        Ok(base) => base,
+        Err(message) => {
+            print!("{message}{LINE_ENDING}");
+            return None;
+        }
+    };
+
+    let reachable = sweep_reachable((1..255).map(|i| increment_base_ip(base_ip_str, i))).await;
+    if reachable.is_empty() {
+        print!(
+            "No hosts responded to ping in that /24 subnet.{}",
+            LINE_ENDING
+        );
+    } else {
+        print!(
+            "Hosts responding to ping in {base_ip_str}/24:{}",
+            LINE_ENDING
+        );
+        for ip in &reachable {
+            print!("  {ip}{}", LINE_ENDING);
+        }
+    }
+    Some(host_findings(reachable))
+}
+
+/// Responding hosts as scan findings; a host has no detail beyond being up.
+fn host_findings(reachable: Vec<String>) -> BTreeMap<String, String> {
+    reachable
+        .into_iter()
+        .map(|ip| (ip, "up".to_string()))
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Saved Scan Profiles
+////////////////////////////////////////////////////////////////////////////////
+
+/// File (in the home directory) holding the saved scan profiles as JSON.
+const PROFILES_FILE: &str = ".net_commander_profiles.json";
+
+/// The inputs of a repeatable scan, tagged with the operation that uses them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+enum ScanParams {
+    PortScan {
+        host: String,
+        start_port: u16,
+        end_port: u16,
+    },
+    PingSweep {
+        base_ip: String,
+        start_id: u32,
+        end_id: u32,
+    },
+    SubnetScan {
+        cidr: String,
+    },
+}
+
+impl ScanParams {
+    /// Runs the scan, printing its results, and adds them to the scan history.
+    async fn run(&self) {
+        let findings = match self {
+            ScanParams::PortScan {
+                host,
+                start_port,
+                end_port,
+            } => Some(run_port_scan(host, *start_port, *end_port).await),
+            ScanParams::PingSweep {
+                base_ip,
+                start_id,
+                end_id,
+            } => Some(run_ping_sweep(base_ip, *start_id, *end_id).await),
+            ScanParams::SubnetScan { cidr } => run_subnet_scan(cidr).await,
+        };
+        if let Some(findings) = findings {
+            if let Err(e) = record_scan(self, findings) {
+                print!("Could not save scan history: {e:#}{LINE_ENDING}");
+            }
+        }
+    }
+
+    /// Whether findings are open ports (port scan) rather than hosts (sweeps).
+    fn finds_ports(&self) -> bool {
+        matches!(self, ScanParams::PortScan { .. })
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            ScanParams::PortScan {
+                host,
+                start_port,
+                end_port,
+            } => format!("Port scan {host} ports {start_port}-{end_port}"),
+            ScanParams::PingSweep {
+                base_ip,
+                start_id,
+                end_id,
+            } => format!("Ping sweep {base_ip}.{start_id}-{end_id}"),
+            ScanParams::SubnetScan { cidr } => format!("Subnet scan {cidr}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScanProfile {
+    name: String,
+    #[serde(flatten)]
+    scan: ScanParams,
+}
+
+fn profiles_path() -> Result<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join(PROFILES_FILE))
+        .context("Could not determine home directory")
+}
+
+/// Loads the saved profiles. A missing file means no profiles yet.
+fn load_profiles() -> Result<Vec<ScanProfile>> {
+    let path = profiles_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(&path).with_context(|| format!("Reading {path:?}"))?;
+    serde_json::from_str(&data).with_context(|| format!("Parsing {path:?}"))
+}
+
+fn save_profiles(profiles: &[ScanProfile]) -> Result<()> {
+    let path = profiles_path()?;
+    let data = serde_json::to_string_pretty(profiles)?;
+    fs::write(&path, data).with_context(|| format!("Writing {path:?}"))
+}
+
+/// Asks for a profile name after a scan and saves its parameters under it,
+/// replacing any profile with the same name. A blank name skips saving.
+fn offer_to_save_profile(scan: ScanParams) {
+    let name = get_user_input("Save these settings as a profile? Enter a name (blank to skip):");
+    if name.is_empty() {
+        return;
+    }
+
+    let result = load_profiles().and_then(|mut profiles| {
+        profiles.retain(|p| p.name != name);
+        profiles.push(ScanProfile {
+            name: name.clone(),
+            scan,
+        });
+        save_profiles(&profiles)
+    });
+    match result {
+        Ok(()) => print!("Saved profile '{name}'.{LINE_ENDING}"),
+        Err(e) => print!("Could not save profile: {e:#}{LINE_ENDING}"),
+    }
+}
+
+/// Lists saved profiles; runs one by number, or deletes one with `d<number>`.
+async fn scan_profiles_menu() {
+    let mut profiles = match load_profiles() {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            print!("Could not load profiles: {e:#}{LINE_ENDING}");
+            wait_for_keypress().await;
+            return;
+        }
+    };
+    if profiles.is_empty() {
+        print!(
+            "No saved profiles yet. Run a port scan, ping sweep, or subnet scan and give it a name to save it.{LINE_ENDING}"
+        );
+        print!("Press any key to return to main menu...{}", LINE_ENDING);
+        wait_for_keypress().await;
+        return;
+    }
+
+    print!("Saved scan profiles:{LINE_ENDING}");
+    for (i, profile) in profiles.iter().enumerate() {
+        print!(
+            "  {}) {:<20} {}{LINE_ENDING}",
+            i + 1,
+            profile.name,
+            profile.scan.describe()
+        );
+    }
+
+    let choice = get_user_input("Enter a number to run, d<number> to delete, or blank to return:");
+    let (delete, number) = match choice.strip_prefix(['d', 'D']) {
+        Some(rest) => (true, rest.trim()),
+        None => (false, choice.as_str()),
+    };
+    if number.is_empty() {
+        return;
+    }
+    let Some(index) = number
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .filter(|i| *i < profiles.len())
+    else {
+        print!("No profile numbered '{number}'.{LINE_ENDING}");
+        wait_for_keypress().await;
+        return;
+    };
+
+    if delete {
+        let removed = profiles.remove(index);
+        match save_profiles(&profiles) {
+            Ok(()) => print!("Deleted profile '{}'.{LINE_ENDING}", removed.name),
+            Err(e) => print!("Could not save profiles: {e:#}{LINE_ENDING}"),
+        }
+    } else {
+        let profile = &profiles[index];
+        print!("Running profile '{}'...{LINE_ENDING}", profile.name);
+        profile.scan.run().await;
+    }
+    print!("Press any key to return to main menu...{}", LINE_ENDING);
+    wait_for_keypress().await;
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Scan History & Comparison
+////////////////////////////////////////////////////////////////////////////////
+
+/// File (in the home directory) holding the results of past scans as JSON.
+const HISTORY_FILE: &str = ".net_commander_history.json";
+
+/// Only the most recent scans are kept in the history.
+const HISTORY_LIMIT: usize = 50;
+
+/// One finished port scan, ping sweep, or subnet scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScanRecord {
+    /// Local time the scan finished, `YYYY-MM-DD HH:MM:SS`
+    timestamp: String,
+    #[serde(flatten)]
+    scan: ScanParams,
+    /// Open ports (with their banner) or responding hosts (with "up"),
+    /// keyed by port number or address
+    findings: BTreeMap<String, String>,
+}
+
+/// What changed between two scans' findings.
+#[derive(Debug, Default)]
+struct ScanDiff {
+    added: Vec<(String, String)>,
+    removed: Vec<(String, String)>,
+    /// Key, old detail, new detail
+    changed: Vec<(String, String, String)>,
+    unchanged: usize,
+}
+
+fn history_path() -> Result<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join(HISTORY_FILE))
+        .context("Could not determine home directory")
+}
+
+/// Loads the scan history, oldest first. A missing file means no history yet.
+fn load_history() -> Result<Vec<ScanRecord>> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(&path).with_context(|| format!("Reading {path:?}"))?;
+    serde_json::from_str(&data).with_context(|| format!("Parsing {path:?}"))
+}
+
+/// Appends a finished scan to the history, dropping the oldest past the limit.
+fn record_scan(scan: &ScanParams, findings: BTreeMap<String, String>) -> Result<()> {
+    let mut history = load_history()?;
+    history.push(ScanRecord {
+        timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        scan: scan.clone(),
+        findings,
+    });
+    if history.len() > HISTORY_LIMIT {
+        history.drain(..history.len() - HISTORY_LIMIT);
+    }
+    let path = history_path()?;
+    let data = serde_json::to_string_pretty(&history)?;
+    fs::write(&path, data).with_context(|| format!("Writing {path:?}"))
+}
+
+/// Sorts ports and IPv4 addresses numerically, anything else after them by text.
+fn finding_order(key: &str) -> (u32, String) {
+    let number = key
+        .parse::<u16>()
+        .map(u32::from)
+        .or_else(|_| key.parse::<std::net::Ipv4Addr>().map(u32::from))
+        .unwrap_or(u32::MAX);
+    (number, key.to_string())
+}
+
+/// Compares the findings of an older scan with a newer one.
+fn diff_findings(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> ScanDiff {
+    let mut diff = ScanDiff::default();
+    for (key, detail) in new {
+        match old.get(key) {
+            None => diff.added.push((key.clone(), detail.clone())),
+            Some(previous) if previous != detail => {
+                diff.changed
+                    .push((key.clone(), previous.clone(), detail.clone()))
+            }
+            Some(_) => diff.unchanged += 1,
+        }
+    }
+    for (key, detail) in old {
+        if !new.contains_key(key) {
+            diff.removed.push((key.clone(), detail.clone()));
+        }
+    }
+    diff.added.sort_by_key(|(key, _)| finding_order(key));
+    diff.removed.sort_by_key(|(key, _)| finding_order(key));
+    diff.changed.sort_by_key(|(key, _, _)| finding_order(key));
+    diff
+}
+
+/// Lists the scan history and prints the differences between two chosen scans.
+async fn compare_scans_menu() {
+    let history = match load_history() {
+        Ok(history) => history,
+        Err(e) => {
+            print!("Could not load scan history: {e:#}{LINE_ENDING}");
+            wait_for_keypress().await;
+            return;
+        }
+    };
+    if history.len() < 2 {
+        print!(
+            "Need at least two scans to compare. Port scans, ping sweeps, and subnet scans are saved to the history automatically.{LINE_ENDING}"
+        );
+        print!("Press any key to return to main menu...{}", LINE_ENDING);
+        wait_for_keypress().await;
+        return;
+    }
+
+    print!("Scan history (oldest first):{LINE_ENDING}");
+    for (i, record) in history.iter().enumerate() {
+        let found = if record.scan.finds_ports() {
+            "open port(s)"
+        } else {
+            "host(s) up"
+        };
+        print!(
+            "  {:>2}) {}  {:<40} {} {found}{LINE_ENDING}",
+            i + 1,
+            record.timestamp,
+            record.scan.describe(),
+            record.findings.len()
+        );
+    }
+
+    let choice = get_user_input(
+        "Enter the two scans to compare, older first (e.g. 3 7), or blank to return:",
+    );
+    if choice.is_empty() {
+        return;
+    }
+    let picked: Vec<usize> = choice
+        .split([' ', ','])
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| s.parse::<usize>().ok())
+        .filter_map(|n| n.checked_sub(1))
+        .filter(|i| *i < history.len())
+        .collect();
+    let [from, to] = picked[..] else {
+        print!("Please enter two scan numbers from the list.{LINE_ENDING}");
+        wait_for_keypress().await;
+        return;
+    };
+    let (old, new) = (&history[from], &history[to]);
+    if old.scan.finds_ports() != new.scan.finds_ports() {
+        print!("A port scan can only be compared with another port scan, and a sweep with a sweep.{LINE_ENDING}");
+        wait_for_keypress().await;
+        return;
+    }
+
+    print!(
+        "{LINE_ENDING}From #{} ({}) {}{LINE_ENDING}  to #{} ({}) {}{LINE_ENDING}",
+        from + 1,
+        old.timestamp,
+        old.scan.describe(),
+        to + 1,
+        new.timestamp,
+        new.scan.describe()
+    );
+    if old.scan.describe() != new.scan.describe() {
+        print!("Note: the scans covered different targets.{LINE_ENDING}");
+    }
+    print!("{LINE_ENDING}");
+
+    let diff = diff_findings(&old.findings, &new.findings);
+    for (key, detail) in &diff.added {
+        print!("{}{LINE_ENDING}", format!("+ {key:<16} {detail}").green());
+    }
+    for (key, detail) in &diff.removed {
+        print!("{}{LINE_ENDING}", format!("- {key:<16} {detail}").red());
+    }
+    for (key, before, after) in &diff.changed {
+        print!(
+            "{}{LINE_ENDING}",
+            format!("~ {key:<16} {before} -> {after}").yellow()
+        );
+    }
+    let (appeared, disappeared) = if new.scan.finds_ports() {
+        ("opened", "closed")
+    } else {
+        ("appeared", "disappeared")
+    };
+    if diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty() {
+        print!(
+            "No differences ({} unchanged).{LINE_ENDING}",
+            diff.unchanged
+        );
+    } else {
+        print!(
+            "{LINE_ENDING}{} {appeared}, {} {disappeared}, {} changed, {} unchanged.{LINE_ENDING}",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.changed.len(),
+            diff.unchanged
+        );
+    }
+    print!("Press any key to return to main menu...{}", LINE_ENDING);
+    wait_for_keypress().await;
+}
+
+fn increment_base_ip(base_ip: &str, offset: u8) -> String {
+    let mut parts: Vec<u8> = base_ip.split('.').filter_map(|s| s.parse().ok()).collect();
+    if parts.len() == 4 {
+        parts[3] = offset;
+        return format!("{}.{}.{}.{}", parts[0], parts[1], parts[2], parts[3]);
+    }
+    base_ip.to_string()
+}
+
+fn detect_firewall_and_vpn() {
+    print!("Detecting Firewall & VPN ...{}", LINE_ENDING);
+
+    if cfg!(target_os = "windows") {
+        let firewall_status = Command::new("netsh")
+            .args(["advfirewall", "show", "allprofiles"])
+            .output();
+        match firewall_status {
+            Ok(o) => {
+                let out = String::from_utf8_lossy(&o.stdout).to_lowercase();
+                if out.contains("state on") {
+                    print!("Windows firewall appears to be ON.{}", LINE_ENDING);
+                } else if out.contains("state off") {
+                    print!("Windows firewall appears to be OFF.{}", LINE_ENDING);
+                } else {
+                    print!("Could not determine Windows firewall state.{}", LINE_ENDING);
+                }
+            }
+            Err(e) => {
+                print!("Error checking Windows firewall: {}{}", e, LINE_ENDING);
+            }
+        }
+
+        let vpn_check = Command::new("ipconfig").output();
+        match vpn_check {
+            Ok(o) => {
+                let out = String::from_utf8_lossy(&o.stdout).to_lowercase();
+                if out.contains("tun") || out.contains("ppp") || out.contains("vpn") {
+                    print!("A VPN interface might be active.{}", LINE_ENDING);
+                } else {
+                    print!("No obvious VPN interface found.{}", LINE_ENDING);
+                }
+            }
+            Err(e) => {
+                print!("Error checking VPN: {}{}", e, LINE_ENDING);
+            }
+        }
+    } else {
+        let firewall_status = Command::new("systemctl")
+            .args(["is-active", "firewalld"])
+            .output();
+        if let Ok(o) = firewall_status {
+            let out = String::from_utf8_lossy(&o.stdout).to_lowercase();
+            if out.contains("active") {
+                print!("firewalld service is ACTIVE.{}", LINE_ENDING);
+            } else {
+                print!(
+                    "firewalld service is not active or not found.{}",
+                    LINE_ENDING
+                );
+            }
+        }
+
+        let iptables_check = Command::new("iptables").arg("-L").output();
+        if let Ok(o) = iptables_check {
+            let out = String::from_utf8_lossy(&o.stdout);
+            print!(
+                "`iptables -L` returned:{}{}{}",
+                LINE_ENDING, out, LINE_ENDING
+            );
+        }
+
+        let ifconfig_check = Command::new("ifconfig").output();
+        if let Ok(o) = ifconfig_check {
+            let out = String::from_utf8_lossy(&o.stdout).to_lowercase();
+            if out.contains("tun0") || out.contains("ppp0") || out.contains("wg0") {
+                print!(
+                    "A VPN or tunneling interface might be active.{}",
+                    LINE_ENDING
+                );
+            } else {
+                print!(
+                    "No obvious VPN interface found (tun0/ppp0/wg0).{}",
+                    LINE_ENDING
+                );
+            }
+        }
+    }
+}
+
+/// Number of samples kept (and graphed) by the latency monitor.
+const LATENCY_HISTORY: usize = 120;
+/// Packet loss is computed over this many most recent pings.
+const LOSS_WINDOW: usize = 20;
+/// Loss above this percentage is drawn in red.
+const LOSS_WARN_PERCENT: f64 = 5.0;
+
+/// One latency-monitor sample: RTT in ms (`None` = lost) and the sliding-window
+/// loss percentage at the time it arrived.
+struct LatencySample {
+    seq: u64,
+    rtt: Option<f64>,
+    loss_percent: f64,
+}
+
+async fn latency_monitoring_menu(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<()> {
+    let host = get_user_input("Enter host/IP for continuous ping:");
+    if host.is_empty() {
+        print!("No host specified.{}", LINE_ENDING);
+        wait_for_keypress().await;
+        return Ok(());
+    }
+
+    // Ping once per second in the background so the UI stays responsive
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let pinger = {
+        let host = host.clone();
+        tokio::spawn(async move {
+            loop {
+                if tx.send(ping_rtt(&host).await).is_err() {
+                    return;
+                }
+                tokio::time::sleep(MULTI_PING_INTERVAL).await;
+            }
+        })
+    };
+
+    let mut samples: Vec<LatencySample> = Vec::new();
+    let mut recent: VecDeque<bool> = VecDeque::new();
+    let mut seq = 0;
+
+    clear_screen(terminal)?;
+    loop {
+        while let Ok(rtt) = rx.try_recv() {
+            recent.push_back(rtt.is_some());
+            if recent.len() > LOSS_WINDOW {
+                recent.pop_front();
+            }
+            let lost = recent.iter().filter(|ok| !**ok).count();
+            samples.push(LatencySample {
+                seq,
+                rtt,
+                loss_percent: lost as f64 * 100.0 / recent.len() as f64,
+            });
+            seq += 1;
+            if samples.len() > LATENCY_HISTORY {
+                samples.remove(0);
+            }
+        }
+        terminal.draw(|frame| draw_latency_monitor(frame, &host, &samples))?;
+
+        if crossterm::event::poll(Duration::from_millis(250))? {
+            if let CEvent::Key(_) = event::read()? {
+                break;
+            }
+        }
+    }
+
+    pinger.abort();
+    clear_screen(terminal)?;
+    Ok(())
+}
+
+fn draw_latency_monitor(frame: &mut Frame, host: &str, samples: &[LatencySample]) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Percentage(60),
+                Constraint::Min(6),
+                Constraint::Length(3),
+            ]
+            .as_ref(),
+        )
+        .split(frame.area());
+
+    // Summary line
+    let rtts: Vec<f64> = samples.iter().filter_map(|s| s.rtt).collect();
+    let last = samples.last();
+    let loss = last.map_or(0.0, |s| s.loss_percent);
+    let loss_color = if loss > LOSS_WARN_PERCENT {
+        Color::Red
+    } else {
+        Color::Green
+    };
+    let summary = Line::from(vec![
+        Span::raw(match last.map(|s| s.rtt) {
+            None => "Waiting for first reply...".to_string(),
+            Some(Some(ms)) => format!("Last RTT: {ms:.1} ms"),
+            Some(None) => "Last RTT: timeout".to_string(),
+        }),
+        Span::raw(if rtts.is_empty() {
+            String::new()
+        } else {
+            let avg = rtts.iter().sum::<f64>() / rtts.len() as f64;
+            let max = rtts.iter().cloned().fold(0.0, f64::max);
+            format!(" | avg {avg:.1} ms | max {max:.1} ms")
+        }),
+        Span::styled(
+            format!(" | loss {loss:.0}% (last {LOSS_WINDOW})"),
+            Style::default().fg(loss_color).add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    frame.render_widget(
+        Paragraph::new(summary).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Latency Monitor: {host} ")),
+        ),
+        chunks[0],
+    );
+
+    let x_start = samples.first().map_or(0, |s| s.seq) as f64;
+    let x_bounds = [x_start, x_start + LATENCY_HISTORY as f64];
+
+    // RTT chart (lost pings leave gaps)
+    let rtt_points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter_map(|s| s.rtt.map(|ms| (s.seq as f64, ms)))
+        .collect();
+    let rtt_max = rtts.iter().cloned().fold(1.0, f64::max) * 1.2;
+    let rtt_chart = Chart::new(vec![Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::Cyan))
+        .data(&rtt_points)])
+    .block(Block::default().borders(Borders::ALL).title(" RTT (ms) "))
+    .x_axis(Axis::default().bounds(x_bounds))
+    .y_axis(Axis::default().bounds([0.0, rtt_max]).labels([
+        "0".to_string(),
+        format!("{:.0}", rtt_max / 2.0),
+        format!("{rtt_max:.0}"),
+    ]));
+    frame.render_widget(rtt_chart, chunks[1]);
+
+    // Packet loss chart over the sliding window
+    let loss_points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|s| (s.seq as f64, s.loss_percent))
+        .collect();
+    let loss_chart = Chart::new(vec![Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(loss_color))
+        .data(&loss_points)])
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        " Packet loss % (sliding window of {LOSS_WINDOW}, red above {LOSS_WARN_PERCENT:.0}%) "
+    )))
+    .x_axis(Axis::default().bounds(x_bounds))
+    .y_axis(
+        Axis::default()
+            .bounds([0.0, 100.0])
+            .labels(["0", "50", "100"]),
+    );
+    frame.render_widget(loss_chart, chunks[2]);
+
+    let footer = Paragraph::new("Pinging every second | press any key to stop")
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, chunks[3]);
+}
+
+fn get_latency_ping_args(host: &str) -> Vec<String> {
+    if cfg!(target_os = "windows") {
+        vec!["-n".to_string(), "1".to_string(), host.to_string()]
+    } else {
+        vec!["-c".to_string(), "1".to_string(), host.to_string()]
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Download Speed Test (live throughput graph)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Downloaded when no URL is given: Cloudflare's speed test endpoint, which
+/// serves this many bytes of throwaway data.
+const SPEED_TEST_URL: &str = "https://speed.cloudflare.com/__down?bytes=100000000";
+/// Throughput is sampled this often.
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+/// The test stops after this long even if the download hasn't finished.
+const SPEED_TEST_LIMIT: Duration = Duration::from_secs(15);
+/// The rolling average covers this many samples (2 seconds).
+const SPEED_ROLLING_SAMPLES: usize = 10;
+
+/// Throughput over one sample interval, in Mbit/s, `secs` after the start.
+struct SpeedSample {
+    secs: f64,
+    mbps: f64,
+}
+
+/// Where a speed test stands, for the footer.
+enum SpeedTestState {
+    Running,
+    Finished,
+    TimeLimit,
+    Stopped,
+    Failed(String),
+}
+
+/// Bits per second over `secs`, in Mbit/s.
+fn megabits_per_sec(bytes: u64, secs: f64) -> f64 {
+    if secs > 0.0 {
+        bytes as f64 * 8.0 / secs / 1_000_000.0
+    } else {
+        0.0
+    }
+}
+
+/// Mean of the last `SPEED_ROLLING_SAMPLES` samples at each point.
+fn rolling_average(samples: &[SpeedSample]) -> Vec<(f64, f64)> {
+    (0..samples.len())
+        .map(|i| {
+            let window = &samples[(i + 1).saturating_sub(SPEED_ROLLING_SAMPLES)..=i];
+            let mean = window.iter().map(|s| s.mbps).sum::<f64>() / window.len() as f64;
+            (samples[i].secs, mean)
+        })
+        .collect()
+}
+
+/// Downloads a URL for up to `SPEED_TEST_LIMIT`, graphing the throughput
+/// every `SPEED_SAMPLE_INTERVAL` with its rolling average, overall average,
+/// and peak. The final graph stays up until a key is pressed.
+async fn speed_test_menu(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> Result<()> {
+    let input = get_user_input(&format!("URL to download (blank for {SPEED_TEST_URL}):"));
+    let url = if input.is_empty() {
+        SPEED_TEST_URL.to_string()
+    } else {
+        input
+    };
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    // The download runs in the background and only counts bytes; the loop
+    // below turns the count into samples
+    let received = Arc::new(AtomicU64::new(0));
+    let mut download = {
+        let received = received.clone();
+        let url = url.clone();
+        tokio::spawn(async move {
+            let mut response = client.get(&url).send().await?.error_for_status()?;
+            while let Some(chunk) = response.chunk().await? {
+                received.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+            }
+            Ok::<(), reqwest::Error>(())
+        })
+    };
+
+    let start = Instant::now();
+    let mut samples: Vec<SpeedSample> = Vec::new();
+    let mut last_sample = (start, 0);
+    let mut state = SpeedTestState::Running;
+
+    clear_screen(terminal)?;
+    loop {
+        let now = Instant::now();
+        let total = received.load(Ordering::Relaxed);
+        let finished = download.is_finished();
+        if matches!(state, SpeedTestState::Running)
+            && (now.duration_since(last_sample.0) >= SPEED_SAMPLE_INTERVAL || finished)
+        {
+            let secs = now.duration_since(last_sample.0).as_secs_f64();
+            samples.push(SpeedSample {
+                secs: now.duration_since(start).as_secs_f64(),
+                mbps: megabits_per_sec(total - last_sample.1, secs),
+            });
+            last_sample = (now, total);
+
+            if finished {
+                state = match (&mut download).await {
+                    Ok(Ok(())) => SpeedTestState::Finished,
+                    Ok(Err(e)) => SpeedTestState::Failed(e.to_string()),
+                    Err(e) => SpeedTestState::Failed(e.to_string()),
+                };
+            } else if now.duration_since(start) >= SPEED_TEST_LIMIT {
+                download.abort();
+                state = SpeedTestState::TimeLimit;
+            }
+        }
+        let elapsed = last_sample.0.duration_since(start).as_secs_f64();
+        terminal
+            .draw(|frame| draw_speed_test(frame, &url, &samples, last_sample.1, elapsed, &state))?;
+
+        if crossterm::event::poll(Duration::from_millis(50))? {
+            if let CEvent::Key(_) = event::read()? {
+                if !matches!(state, SpeedTestState::Running) {
+                    break;
+                }
+                download.abort();
+                state = SpeedTestState::Stopped;
+            }
+        }
+    }
+
+    clear_screen(terminal)?;
+    Ok(())
+}
+
+fn draw_speed_test(
+    frame: &mut Frame,
+    url: &str,
+    samples: &[SpeedSample],
+    total_bytes: u64,
+    elapsed: f64,
+    state: &SpeedTestState,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(8),
+                Constraint::Length(3),
+            ]
+            .as_ref(),
+        )
+        .split(frame.area());
+
+    // Summary line
+    let rolling = rolling_average(samples);
+    let peak = samples.iter().map(|s| s.mbps).fold(0.0, f64::max);
+    let summary = Line::from(vec![
+        Span::styled(
+            format!("Now {:.1} Mbit/s", samples.last().map_or(0.0, |s| s.mbps)),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                " | rolling {:.1}",
+                rolling.last().map_or(0.0, |&(_, mbps)| mbps)
+            ),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw(format!(
+            " | average {:.1} | peak {peak:.1} | {:.1} MB in {elapsed:.1} s",
+            megabits_per_sec(total_bytes, elapsed),
+            total_bytes as f64 / 1_000_000.0
+        )),
+    ]);
+    frame.render_widget(
+        Paragraph::new(summary).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" Speed Test: {url} ")),
+        ),
+        chunks[0],
+    );
+
+    // Throughput chart with its rolling average
+    let points: Vec<(f64, f64)> = samples.iter().map(|s| (s.secs, s.mbps)).collect();
+    let x_max = elapsed.max(SPEED_TEST_LIMIT.as_secs_f64());
+    let y_max = (peak * 1.2).max(1.0);
+    let chart = Chart::new(vec![
+        Dataset::default()
+            .name("throughput")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&points),
+        Dataset::default()
+            .name(format!(
+                "rolling average ({:.0} s)",
+                (SPEED_SAMPLE_INTERVAL * SPEED_ROLLING_SAMPLES as u32).as_secs_f64()
+            ))
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&rolling),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Throughput (Mbit/s) over time (s) "),
+    )
+    .x_axis(Axis::default().bounds([0.0, x_max]).labels([
+        "0".to_string(),
+        format!("{:.0}", x_max / 2.0),
+        format!("{x_max:.0}"),
+    ]))
+    .y_axis(Axis::default().bounds([0.0, y_max]).labels([
+        "0".to_string(),
+        format!("{:.0}", y_max / 2.0),
+        format!("{y_max:.0}"),
+    ]));
+    frame.render_widget(chart, chunks[1]);
+
+    let (footer, color) = match state {
+        SpeedTestState::Running => (
+            format!(
+                "Downloading (up to {} s) | press any key to stop",
+                SPEED_TEST_LIMIT.as_secs()
+            ),
+            Color::White,
+        ),
+        SpeedTestState::Finished => (
+            "Download complete | press any key to return".to_string(),
+            Color::Green,
+        ),
+        SpeedTestState::TimeLimit => (
+            format!(
+                "Stopped after {} s | press any key to return",
+                SPEED_TEST_LIMIT.as_secs()
+            ),
+            Color::Green,
+        ),
+        SpeedTestState::Stopped => (
+            "Stopped | press any key to return".to_string(),
+            Color::Yellow,
+        ),
+        SpeedTestState::Failed(e) => (
+            format!("Download failed: {e} | press any key to return"),
+            Color::Red,
+        ),
+    };
+    let footer = Paragraph::new(footer)
+        .style(Style::default().fg(color))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, chunks[2]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Multi-Host Ping (live table)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Maximum number of ping processes running at once.
+const MULTI_PING_CONCURRENCY: usize = 32;
+/// Pause between pings to the same host.
+const MULTI_PING_INTERVAL: Duration = Duration::from_secs(1);
+/// A ping that hasn't answered by now counts as lost.
+const MULTI_PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Rolling ping statistics for one host in the multi-ping table.
+struct HostPingStatus {
+    host: String,
+    sent: u32,
+    received: u32,
+    /// Result of the most recent ping: RTT in ms, `None` if it was lost
+    last: Option<Option<f64>>,
+}
+
+async fn multi_ping_menu(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> Result<()> {
+    let input = get_user_input(
+        "Enter hosts separated by spaces/commas, or @path to read one host per line from a file:",
+    );
+    let hosts = match parse_host_list(&input) {
+        Ok(hosts) if !hosts.is_empty() => hosts,
+        Ok(_) => {
+            print!("No hosts specified.{}", LINE_ENDING);
+            wait_for_keypress().await;
+            return Ok(());
+        }
+        Err(e) => {
+            print!("{e:#}{LINE_ENDING}");
+            print!("Press any key to return to main menu...{}", LINE_ENDING);
+            wait_for_keypress().await;
+            return Ok(());
+        }
+    };
+
+    let mut statuses: Vec<HostPingStatus> = hosts
+        .iter()
+        .map(|h| HostPingStatus {
+            host: h.clone(),
+            sent: 0,
+            received: 0,
+            last: None,
+        })
+        .collect();
+
+    // One looping task per host; the semaphore bounds concurrent ping processes
+    let semaphore = Arc::new(Semaphore::new(MULTI_PING_CONCURRENCY));
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let tasks: Vec<_> = hosts
+        .into_iter()
+        .enumerate()
+        .map(|(idx, host)| {
+            let semaphore = semaphore.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                loop {
+                    let rtt = {
+                        let Ok(_permit) = semaphore.acquire().await else {
+                            return;
+                        };
+                        ping_rtt(&host).await
+                    };
+                    if tx.send((idx, rtt)).is_err() {
+                        return;
+                    }
+                    tokio::time::sleep(MULTI_PING_INTERVAL).await;
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    clear_screen(terminal)?;
+    loop {
+        while let Ok((idx, rtt)) = rx.try_recv() {
+            let status = &mut statuses[idx];
+            status.sent += 1;
+            if rtt.is_some() {
+                status.received += 1;
+            }
+            status.last = Some(rtt);
+        }
+        terminal.draw(|frame| draw_multi_ping(frame, &statuses))?;
+
+        if crossterm::event::poll(Duration::from_millis(250))? {
+            if let CEvent::Key(key_event) = event::read()? {
+                if matches!(
+                    key_event.code,
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter
+                ) {
+                    break;
+                }
+            }
+        }
+    }
+
+    for task in tasks {
+        task.abort();
+    }
+    clear_screen(terminal)?;
+    Ok(())
+}
+
+/// Splits typed hosts on commas/whitespace, or reads one host per line from
+/// `@path` (blank lines and `#` comments are ignored). Duplicates are dropped.
+fn parse_host_list(input: &str) -> Result<Vec<String>> {
+    let text = match input.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path.trim())
+            .with_context(|| format!("Failed to read host list {}", path.trim()))?,
+        None => input.to_string(),
+    };
+    let mut hosts: Vec<String> = Vec::new();
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or("");
+        for host in line.split([',', ' ', '\t']).filter(|h| !h.is_empty()) {
+            if !hosts.iter().any(|h| h == host) {
+                hosts.push(host.to_string());
+            }
+        }
+    }
+    Ok(hosts)
+}
+
+/// Sends a single ping and returns the round-trip time in milliseconds,
+/// or `None` if the host didn't answer within `MULTI_PING_TIMEOUT`.
+async fn ping_rtt(host: &str) -> Option<f64> {
+    let output = tokio::process::Command::new("ping")
+        .args(get_latency_ping_args(host))
+        .kill_on_drop(true)
+        .output();
+    let output = timeout(MULTI_PING_TIMEOUT, output).await.ok()?.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_ping_rtt(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Extracts the RTT from ping output (`time=12.3 ms`, `time=12ms`, or `time<1ms`).
+fn parse_ping_rtt(output: &str) -> Option<f64> {
+    let start = output.find("time=").or_else(|| output.find("time<"))? + "time=".len();
+    let number: String = output[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    number.parse().ok()
+}
+
+fn draw_multi_ping(frame: &mut Frame, statuses: &[HostPingStatus]) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+        .split(frame.area());
+
+    let header = Row::new(vec!["Host", "Status", "Last RTT", "Replies", "Loss"]).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows: Vec<Row> = statuses
+        .iter()
+        .map(|s| {
+            let (status, rtt, color) = match s.last {
+                None => ("PENDING", "-".to_string(), Color::DarkGray),
+                Some(Some(ms)) => ("UP", format!("{ms:.1} ms"), Color::Green),
+                Some(None) => ("DOWN", "timeout".to_string(), Color::Red),
+            };
+            let loss = if s.sent == 0 {
+                "-".to_string()
+            } else {
+                format!(
+                    "{:.0}%",
+                    (s.sent - s.received) as f64 * 100.0 / s.sent as f64
+                )
+            };
+            Row::new(vec![
+                s.host.clone(),
+                status.to_string(),
+                rtt,
+                format!("{}/{}", s.received, s.sent),
+                loss,
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+
+    let up = statuses
+        .iter()
+        .filter(|s| matches!(s.last, Some(Some(_))))
+        .count();
+    let title = format!(" Multi-Host Ping ({up}/{} up) ", statuses.len());
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(40),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Cyan)),
+    )
+    .column_spacing(1);
+    frame.render_widget(table, chunks[0]);
+
+    let footer = Paragraph::new("Pinging every second | Esc/q/Enter = stop")
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, chunks[1]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Port Monitor (alerts on open/closed transitions)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Re-scan interval used when the prompt is left blank.
+const PORT_MONITOR_DEFAULT_INTERVAL: u64 = 5;
+/// Number of transition events kept in the on-screen log.
+const PORT_MONITOR_EVENTS: usize = 100;
+
+/// Current state of one monitored `host:port` pair.
+struct PortWatch {
+    host: String,
+    port: u16,
+    /// `None` until the first (baseline) scan has finished
+    open: Option<bool>,
+    /// When the port entered its current state (baseline time for the first scan)
+    since: Option<DateTime<Local>>,
+    changes: u32,
+}
+
+async fn port_monitor_menu(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<()> {
+    let input = get_user_input(
+        "Enter host:port pairs separated by spaces/commas, or @path to read one per line:",
+    );
+    let targets = match parse_host_list(&input).and_then(|list| parse_port_targets(&list)) {
+        Ok(targets) if !targets.is_empty() => targets,
+        Ok(_) => {
+            print!("No targets specified.{}", LINE_ENDING);
+            wait_for_keypress().await;
+            return Ok(());
+        }
+        Err(e) => {
+            print!("{e:#}{LINE_ENDING}");
+            print!("Press any key to return to main menu...{}", LINE_ENDING);
+            wait_for_keypress().await;
+            return Ok(());
+        }
+    };
+    let interval = get_user_input(&format!(
+        "Re-scan interval in seconds (default {PORT_MONITOR_DEFAULT_INTERVAL}):"
+    ))
+    .parse::<u64>()
+    .ok()
+    .filter(|secs| *secs > 0)
+    .unwrap_or(PORT_MONITOR_DEFAULT_INTERVAL);
+
+    let mut watches: Vec<PortWatch> = targets
+        .iter()
+        .map(|(host, port)| PortWatch {
+            host: host.clone(),
+            port: *port,
+            open: None,
+            since: None,
+            changes: 0,
+        })
+        .collect();
+
+    // Scan every target concurrently each round and send the whole round back
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let scanner = tokio::spawn(async move {
+        loop {
+            let round: Vec<_> = targets
+                .iter()
+                .map(|(host, port)| {
+                    let host = host.clone();
+                    let port = *port;
+                    tokio::spawn(async move { scan_port(&host, port).await.is_some() })
+                })
+                .collect();
+            let mut states = Vec::with_capacity(round.len());
+            for handle in round {
+                states.push(handle.await.unwrap_or(false));
+            }
+            if tx.send(states).is_err() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+
+    let mut events: Vec<Line<'static>> = Vec::new();
+    let mut status = "Scanning for the baseline...".to_string();
+    let mut rounds = 0u64;
+
+    clear_screen(terminal)?;
+    loop {
+        while let Ok(states) = rx.try_recv() {
+            rounds += 1;
+            let now = Local::now();
+            let mut alerted = false;
+            for (watch, open) in watches.iter_mut().zip(states) {
+                match watch.open {
+                    None => {
+                        watch.since = Some(now);
+                        events.push(port_event_line(now, watch, open, false));
+                    }
+                    Some(previous) if previous != open => {
+                        watch.since = Some(now);
+                        watch.changes += 1;
+                        events.push(port_event_line(now, watch, open, true));
+                        status = format!(
+                            "{} {}:{} is now {}",
+                            now.format("%H:%M:%S"),
+                            watch.host,
+                            watch.port,
+                            if open { "OPEN" } else { "CLOSED" }
+                        );
+                        alerted = true;
+                    }
+                    Some(_) => {}
+                }
+                watch.open = Some(open);
+            }
+            if rounds == 1 {
+                status = format!("Baseline recorded at {}", now.format("%H:%M:%S"));
+            }
+            if events.len() > PORT_MONITOR_EVENTS {
+                events.drain(..events.len() - PORT_MONITOR_EVENTS);
+            }
+            if alerted {
+                // Terminal bell
+                print!("\x07");
+                io::stdout().flush()?;
+            }
+        }
+        terminal
+            .draw(|frame| draw_port_monitor(frame, &watches, &events, &status, interval, rounds))?;
+
+        if crossterm::event::poll(Duration::from_millis(250))? {
+            if let CEvent::Key(key_event) = event::read()? {
+                if matches!(
+                    key_event.code,
+                    KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter
+                ) {
+                    break;
+                }
+            }
+        }
+    }
+
+    scanner.abort();
+    clear_screen(terminal)?;
+    Ok(())
+}
+
+/// Splits each `host:port` entry on its last colon (so `[::1]:22` works).
+fn parse_port_targets(entries: &[String]) -> Result<Vec<(String, u16)>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let (host, port) = entry
+                .rsplit_once(':')
+                .filter(|(host, _)| !host.is_empty())
+                .with_context(|| format!("'{entry}' is not in host:port form"))?;
+            let port = port
+                .parse::<u16>()
+                .ok()
+                .filter(|p| *p > 0)
+                .with_context(|| format!("'{port}' is not a valid port in '{entry}'"))?;
+            Ok((host.to_string(), port))
+        })
+        .collect()
+}
+
+/// One timestamped line for the event log; transitions are highlighted.
+fn port_event_line(
+    at: DateTime<Local>,
+    watch: &PortWatch,
+    open: bool,
+    transition: bool,
+) -> Line<'static> {
+    let state = if open { "OPEN" } else { "CLOSED" };
+    let text = if transition {
+        let previous = if open { "CLOSED" } else { "OPEN" };
+        format!(
+            "{} {}:{} {} -> {}",
+            at.format("%Y-%m-%d %H:%M:%S"),
+            watch.host,
+            watch.port,
+            previous,
+            state
+        )
+    } else {
+        format!(
+            "{} {}:{} baseline {}",
+            at.format("%Y-%m-%d %H:%M:%S"),
+            watch.host,
+            watch.port,
+            state
+        )
+    };
+    let style = match (transition, open) {
+        (false, _) => Style::default().fg(Color::DarkGray),
+        (true, true) => Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+        (true, false) => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+    };
+    Line::from(Span::styled(text, style))
+}
+
+fn draw_port_monitor(
+    frame: &mut Frame,
+    watches: &[PortWatch],
+    events: &[Line<'static>],
+    status: &str,
+    interval: u64,
+    rounds: u64,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(5),
+                Constraint::Length(10),
+                Constraint::Length(3),
+            ]
+            .as_ref(),
+        )
+        .split(frame.area());
+
+    frame.render_widget(
+        Paragraph::new(status.to_string())
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .block(Block::default().borders(Borders::ALL).title(" Status ")),
+        chunks[0],
+    );
+
+    let header = Row::new(vec!["Target", "State", "Since", "Changes"]).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows: Vec<Row> = watches
+        .iter()
+        .map(|w| {
+            let (state, color) = match w.open {
+                None => ("PENDING", Color::DarkGray),
+                Some(true) => ("OPEN", Color::Green),
+                Some(false) => ("CLOSED", Color::Red),
+            };
+            Row::new(vec![
+                format!("{}:{}", w.host, w.port),
+                state.to_string(),
+                w.since
+                    .map_or("-".to_string(), |t| t.format("%H:%M:%S").to_string()),
+                w.changes.to_string(),
+            ])
+            .style(Style::default().fg(color))
+        })
+        .collect();
+    let open = watches.iter().filter(|w| w.open == Some(true)).count();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(50),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(8),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Port Monitor ({open}/{} open) ", watches.len()))
+            .border_style(Style::default().fg(Color::Cyan)),
+    )
+    .column_spacing(1);
+    frame.render_widget(table, chunks[1]);
+
+    // Newest events at the bottom, like a log
+    let visible = chunks[2].height.saturating_sub(2) as usize;
+    let start = events.len().saturating_sub(visible);
+    let items: Vec<ListItem> = events[start..]
+        .iter()
+        .map(|line| ListItem::new(line.clone()))
+        .collect();
+    frame.render_widget(
+        List::new(items).block(Block::default().borders(Borders::ALL).title(" Events ")),
+        chunks[2],
+    );
+
+    let footer = Paragraph::new(format!(
+        "Re-scanning every {interval}s (round {rounds}) | Esc/q/Enter = stop"
+    ))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, chunks[3]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// mDNS / Bonjour Service Discovery
+////////////////////////////////////////////////////////////////////////////////
+
+/// Service types browsed when the user doesn't name one explicitly.
+const COMMON_MDNS_SERVICE_TYPES: &[&str] = &[
+    "_http._tcp",
+    "_https._tcp",
+    "_ssh._tcp",
+    "_sftp-ssh._tcp",
+    "_smb._tcp",
+    "_afpovertcp._tcp",
+    "_ipp._tcp",
+    "_printer._tcp",
+    "_airplay._tcp",
+    "_raop._tcp",
+    "_googlecast._tcp",
+    "_spotify-connect._tcp",
+    "_homekit._tcp",
+    "_workstation._tcp",
+    "_device-info._tcp",
+];
+
+/// How long to listen for mDNS responses before showing results.
+const MDNS_BROWSE_SECS: u64 = 5;
+
+/// A resolved mDNS service instance.
+#[derive(Debug, Clone)]
+struct MdnsService {
+    service_type: String,
+    instance: String,
+    hostname: String,
+    addresses: Vec<String>,
+    port: u16,
+}
+
+async fn mdns_discovery_menu(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<()> {
+    let input = get_user_input(
+        "Enter service types to browse, comma-separated (e.g. _http._tcp), or blank for common types:",
+    );
+    let service_types: Vec<String> = if input.is_empty() {
+        COMMON_MDNS_SERVICE_TYPES
+            .iter()
+            .map(|t| mdns_type_domain(t))
+            .collect()
+    } else {
+        input
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(mdns_type_domain)
+            .collect()
+    };
+
+    print!(
+        "Browsing {} mDNS service type(s) for {MDNS_BROWSE_SECS} seconds...{LINE_ENDING}",
+        service_types.len()
+    );
+    let _ = io::stdout().flush();
+
+    let services = match browse_mdns(&service_types).await {
+        Ok(s) => s,
+        Err(e) => {
+            print!("mDNS discovery failed: {e}{LINE_ENDING}");
+            print!("Press any key to return to main menu...{}", LINE_ENDING);
+            wait_for_keypress().await;
+            return Ok(());
+        }
+    };
+
+    // Distinct types seen, used to cycle the filter with Tab
+    let mut found_types: Vec<String> = services.iter().map(|s| s.service_type.clone()).collect();
+    found_types.sort();
+    found_types.dedup();
+    let mut filter_idx: Option<usize> = None;
+
+    clear_screen(terminal)?;
+    loop {
+        let filter = filter_idx.map(|i| found_types[i].as_str());
+        terminal.draw(|frame| draw_mdns_results(frame, &services, filter))?;
+
+        if let Ok(CEvent::Key(key_event)) = event::read() {
+            match key_event.code {
+                KeyCode::Tab if !found_types.is_empty() => {
+                    filter_idx = match filter_idx {
+                        None => Some(0),
+                        Some(i) if i + 1 < found_types.len() => Some(i + 1),
+                        Some(_) => None,
+                    };
+                }
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => break,
+                _ => {}
+            }
+        }
+    }
+    clear_screen(terminal)?;
+    Ok(())
+}
+
+/// Normalizes `_http._tcp` (or `_http._tcp.local`) to `_http._tcp.local.`.
+fn mdns_type_domain(service_type: &str) -> String {
+    let trimmed = service_type.trim_end_matches('.');
+    if trimmed.ends_with(".local") {
+        format!("{trimmed}.")
+    } else {
+        format!("{trimmed}.local.")
+    }
+}
+
+/// Browses the given service types for `MDNS_BROWSE_SECS`, returning resolved instances.
+async fn browse_mdns(service_types: &[String]) -> Result<Vec<MdnsService>> {
+    let daemon = ServiceDaemon::new().context("Failed to start mDNS daemon")?;
+    let mut receivers = Vec::new();
+    for ty in service_types {
+        receivers.push(
+            daemon
+                .browse(ty)
+                .with_context(|| format!("Failed to browse {ty}"))?,
+        );
+    }
+
+    // Keyed by full service name so re-announcements don't duplicate rows
+    let mut found: BTreeMap<String, MdnsService> = BTreeMap::new();
+    let deadline = Instant::now() + Duration::from_secs(MDNS_BROWSE_SECS);
+    while Instant::now() < deadline {
+        for rx in &receivers {
+            while let Ok(event) = rx.try_recv() {
+                if let ServiceEvent::ServiceResolved(info) = event {
+                    let ty = info.get_type().to_string();
+                    let instance = info
+                        .get_fullname()
+                        .strip_suffix(&format!(".{ty}"))
+                        .unwrap_or(info.get_fullname())
+                        .to_string();
+                    let mut addresses: Vec<String> =
+                        info.get_addresses().iter().map(|a| a.to_string()).collect();
+                    addresses.sort();
+                    found.insert(
+                        info.get_fullname().to_string(),
+                        MdnsService {
+                            service_type: ty.trim_end_matches(".local.").to_string(),
+                            instance,
+                            hostname: info.get_hostname().trim_end_matches('.').to_string(),
+                            addresses,
+                            port: info.get_port(),
+                        },
+                    );
+                }
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let _ = daemon.shutdown();
+    Ok(found.into_values().collect())
+}
+
+fn draw_mdns_results(frame: &mut Frame, services: &[MdnsService], filter: Option<&str>) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+        .split(frame.area());
+
+    let visible: Vec<&MdnsService> = services
+        .iter()
+        .filter(|s| filter.is_none_or(|f| s.service_type == f))
+        .collect();
+
+    let header = Row::new(vec!["Service", "Instance", "Hostname", "Addresses", "Port"]).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    let rows: Vec<Row> = visible
+        .iter()
+        .map(|s| {
+            Row::new(vec![
+                s.service_type.clone(),
+                s.instance.clone(),
+                s.hostname.clone(),
+                s.addresses.join(", "),
+                s.port.to_string(),
+            ])
+        })
+        .collect();
+
+    let title = format!(
+        " mDNS Services ({} shown, filter: {}) ",
+        visible.len(),
+        filter.unwrap_or("all")
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(20),
+            Constraint::Percentage(30),
+            Constraint::Percentage(25),
+            Constraint::Percentage(30),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Cyan)),
+    )
+    .column_spacing(1);
+    frame.render_widget(table, chunks[0]);
+
+    let help = if services.is_empty() {
+        "No mDNS services responded. Esc/q/Enter = back"
+    } else {
+        "Tab = cycle service-type filter | Esc/q/Enter = back"
+    };
+    let footer = Paragraph::new(help)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(footer, chunks[1]);
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// TLS Certificate Inspection
+////////////////////////////////////////////////////////////////////////////////
+
+/// Certificates expiring within this many days are flagged with a warning.
+const TLS_EXPIRY_WARN_DAYS: i64 = 30;
+
+/// How long to wait for the TCP connect and TLS handshake.
+const TLS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Accepts every server certificate but records what the normal WebPKI
+/// verification would have said, so self-signed or expired certificates can be
+/// reported instead of aborting the handshake.
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    outcome: Mutex<Option<Result<(), String>>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        if let Ok(mut outcome) = self.outcome.lock() {
+            *outcome = Some(result);
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Result of a TLS handshake: the peer's chain and the verification verdict.
+struct TlsReport {
+    protocol: String,
+    cipher_suite: String,
+    chain: Vec<Vec<u8>>,
+    verification: Result<(), String>,
+}
+
+async fn tls_inspect_menu() {
+    let host = get_user_input("Enter host to inspect (e.g. example.com):");
+    if host.is_empty() {
+        print!("No host specified.{}", LINE_ENDING);
+        wait_for_keypress().await;
+        return;
+    }
+    let port_str = get_user_input("Enter TLS port (default 443):");
+    let port: u16 = port_str.parse().unwrap_or(443);
+
+    print!("Connecting to {host}:{port} ...{LINE_ENDING}");
+    let host_clone = host.clone();
+    let report = tokio::task::spawn_blocking(move || fetch_tls_chain(&host_clone, port)).await;
+
+    match report {
+        Ok(Ok(report)) => print_tls_report(&report),
+        Ok(Err(e)) => print!("TLS inspection failed: {:#}{}", e, LINE_ENDING),
+        Err(e) => print!("TLS inspection task failed: {}{}", e, LINE_ENDING),
+    }
+
+    print!("Press any key to return to main menu...{}", LINE_ENDING);
+    wait_for_keypress().await;
+}
+
+/// Connects to `host:port`, completes a TLS handshake, and returns the chain.
+fn fetch_tls_chain(host: &str, port: u16) -> Result<TlsReport> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let roots = Arc::new(rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    });
+    let verifier = Arc::new(RecordingVerifier {
+        inner: WebPkiServerVerifier::builder_with_provider(roots, provider.clone())
+            .build()
+            .context("Failed to build certificate verifier")?,
+        outcome: Mutex::new(None),
+    });
+    let config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .context("Failed to configure TLS protocol versions")?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+
+    let server_name =
+        ServerName::try_from(host.to_string()).context("Invalid host name for TLS")?;
+    let mut conn = rustls::ClientConnection::new(Arc::new(config), server_name)
+        .context("Failed to start TLS session")?;
+
+    let addr = (host, port)
+        .to_socket_addrs()
+        .context("Failed to resolve host")?
+        .next()
+        .context("Host resolved to no addresses")?;
+    let mut sock = std::net::TcpStream::connect_timeout(&addr, TLS_TIMEOUT)
+        .with_context(|| format!("Failed to connect to {addr}"))?;
+    sock.set_read_timeout(Some(TLS_TIMEOUT))?;
+    sock.set_write_timeout(Some(TLS_TIMEOUT))?;
+
+    while conn.is_handshaking() {
+        conn.complete_io(&mut sock)
+            .context("TLS handshake failed")?;
+    }
+
+    let chain = conn
+        .peer_certificates()
+        .map(|certs| certs.iter().map(|c| c.as_ref().to_vec()).collect())
+        .unwrap_or_default();
+    let verification = verifier
+        .outcome
+        .lock()
+        .ok()
+        .and_then(|o| o.clone())
+        .unwrap_or_else(|| Err("Certificate was not checked".to_string()));
+
+    Ok(TlsReport {
+        protocol: conn
+            .protocol_version()
+            .map(|v| format!("{v:?}"))
+            .unwrap_or_else(|| "unknown".to_string()),
+        cipher_suite: conn
+            .negotiated_cipher_suite()
+            .map(|s| format!("{:?}", s.suite()))
+            .unwrap_or_else(|| "unknown".to_string()),
+        chain,
+        verification,
+    })
+}
+
+fn print_tls_report(report: &TlsReport) {
+    print!(
+        "Protocol: {}  Cipher: {}{}",
+        report.protocol, report.cipher_suite, LINE_ENDING
+    );
+    match &report.verification {
+        Ok(()) => print!("Verification: OK (trusted chain){}", LINE_ENDING),
+        Err(e) => print!("Verification: FAILED - {}{}", e, LINE_ENDING),
+    }
+    if report.chain.is_empty() {
+        print!("The server sent no certificates.{}", LINE_ENDING);
+        return;
+    }
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    for (i, der) in report.chain.iter().enumerate() {
+        print!("{}", LINE_ENDING);
+        let role = if i == 0 { "leaf" } else { "intermediate/root" };
+        print!("[{}] Certificate ({}){}", i, role, LINE_ENDING);
+        let cert = match x509_parser::parse_x509_certificate(der) {
+            Ok((_, cert)) => cert,
+            Err(e) => {
+                print!("  Could not parse certificate: {}{}", e, LINE_ENDING);
+                continue;
+            }
+        };
+
+        print!("  Subject:    {}{}", cert.subject(), LINE_ENDING);
+        print!("  Issuer:     {}{}", cert.issuer(), LINE_ENDING);
+        if cert.subject() == cert.issuer() {
+            print!("  Note:       self-signed{}", LINE_ENDING);
+        }
+
+        let validity = cert.validity();
+        print!("  Not before: {}{}", validity.not_before, LINE_ENDING);
+        print!("  Not after:  {}{}", validity.not_after, LINE_ENDING);
+        let days_left = (validity.not_after.timestamp() - now).div_euclid(86_400);
+        if days_left < 0 {
+            print!(
+                "  Expiry:     EXPIRED {} day(s) ago{}",
+                -days_left, LINE_ENDING
+            );
+        } else if days_left <= TLS_EXPIRY_WARN_DAYS {
+            print!(
+                "  Expiry:     WARNING - expires in {} day(s){}",
+                days_left, LINE_ENDING
+            );
+        } else {
+            print!("  Expiry:     {} day(s) left{}", days_left, LINE_ENDING);
+        }
+        if validity.not_before.timestamp() > now {
+            print!("  Note:       not yet valid{}", LINE_ENDING);
+        }
+
+        if let Ok(Some(san)) = cert.subject_alternative_name() {
+            let names: Vec<String> = san
+                .value
+                .general_names
+                .iter()
+                .map(|name| match name {
+                    GeneralName::DNSName(dns) => dns.to_string(),
+                    GeneralName::IPAddress(bytes) => format_san_ip(bytes),
+                    other => other.to_string(),
+                })
+                .collect();
+            if !names.is_empty() {
+                print!("  SANs:       {}{}", names.join(", "), LINE_ENDING);
+            }
+        }
+    }
+}
+
+/// Formats a SAN IP address (4 or 16 raw bytes).
+fn format_san_ip(bytes: &[u8]) -> String {
+    if let Ok(v4) = <[u8; 4]>::try_from(bytes) {
+        std::net::IpAddr::from(v4).to_string()
+    } else if let Ok(v6) = <[u8; 16]>::try_from(bytes) {
+        std::net::IpAddr::from(v6).to_string()
+    } else {
+        format!("{bytes:?}")
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// SSH Host Key Fingerprints
+////////////////////////////////////////////////////////////////////////////////
+
+/// Host key algorithms probed one connection at a time (like `ssh-keyscan`),
+/// so every key the server offers is shown, not just the negotiated one.
+const SSH_HOST_KEY_ALGORITHMS: &[(&str, &str)] = &[
+    ("ED25519", "ssh-ed25519"),
+    ("ECDSA-256", "ecdsa-sha2-nistp256"),
+    ("ECDSA-384", "ecdsa-sha2-nistp384"),
+    ("ECDSA-521", "ecdsa-sha2-nistp521"),
+    ("RSA", "rsa-sha2-512,rsa-sha2-256,ssh-rsa"),
+];
+
+const SSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One host key offered by the server.
+struct SshHostKey {
+    label: &'static str,
+    /// OpenSSH-style fingerprint, `SHA256:<base64>`
+    fingerprint: String,
+    /// Result of checking the key against `~/.ssh/known_hosts`, if it was read
+    known: Option<CheckResult>,
+}
+
+struct SshScan {
+    banner: Option<String>,
+    keys: Vec<SshHostKey>,
+    /// Why `known_hosts` wasn't checked (missing file, unreadable, ...)
+    known_hosts_note: Option<String>,
+}
+
+async fn ssh_fingerprint_menu() {
+    let host = get_user_input("Enter SSH host (e.g. server.example.com):");
+    if host.is_empty() {
+        print!("No host specified.{}", LINE_ENDING);
+        wait_for_keypress().await;
+        return;
+    }
+    let port_str = get_user_input("Enter SSH port (default 22):");
+    let port: u16 = port_str.parse().unwrap_or(22);
+
+    print!("Fetching host keys from {host}:{port} ...{LINE_ENDING}");
+    let host_clone = host.clone();
+    let scan = tokio::task::spawn_blocking(move || fetch_ssh_host_keys(&host_clone, port)).await;
+
+    match scan {
+        Ok(Ok(scan)) => print_ssh_scan(&host, port, &scan),
+        Ok(Err(e)) => print!("SSH fingerprint check failed: {:#}{}", e, LINE_ENDING),
+        Err(e) => print!("SSH fingerprint task failed: {}{}", e, LINE_ENDING),
+    }
+
+    print!("Press any key to return to main menu...{}", LINE_ENDING);
+    wait_for_keypress().await;
+}
+
+/// Connects once per host key algorithm and collects each key the server
+/// accepts. The libssh2 handshake verifies the server's signature, so every
+/// key returned is one the server actually holds.
+fn fetch_ssh_host_keys(host: &str, port: u16) -> Result<SshScan> {
+    let addr = (host, port)
+        .to_socket_addrs()
+        .with_context(|| format!("Could not resolve {host}"))?
+        .next()
+        .with_context(|| format!("No addresses found for {host}"))?;
+    let known_hosts_path = dirs::home_dir().map(|home| home.join(".ssh").join("known_hosts"));
+
+    let mut scan = SshScan {
+        banner: None,
+        keys: Vec::new(),
+        known_hosts_note: None,
+    };
+    let mut last_error = None;
+    for (label, algorithms) in SSH_HOST_KEY_ALGORITHMS {
+        let stream = std::net::TcpStream::connect_timeout(&addr, SSH_TIMEOUT)
+            .with_context(|| format!("Could not connect to {addr}"))?;
+        let mut session = Session::new().context("Could not create SSH session")?;
+        session.set_timeout(SSH_TIMEOUT.as_millis() as u32);
+        session.set_tcp_stream(stream);
+        session.method_pref(MethodType::HostKey, algorithms)?;
+        // A failed handshake usually just means the server has no key of this type
+        if let Err(e) = session.handshake() {
+            last_error = Some(e);
+            continue;
+        }
+
+        let Some((key, _)) = session.host_key() else {
+            continue;
+        };
+        let hash = session.host_key_hash(HashType::Sha256).unwrap_or_default();
+        let fingerprint = format!(
+            "SHA256:{}",
+            base64::engine::general_purpose::STANDARD_NO_PAD.encode(hash)
+        );
+
+        let known = match &known_hosts_path {
+            Some(path) if path.exists() => {
+                let mut known_hosts = session.known_hosts()?;
+                match known_hosts.read_file(path, KnownHostFileKind::OpenSSH) {
+                    Ok(_) => Some(known_hosts.check_port(host, port, key)),
+                    Err(e) => {
+                        scan.known_hosts_note =
+                            Some(format!("Could not read {}: {e}", path.display()));
+                        None
+                    }
+                }
+            }
+            Some(path) => {
+                scan.known_hosts_note = Some(format!("{} does not exist", path.display()));
+                None
+            }
+            None => {
+                scan.known_hosts_note = Some("Could not locate the home directory".to_string());
+                None
+            }
+        };
 
-    let mut reachable = Vec::new();
-    for t in tasks {
-        if let Ok(Some(ip)) = t.await {
-            reachable.push(ip);
+        if scan.banner.is_none() {
+            scan.banner = session.banner().map(str::to_string);
         }
+        scan.keys.push(SshHostKey {
+            label,
+            fingerprint,
+            known,
+        });
+        let _ = session.disconnect(None, "fingerprint check done", None);
     }
 
-    if reachable.is_empty() {
+    if scan.keys.is_empty() {
+        return Err(match last_error {
+            Some(e) => anyhow::Error::new(e).context("SSH handshake failed"),
+            None => anyhow::anyhow!("The server offered no host keys"),
+        });
+    }
+    Ok(scan)
+}
+
+/// Prints each key and an overall known_hosts verdict. libssh2 reports a
+/// mismatch whenever the host is listed with a different key, including one of
+/// another type, so a per-key mismatch only counts when no offered key matches.
+fn print_ssh_scan(host: &str, port: u16, scan: &SshScan) {
+    print!(
+        "{LINE_ENDING}SSH host keys for {host}:{port} (server: {}){LINE_ENDING}",
+        scan.banner.as_deref().unwrap_or("unknown")
+    );
+    let any_match = scan
+        .keys
+        .iter()
+        .any(|k| matches!(k.known, Some(CheckResult::Match)));
+    for key in &scan.keys {
+        let status = match key.known {
+            None => "",
+            Some(CheckResult::Match) => "[matches known_hosts]",
+            Some(CheckResult::Mismatch) if any_match => "[not recorded]",
+            Some(CheckResult::Mismatch) => "[!! DIFFERS from known_hosts]",
+            Some(CheckResult::NotFound) => "[not in known_hosts]",
+            Some(CheckResult::Failure) => "[check failed]",
+        };
         print!(
-            "No hosts responded to ping in that /24 subnet.{}",
-            LINE_ENDING
+            "  {:<10} {}  {}{LINE_ENDING}",
+            key.label, key.fingerprint, status
         );
+    }
+
+    print!("{LINE_ENDING}");
+    if let Some(note) = &scan.known_hosts_note {
+        print!("known_hosts not checked: {note}.{LINE_ENDING}");
+        print!("Compare the fingerprints above with ones obtained from the server's administrator.{LINE_ENDING}");
+    } else if any_match {
+        print!("OK: the server presented a key recorded for it in known_hosts.{LINE_ENDING}");
+    } else if scan
+        .keys
+        .iter()
+        .any(|k| matches!(k.known, Some(CheckResult::Mismatch)))
+    {
+        print!("!! WARNING: known_hosts lists a DIFFERENT key for {host}.{LINE_ENDING}");
+        print!("!! The server was re-keyed or someone is intercepting the connection. Do not log in until you have confirmed the new fingerprint.{LINE_ENDING}");
     } else {
-        print!(
-            "Hosts responding to ping in {base_ip_str}/{cidr_bits}:{}",
-            LINE_ENDING
-        );
-        for ip in reachable {
-            print!("  {ip}{}", LINE_ENDING);
-        }
+        print!("{host} is not in known_hosts. Verify the fingerprints out of band before trusting it.{LINE_ENDING}");
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Web Server Discovery
+////////////////////////////////////////////////////////////////////////////////
+
+/// Ports checked on every host, with the scheme used to talk to them.
+const WEB_DISCOVERY_PORTS: &[(u16, &str)] = &[(80, "http"), (443, "https")];
+
+/// Concurrent TCP connection attempts during the sweep.
+const WEB_CONNECT_CONCURRENCY: usize = 64;
+
+/// Concurrent HTTP requests once open ports are known; kept low so small
+/// embedded web UIs (printers, routers, cameras) aren't swamped.
+const WEB_REQUEST_CONCURRENCY: usize = 8;
+
+/// Total time allowed for each HTTP request, including reading the body.
+const WEB_REQUEST_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Only this much of a page is read when looking for its `<title>`.
+const WEB_TITLE_MAX_BYTES: usize = 64 * 1024;
+
+/// One web server found during discovery.
+struct WebServer {
+    ip: String,
+    port: u16,
+    /// HTTP status line, or the error if the request failed
+    status: String,
+    server: Option<String>,
+    title: Option<String>,
+}
+
+async fn web_discovery_menu() {
+    let cidr_input = get_user_input("Enter subnet in CIDR notation (e.g., 192.168.1.0/24):");
+    if cidr_input.is_empty() {
+        print!("No subnet specified.{}", LINE_ENDING);
+        wait_for_keypress().await;
+        return;
     }
+    let base_ip = match parse_subnet_base(&cidr_input) {
+        Ok(base) => base.to_string(),
+        Err(message) => {
+            print!("{message}{LINE_ENDING}");
+            wait_for_keypress().await;
+            return;
+        }
+    };
 
+    match discover_web_servers(&base_ip).await {
+        Ok(servers) => print_web_servers(&base_ip, &servers),
+        Err(e) => print!("Web discovery failed: {e:#}{LINE_ENDING}"),
+    }
     print!("Press any key to return to main menu...{}", LINE_ENDING);
     wait_for_keypress().await;
 }
 
-fn increment_base_ip(base_ip: &str, offset: u8) -> String {
-    let mut parts: Vec<u8> = base_ip.split('.').filter_map(|s| s.parse().ok()).collect();
-    if parts.len() == 4 {
-        parts[3] = offset;
-        return format!("{}.{}.{}.{}", parts[0], parts[1], parts[2], parts[3]);
+/// Sweeps `base_ip`.1-254 for open web ports, then asks each one what it is.
+async fn discover_web_servers(base_ip: &str) -> Result<Vec<WebServer>> {
+    print!(
+        "Checking ports 80/443 on {base_ip}/24 ({} connections at a time)...{LINE_ENDING}",
+        WEB_CONNECT_CONCURRENCY
+    );
+    let connect_slots = Arc::new(Semaphore::new(WEB_CONNECT_CONCURRENCY));
+    let mut tasks = Vec::new();
+    for i in 1..255 {
+        let ip = increment_base_ip(base_ip, i);
+        for &(port, scheme) in WEB_DISCOVERY_PORTS {
+            let ip = ip.clone();
+            let slots = connect_slots.clone();
+            tasks.push(tokio::spawn(async move {
+                let _permit = slots.acquire().await.ok()?;
+                scan_port(&ip, port).await.map(|_| (ip, port, scheme))
+            }));
+        }
     }
-    base_ip.to_string()
+    let mut open = Vec::new();
+    for t in tasks {
+        if let Ok(Some(found)) = t.await {
+            open.push(found);
+        }
+    }
+    if open.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    print!(
+        "{} open web port(s); requesting headers and titles...{LINE_ENDING}",
+        open.len()
+    );
+    // LAN devices almost always use self-signed certificates, and we only
+    // want to identify them, so certificate checks are skipped here
+    let client = reqwest::Client::builder()
+        .timeout(WEB_REQUEST_TIMEOUT)
+        .danger_accept_invalid_certs(true)
+        .redirect(reqwest::redirect::Policy::limited(3))
+        .build()
+        .context("Failed to create HTTP client")?;
+    let request_slots = Arc::new(Semaphore::new(WEB_REQUEST_CONCURRENCY));
+    let tasks: Vec<_> = open
+        .into_iter()
+        .map(|(ip, port, scheme)| {
+            let client = client.clone();
+            let slots = request_slots.clone();
+            tokio::spawn(async move {
+                let _permit = slots.acquire().await.ok()?;
+                Some(identify_web_server(&client, ip, port, scheme).await)
+            })
+        })
+        .collect();
+
+    let mut servers = Vec::new();
+    for t in tasks {
+        if let Ok(Some(server)) = t.await {
+            servers.push(server);
+        }
+    }
+    Ok(servers)
 }
 
-fn detect_firewall_and_vpn() {
-    print!("Detecting Firewall & VPN ...{}", LINE_ENDING);
+/// Sends a `HEAD` for the status and `Server` header, then fetches the start
+/// of the page for its title when the server says it is HTML (or doesn't
+/// support `HEAD`).
+async fn identify_web_server(
+    client: &reqwest::Client,
+    ip: String,
+    port: u16,
+    scheme: &str,
+) -> WebServer {
+    let url = format!("{scheme}://{ip}:{port}/");
+    let mut server = WebServer {
+        ip,
+        port,
+        status: String::new(),
+        server: None,
+        title: None,
+    };
 
-    if cfg!(target_os = "windows") {
-        let firewall_status = Command::new("netsh")
-            .args(["advfirewall", "show", "allprofiles"])
-            .output();
-        match firewall_status {
-            Ok(o) => {
-                let out = String::from_utf8_lossy(&o.stdout).to_lowercase();
-                if out.contains("state on") {
-                    print!("Windows firewall appears to be ON.{}", LINE_ENDING);
-                } else if out.contains("state off") {
-                    print!("Windows firewall appears to be OFF.{}", LINE_ENDING);
-                } else {
-                    print!("Could not determine Windows firewall state.{}", LINE_ENDING);
-                }
-            }
-            Err(e) => {
-                print!("Error checking Windows firewall: {}{}", e, LINE_ENDING);
-            }
+    let head = match client.head(&url).send().await {
+        Ok(resp) => resp,
+        Err(e) => {
+            server.status = describe_request_error(e);
+            return server;
         }
+    };
+    server.status = head.status().to_string();
+    server.server = head
+        .headers()
+        .get(reqwest::header::SERVER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let is_html = head
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|t| t.contains("html"));
+    let head_refused = matches!(head.status().as_u16(), 405 | 501);
+    if !is_html && !head_refused {
+        return server;
+    }
 
-        let vpn_check = Command::new("ipconfig").output();
-        match vpn_check {
-            Ok(o) => {
-                let out = String::from_utf8_lossy(&o.stdout).to_lowercase();
-                if out.contains("tun") || out.contains("ppp") || out.contains("vpn") {
-                    print!("A VPN interface might be active.{}", LINE_ENDING);
-                } else {
-                    print!("No obvious VPN interface found.{}", LINE_ENDING);
-                }
-            }
-            Err(e) => {
-                print!("Error checking VPN: {}{}", e, LINE_ENDING);
-            }
+    if let Ok(mut resp) = client.get(&url).send().await {
+        if server.server.is_none() {
+            server.server = resp
+                .headers()
+                .get(reqwest::header::SERVER)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
         }
-    } else {
-        let firewall_status = Command::new("systemctl")
-            .args(["is-active", "firewalld"])
-            .output();
-        if let Ok(o) = firewall_status {
-            let out = String::from_utf8_lossy(&o.stdout).to_lowercase();
-            if out.contains("active") {
-                print!("firewalld service is ACTIVE.{}", LINE_ENDING);
-            } else {
-                print!(
-                    "firewalld service is not active or not found.{}",
-                    LINE_ENDING
-                );
+        if head_refused {
+            server.status = resp.status().to_string();
+        }
+        let mut body = Vec::new();
+        while let Ok(Some(chunk)) = resp.chunk().await {
+            body.extend_from_slice(&chunk);
+            if body.len() >= WEB_TITLE_MAX_BYTES {
+                break;
             }
         }
+        server.title = extract_html_title(&String::from_utf8_lossy(&body));
+    }
+    server
+}
 
-        let iptables_check = Command::new("iptables").arg("-L").output();
-        if let Ok(o) = iptables_check {
-            let out = String::from_utf8_lossy(&o.stdout);
-            print!(
-                "`iptables -L` returned:{}{}{}",
-                LINE_ENDING, out, LINE_ENDING
-            );
-        }
+/// Short reason for a failed request (timeout, TLS, connection reset, ...).
+fn describe_request_error(e: reqwest::Error) -> String {
+    if e.is_timeout() {
+        "timed out".to_string()
+    } else if e.is_connect() {
+        "connection failed".to_string()
+    } else {
+        format!("error: {}", e.without_url())
+    }
+}
 
-        let ifconfig_check = Command::new("ifconfig").output();
-        if let Ok(o) = ifconfig_check {
-            let out = String::from_utf8_lossy(&o.stdout).to_lowercase();
-            if out.contains("tun0") || out.contains("ppp0") || out.contains("wg0") {
-                print!(
-                    "A VPN or tunneling interface might be active.{}",
-                    LINE_ENDING
-                );
-            } else {
-                print!(
-                    "No obvious VPN interface found (tun0/ppp0/wg0).{}",
-                    LINE_ENDING
-                );
-            }
-        }
+/// Text of the first `<title>` element, with whitespace collapsed.
+fn extract_html_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = html[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if title.is_empty() {
+        None
+    } else {
+        Some(title.chars().take(BANNER_MAX_CHARS).collect())
     }
 }
 
-async fn latency_monitoring_menu() {
-    let host = get_user_input("Enter host/IP for continuous ping:");
+fn print_web_servers(base_ip: &str, servers: &[WebServer]) {
+    if servers.is_empty() {
+        print!("No web servers found on {base_ip}/24.{LINE_ENDING}");
+        return;
+    }
+    let mut sorted: Vec<&WebServer> = servers.iter().collect();
+    sorted.sort_by_key(|s| {
+        let last_octet: u8 =
+            s.ip.rsplit('.')
+                .next()
+                .and_then(|o| o.parse().ok())
+                .unwrap_or(0);
+        (last_octet, s.port)
+    });
+
+    print!(
+        "{LINE_ENDING}{:<16}{:<6}{:<20}{:<24}TITLE{LINE_ENDING}",
+        "ADDRESS", "PORT", "STATUS", "SERVER"
+    );
+    for s in sorted {
+        print!(
+            "{:<16}{:<6}{:<20}{:<24}{}{LINE_ENDING}",
+            s.ip,
+            s.port,
+            s.status,
+            // Keep a space before the title column
+            s.server
+                .as_deref()
+                .unwrap_or("-")
+                .chars()
+                .take(23)
+                .collect::<String>(),
+            s.title.as_deref().unwrap_or("-")
+        );
+    }
+    print!(
+        "{} web server(s) on {base_ip}/24.{LINE_ENDING}",
+        servers.len()
+    );
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Traceroute with Reverse DNS & Geolocation
+////////////////////////////////////////////////////////////////////////////////
+
+/// Hops probed before giving up.
+const TRACE_MAX_HOPS: u32 = 30;
+
+/// IP geolocation service; `{ip}` is replaced with the hop address.
+const GEO_LOOKUP_URL: &str = "https://ipinfo.io/{ip}/json";
+
+/// At most this many reverse DNS and geolocation lookups run at once, to stay
+/// polite to the (rate-limited) public services.
+const HOP_LOOKUP_CONCURRENCY: usize = 4;
+
+const HOP_LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One line of traceroute output, enriched with lookups.
+struct TraceHop {
+    number: u32,
+    /// `None` when the hop didn't answer (`*`)
+    ip: Option<std::net::IpAddr>,
+    rtt_ms: Option<f64>,
+    hostname: Option<String>,
+    /// Location, or why there is none (private address, lookup error, ...)
+    location: String,
+}
+
+/// Fields used from the ipinfo.io reply. Private addresses come back as `bogon`.
+#[derive(Debug, Deserialize)]
+struct GeoInfo {
+    city: Option<String>,
+    region: Option<String>,
+    country: Option<String>,
+    #[serde(default)]
+    bogon: bool,
+}
+
+async fn traceroute_menu() {
+    let host = get_user_input("Enter host for traceroute:");
     if host.is_empty() {
         print!("No host specified.{}", LINE_ENDING);
         wait_for_keypress().await;
         return;
     }
+    let with_geo = matches!(
+        get_user_input("Look up hop locations via ipinfo.io? (y/N):")
+            .to_lowercase()
+            .as_str(),
+        "y" | "yes"
+    );
 
     print!(
-        "Latency monitoring for {host} (press any key to stop)...{LINE_ENDING}\
-         Pinging once per second...{LINE_ENDING}"
+        "Tracing the route to {host} (up to {TRACE_MAX_HOPS} hops, this can take a minute)...{LINE_ENDING}"
     );
+    match run_traceroute(&host, with_geo).await {
+        Ok(hops) => print_trace_hops(&hops),
+        Err(e) => print!("Traceroute failed: {e:#}{LINE_ENDING}"),
+    }
 
-    loop {
-        let output = Command::new("ping")
-            .args(get_latency_ping_args(&host))
-            .output();
-        match output {
-            Ok(o) => {
-                let out = String::from_utf8_lossy(&o.stdout).to_string();
-                // Try to grab the last line for a short print
-                if let Some(line) = out.lines().last() {
-                    print!("{line}{}", LINE_ENDING);
-                } else {
-                    print!("{out}{}", LINE_ENDING);
-                }
-            }
-            Err(e) => {
-                print!("Ping error: {e}{}", LINE_ENDING);
+    print!("Press any key to return to main menu...{}", LINE_ENDING);
+    wait_for_keypress().await;
+}
+
+/// Runs the system traceroute with numeric output, then resolves every hop's
+/// hostname (PTR over DNS-over-HTTPS) and, if asked, its location. A failed
+/// lookup only affects its own hop.
+async fn run_traceroute(host: &str, with_geo: bool) -> Result<Vec<TraceHop>> {
+    let max_hops = TRACE_MAX_HOPS.to_string();
+    let output = if cfg!(target_os = "windows") {
+        tokio::process::Command::new("tracert")
+            .args(["-d", "-h", &max_hops, "-w", "2000", host])
+            .output()
+            .await
+            .context("could not run tracert")?
+    } else {
+        tokio::process::Command::new("traceroute")
+            .args(["-n", "-q", "1", "-w", "2", "-m", &max_hops, host])
+            .output()
+            .await
+            .context("could not run traceroute")?
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut hops: Vec<TraceHop> = stdout.lines().filter_map(parse_trace_hop).collect();
+    if hops.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("no hops in the output. {}", stderr.trim());
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(HOP_LOOKUP_TIMEOUT)
+        .build()
+        .context("Failed to create HTTP client")?;
+    let semaphore = Arc::new(Semaphore::new(HOP_LOOKUP_CONCURRENCY));
+    let mut tasks = Vec::new();
+    for (index, ip) in hops.iter().enumerate() {
+        let Some(ip) = ip.ip else {
+            continue;
+        };
+        let client = client.clone();
+        let semaphore = Arc::clone(&semaphore);
+        tasks.push(tokio::spawn(async move {
+            let Ok(_permit) = semaphore.acquire_owned().await else {
+                return (index, None, String::new());
+            };
+            if is_private_address(ip) {
+                // Public resolvers and geo services know nothing about these
+                return (index, None, "private network".to_string());
             }
+            let hostname = reverse_lookup(&client, ip).await;
+            let location = if with_geo {
+                match geolocate(&client, ip).await {
+                    Ok(location) => location,
+                    Err(e) => format!("lookup failed ({e:#})"),
+                }
+            } else {
+                "-".to_string()
+            };
+            (index, hostname, location)
+        }));
+    }
+    for task in tasks {
+        if let Ok((index, hostname, location)) = task.await {
+            hops[index].hostname = hostname;
+            hops[index].location = location;
+        }
+    }
+    Ok(hops)
+}
+
+/// Parses one hop line from `traceroute -n -q 1` (` 3  10.0.0.1  12.3 ms`)
+/// or `tracert -d` (`  3    12 ms    11 ms    <1 ms  10.0.0.1`).
+fn parse_trace_hop(line: &str) -> Option<TraceHop> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let number = tokens.first()?.parse().ok()?;
+    let ip = tokens.iter().find_map(|t| {
+        t.trim_matches(|c| c == '(' || c == ')' || c == '[' || c == ']')
+            .parse()
+            .ok()
+    });
+    let rtt_ms = tokens
+        .windows(2)
+        .find(|w| w[1] == "ms")
+        .and_then(|w| w[0].trim_start_matches('<').parse().ok());
+    Some(TraceHop {
+        number,
+        ip,
+        rtt_ms,
+        hostname: None,
+        location: "-".to_string(),
+    })
+}
+
+/// Addresses that only mean something inside a local network.
+fn is_private_address(ip: std::net::IpAddr) -> bool {
+    match ip {
+        std::net::IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            // 100.64.0.0/10 is carrier-grade NAT
+            v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || (a == 100 && (64..128).contains(&b))
         }
+        std::net::IpAddr::V6(v6) => {
+            let first = v6.segments()[0];
+            // fc00::/7 unique local, fe80::/10 link-local
+            v6.is_loopback() || (first & 0xfe00) == 0xfc00 || (first & 0xffc0) == 0xfe80
+        }
+    }
+}
 
-        // Check if a key was pressed to break out
-        if crossterm::event::poll(Duration::from_millis(100)).unwrap() {
-            if let Ok(CEvent::Key(_)) = event::read() {
-                break;
+/// The `in-addr.arpa`/`ip6.arpa` name queried for an address's PTR record.
+fn reverse_dns_name(ip: std::net::IpAddr) -> String {
+    match ip {
+        std::net::IpAddr::V4(v4) => {
+            let [a, b, c, d] = v4.octets();
+            format!("{d}.{c}.{b}.{a}.in-addr.arpa")
+        }
+        std::net::IpAddr::V6(v6) => {
+            let mut name = String::new();
+            for byte in v6.octets().iter().rev() {
+                name.push_str(&format!("{:x}.{:x}.", byte & 0x0f, byte >> 4));
             }
+            name + "ip6.arpa"
         }
-        // Sleep for ~1s
-        tokio::time::sleep(Duration::from_secs(1)).await;
     }
+}
 
-    print!(
-        "Stopped. Press any key to return to main menu...{}",
-        LINE_ENDING
-    );
-    wait_for_keypress().await;
+/// Reverse DNS through the default DoH provider; `None` if there's no PTR record.
+async fn reverse_lookup(client: &reqwest::Client, ip: std::net::IpAddr) -> Option<String> {
+    let (_, endpoint) = DOH_PROVIDERS[0];
+    let answers = doh_query(client, endpoint, &reverse_dns_name(ip), "PTR")
+        .await
+        .ok()?;
+    answers
+        .into_iter()
+        .find(|a| a.record_type == 12)
+        .map(|a| a.data.trim_end_matches('.').to_string())
 }
 
-fn get_latency_ping_args(host: &str) -> Vec<String> {
-    if cfg!(target_os = "windows") {
-        vec!["-n".to_string(), "1".to_string(), host.to_string()]
+/// "City, Region, CC" for a public address.
+async fn geolocate(client: &reqwest::Client, ip: std::net::IpAddr) -> Result<String> {
+    let info: GeoInfo = client
+        .get(GEO_LOOKUP_URL.replace("{ip}", &ip.to_string()))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| anyhow::anyhow!(describe_request_error(e)))?
+        .json()
+        .await
+        .context("invalid reply")?;
+    if info.bogon {
+        return Ok("private network".to_string());
+    }
+    let parts: Vec<String> = [info.city, info.region, info.country]
+        .into_iter()
+        .flatten()
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts.is_empty() {
+        Ok("unknown".to_string())
     } else {
-        vec!["-c".to_string(), "1".to_string(), host.to_string()]
+        Ok(parts.join(", "))
     }
 }
 
-async fn traceroute_menu() {
-    let host = get_user_input("Enter host for traceroute:");
-    if host.is_empty() {
-        print!("No host specified.{}", LINE_ENDING);
-        wait_for_keypress().await;
-        return;
+fn print_trace_hops(hops: &[TraceHop]) {
+    print!(
+        "{LINE_ENDING}{:<5}{:<18}{:<40}{:<11}LOCATION{LINE_ENDING}",
+        "HOP", "ADDRESS", "HOSTNAME", "RTT"
+    );
+    for hop in hops {
+        let address = hop.ip.map_or_else(|| "*".to_string(), |ip| ip.to_string());
+        let hostname: String = hop
+            .hostname
+            .as_deref()
+            .unwrap_or("-")
+            .chars()
+            .take(39)
+            .collect();
+        let rtt = hop
+            .rtt_ms
+            .map_or_else(|| "-".to_string(), |ms| format!("{ms:.1} ms"));
+        // Long IPv6 addresses push the row over rather than being cut
+        print!(
+            "{:<5}{:<18}{:<40}{:<11}{}{LINE_ENDING}",
+            hop.number,
+            address + " ",
+            hostname,
+            rtt,
+            hop.location
+        );
     }
+}
 
-    print!("Performing traceroute to {host} ...{}", LINE_ENDING);
-    if cfg!(target_os = "windows") {
-        let output = Command::new("tracert").arg(host.clone()).output();
-        match output {
-            Ok(o) => {
-                print!("{}{}", String::from_utf8_lossy(&o.stdout), LINE_ENDING);
-            }
-            Err(e) => {
-                print!("Failed to run tracert: {e}{}", LINE_ENDING);
+////////////////////////////////////////////////////////////////////////////////
+// Public vs Local IP Addresses
+////////////////////////////////////////////////////////////////////////////////
+
+/// Echo services that reply with the caller's address as plain text. The
+/// `api6` host only has an AAAA record, so it answers over IPv6 or not at all.
+const PUBLIC_IPV4_SERVICE: &str = "https://api.ipify.org";
+const PUBLIC_IPV6_SERVICE: &str = "https://api6.ipify.org";
+
+const PUBLIC_IP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Shows the public (NAT'd) IPv4/IPv6 addresses next to the local interface addresses.
+async fn show_ip_addresses() {
+    print!("Local interface addresses:{}", LINE_ENDING);
+    match if_addrs::get_if_addrs() {
+        Ok(mut interfaces) => {
+            interfaces.sort_by_key(|i| (i.is_loopback(), i.name.clone(), i.ip().is_ipv6()));
+            for interface in interfaces {
+                let note = if interface.is_loopback() {
+                    " (loopback)"
+                } else if interface.is_link_local() {
+                    " (link-local)"
+                } else {
+                    ""
+                };
+                print!(
+                    "  {:<12} {}{}{}",
+                    interface.name,
+                    interface.ip(),
+                    note,
+                    LINE_ENDING
+                );
             }
         }
-    } else {
-        let output = Command::new("traceroute").arg(host.clone()).output();
-        match output {
-            Ok(o) => {
-                print!("{}{}", String::from_utf8_lossy(&o.stdout), LINE_ENDING);
-            }
-            Err(e) => {
-                print!("Failed to run traceroute: {e}{}", LINE_ENDING);
+        Err(e) => print!("  Could not list interfaces: {}{}", e, LINE_ENDING),
+    }
+
+    print!(
+        "{}Public addresses (as seen from the internet):{}",
+        LINE_ENDING, LINE_ENDING
+    );
+    match reqwest::Client::builder()
+        .timeout(PUBLIC_IP_TIMEOUT)
+        .build()
+    {
+        Ok(client) => {
+            let (v4, v6) = tokio::join!(
+                fetch_public_ip(&client, PUBLIC_IPV4_SERVICE),
+                fetch_public_ip(&client, PUBLIC_IPV6_SERVICE),
+            );
+            for (label, result) in [("IPv4", v4), ("IPv6", v6)] {
+                match result {
+                    Ok(ip) => print!("  {label}: {ip}{LINE_ENDING}"),
+                    Err(e) => print!("  {label}: unavailable ({e:#}){LINE_ENDING}"),
+                }
             }
         }
+        Err(e) => print!("  Failed to create HTTP client: {}{}", e, LINE_ENDING),
     }
 
     print!("Press any key to return to main menu...{}", LINE_ENDING);
     wait_for_keypress().await;
 }
 
+/// Asks an echo service for our public address and checks it parses as an IP.
+async fn fetch_public_ip(client: &reqwest::Client, url: &str) -> Result<std::net::IpAddr> {
+    let body = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .context("service unreachable")?
+        .text()
+        .await?;
+    body.trim()
+        .parse()
+        .with_context(|| format!("unexpected reply {:?}", body.trim()))
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Graceful Exit & Misc Helpers
 ////////////////////////////////////////////////////////////////////////////////
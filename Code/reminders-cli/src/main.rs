@@ -9,12 +9,15 @@
 use std::{
     fs::{File, OpenOptions},
     io::{self, BufReader, BufWriter, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
 use anyhow::{bail, Context, Result};
-use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Weekday,
+};
 use clap::Parser;
 use crossterm::{
     cursor::MoveTo,
@@ -54,6 +57,9 @@ const LINE_ENDING: &str = "\n";
 #[command(author, version, about = "Reminders CLI - Ratatui Edition", long_about = None)]
 struct CliArgs {
     // Here, we've removed the verbose option entirely.
+    /// Reminders file to use; repeat to view several together (default: ~/.reminders.json)
+    #[arg(short, long = "file", value_name = "PATH")]
+    files: Vec<PathBuf>,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -68,6 +74,169 @@ struct Reminder {
     title: String,
     due: Option<DateTime<Local>>,
     completed: bool,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    priority: Option<Priority>,
+    /// Index of the file (in `App::sources`) this reminder is loaded from and saved to
+    #[serde(skip)]
+    source: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    fn parse(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "low" | "l" | "3" => Some(Priority::Low),
+            "medium" | "med" | "m" | "2" => Some(Priority::Medium),
+            "high" | "h" | "1" => Some(Priority::High),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Priority::Low => "low",
+            Priority::Medium => "medium",
+            Priority::High => "high",
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Color Themes & Config
+////////////////////////////////////////////////////////////////////////////////
+
+const CONFIG_FILE: &str = ".reminders_config.json";
+
+/// Every style the UI uses, so a theme can restyle the whole app.
+struct Theme {
+    name: &'static str,
+    /// Banner title text
+    title: Style,
+    /// Banner border
+    banner_border: Style,
+    /// Border of the welcome screen's Quick Start box
+    accent: Style,
+    /// Key help in the main banner
+    help: Style,
+    /// Highlighted reminder
+    selected: Style,
+    /// Text being typed
+    input: Style,
+    clock: Style,
+    countdown: Style,
+    status: Style,
+    /// Placeholder hints
+    hint: Style,
+    error: Style,
+    success: Style,
+}
+
+const THEMES: [Theme; 3] = [
+    Theme {
+        name: "default",
+        title: Style::new().fg(Color::Green).add_modifier(Modifier::BOLD),
+        banner_border: Style::new().fg(Color::Green),
+        accent: Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD),
+        help: Style::new().fg(Color::Cyan),
+        selected: Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        input: Style::new().fg(Color::Yellow),
+        clock: Style::new().fg(Color::Cyan),
+        countdown: Style::new().fg(Color::Green),
+        status: Style::new().fg(Color::Magenta),
+        hint: Style::new().fg(Color::DarkGray),
+        error: Style::new().fg(Color::Red),
+        success: Style::new().fg(Color::Green),
+    },
+    Theme {
+        name: "high-contrast",
+        title: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+        banner_border: Style::new().fg(Color::White),
+        accent: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+        help: Style::new().fg(Color::White),
+        selected: Style::new()
+            .fg(Color::Black)
+            .bg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+        input: Style::new()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+        clock: Style::new().fg(Color::White),
+        countdown: Style::new()
+            .fg(Color::LightGreen)
+            .add_modifier(Modifier::BOLD),
+        status: Style::new()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD),
+        hint: Style::new().fg(Color::Gray),
+        error: Style::new()
+            .fg(Color::LightRed)
+            .add_modifier(Modifier::BOLD),
+        success: Style::new()
+            .fg(Color::LightGreen)
+            .add_modifier(Modifier::BOLD),
+    },
+    // No colors at all: emphasis comes from bold/reversed/dim only
+    Theme {
+        name: "monochrome",
+        title: Style::new().add_modifier(Modifier::BOLD),
+        banner_border: Style::new(),
+        accent: Style::new().add_modifier(Modifier::BOLD),
+        help: Style::new(),
+        selected: Style::new().add_modifier(Modifier::REVERSED.union(Modifier::BOLD)),
+        input: Style::new().add_modifier(Modifier::BOLD),
+        clock: Style::new(),
+        countdown: Style::new(),
+        status: Style::new().add_modifier(Modifier::BOLD),
+        hint: Style::new().add_modifier(Modifier::DIM),
+        error: Style::new().add_modifier(Modifier::BOLD.union(Modifier::UNDERLINED)),
+        success: Style::new(),
+    },
+];
+
+/// Settings kept between runs in `~/.reminders_config.json`.
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct Config {
+    /// Name of the selected theme (falls back to the default if unknown)
+    #[serde(default)]
+    theme: String,
+}
+
+impl Config {
+    /// Loads the config; a missing or unreadable file means defaults.
+    fn load() -> Self {
+        get_config_file_path()
+            .ok()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = get_config_file_path()?;
+        let data = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, data).with_context(|| format!("Unable to write {:?}", path))
+    }
+
+    /// Index into `THEMES` of the configured theme.
+    fn theme_index(&self) -> usize {
+        THEMES
+            .iter()
+            .position(|t| t.name == self.theme)
+            .unwrap_or(0)
+    }
+}
+
+fn get_config_file_path() -> Result<PathBuf> {
+    let home = home_dir().context("Could not locate home directory")?;
+    Ok(home.join(CONFIG_FILE))
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -99,11 +268,19 @@ impl Drop for RawModeGuard {
 ////////////////////////////////////////////////////////////////////////////////
 
 struct App {
+    /// Reminders from every source file, merged into one list
     reminders: Vec<Reminder>,
+    /// The reminders files being viewed, in the order given on the command line
+    sources: Vec<PathBuf>,
+    /// Source file that new reminders are added to
+    target_source: usize,
     status_message: String,
     cursor_idx: usize,
     input_mode: InputMode,
     input_buffer: String,
+    /// Index into `THEMES`
+    theme: usize,
+    config: Config,
 }
 
 #[derive(PartialEq)]
@@ -111,24 +288,109 @@ enum InputMode {
     Normal,
     AddTitle,
     AddDue,
+    QuickAdd,
 }
 
 impl App {
-    fn new() -> Result<Self> {
-        let reminders = load_reminders()?;
+    fn new(sources: Vec<PathBuf>, config: Config) -> Result<Self> {
+        let mut reminders = Vec::new();
+        for (source, path) in sources.iter().enumerate() {
+            for mut reminder in load_reminders(path)? {
+                reminder.source = source;
+                reminders.push(reminder);
+            }
+        }
         Ok(Self {
             reminders,
+            sources,
+            target_source: 0,
             status_message: String::new(),
             cursor_idx: 0,
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
+            theme: config.theme_index(),
+            config,
         })
     }
 
+    fn theme(&self) -> &'static Theme {
+        &THEMES[self.theme]
+    }
+
+    /// Switches to the next built-in theme and remembers it in the config.
+    fn cycle_theme(&mut self) {
+        self.theme = (self.theme + 1) % THEMES.len();
+        self.config.theme = self.theme().name.to_string();
+        match self.config.save() {
+            Ok(()) => self.set_status(format!("Theme: {}", self.theme().name)),
+            Err(e) => self.set_status(format!(
+                "Theme: {} (could not save config: {e:#})",
+                self.theme().name
+            )),
+        }
+    }
+
     fn set_status(&mut self, msg: impl Into<String>) {
         self.status_message = msg.into();
     }
 
+    /// Short label for a source file (its name without extension). Files
+    /// with the same name are told apart by their directory, then by their
+    /// position on the command line. Only shown when more than one file is open.
+    fn source_label(&self, source: usize) -> String {
+        let clashes = |label: &dyn Fn(&Path) -> String| {
+            let own = label(&self.sources[source]);
+            self.sources
+                .iter()
+                .enumerate()
+                .any(|(i, path)| i != source && label(path) == own)
+        };
+        let with_dir = |path: &Path| match path.parent().and_then(Path::file_name) {
+            Some(dir) => format!("{}/{}", dir.to_string_lossy(), file_label(path)),
+            None => file_label(path),
+        };
+        if !clashes(&file_label) {
+            file_label(&self.sources[source])
+        } else if !clashes(&with_dir) {
+            with_dir(&self.sources[source])
+        } else {
+            format!("{}#{}", file_label(&self.sources[source]), source + 1)
+        }
+    }
+
+    /// How a reminder is referred to: `ID 3`, or `work:3` when several files
+    /// are open, since IDs are only unique within one file.
+    fn reminder_ref(&self, reminder: &Reminder) -> String {
+        if self.sources.len() > 1 {
+            format!("{}:{}", self.source_label(reminder.source), reminder.id)
+        } else {
+            format!("ID {}", reminder.id)
+        }
+    }
+
+    /// Writes one source file back with just the reminders that belong to it.
+    fn save_source(&self, source: usize) -> Result<()> {
+        let own: Vec<&Reminder> = self
+            .reminders
+            .iter()
+            .filter(|r| r.source == source)
+            .collect();
+        save_reminders(&self.sources[source], &own)
+    }
+
+    /// Cycles which file new reminders are added to.
+    fn cycle_target_source(&mut self) {
+        if self.sources.len() < 2 {
+            self.set_status("Only one reminders file is open.");
+            return;
+        }
+        self.target_source = (self.target_source + 1) % self.sources.len();
+        self.set_status(format!(
+            "New reminders go to {}",
+            self.sources[self.target_source].display()
+        ));
+    }
+
     fn move_cursor_up(&mut self) {
         if self.cursor_idx > 0 {
             self.cursor_idx -= 1;
@@ -141,21 +403,38 @@ impl App {
         }
     }
 
-    fn add_reminder(&mut self, title: &str, due: Option<DateTime<Local>>) -> Result<()> {
+    fn add_reminder(
+        &mut self,
+        title: &str,
+        due: Option<DateTime<Local>>,
+        tags: Vec<String>,
+        priority: Option<Priority>,
+    ) -> Result<()> {
         if title.trim().is_empty() {
             self.set_status("Title cannot be empty.");
             return Ok(());
         }
+        // Numbered past every open file, so new IDs never collide across files
         let new_id = self.reminders.iter().map(|r| r.id).max().unwrap_or(0) + 1;
         let reminder = Reminder {
             id: new_id,
             title: title.trim().to_string(),
             due,
             completed: false,
+            tags,
+            priority,
+            source: self.target_source,
         };
         self.reminders.push(reminder);
-        save_reminders(&self.reminders)?;
-        self.set_status("Reminder added successfully!");
+        self.save_source(self.target_source)?;
+        if self.sources.len() > 1 {
+            self.set_status(format!(
+                "Reminder added to {}!",
+                self.source_label(self.target_source)
+            ));
+        } else {
+            self.set_status("Reminder added successfully!");
+        }
         Ok(())
     }
 
@@ -167,8 +446,9 @@ impl App {
         if let Some(rem) = self.reminders.get_mut(self.cursor_idx) {
             rem.completed = true;
             let msg = format!("'{}' marked as completed.", rem.title);
+            let source = rem.source;
             self.set_status(msg);
-            save_reminders(&self.reminders)?;
+            self.save_source(source)?;
         }
         Ok(())
     }
@@ -178,14 +458,13 @@ impl App {
             self.set_status("No reminders to remove.");
             return Ok(());
         }
-        let removed_id = self.reminders[self.cursor_idx].id;
-        self.reminders.remove(self.cursor_idx);
+        let removed = self.reminders.remove(self.cursor_idx);
 
         if self.cursor_idx >= self.reminders.len() && !self.reminders.is_empty() {
             self.cursor_idx = self.reminders.len() - 1;
         }
-        save_reminders(&self.reminders)?;
-        self.set_status(format!("Removed reminder with ID {}", removed_id));
+        self.save_source(removed.source)?;
+        self.set_status(format!("Removed reminder {}", self.reminder_ref(&removed)));
         Ok(())
     }
 
@@ -194,7 +473,9 @@ impl App {
         if self.cursor_idx >= self.reminders.len() && !self.reminders.is_empty() {
             self.cursor_idx = self.reminders.len() - 1;
         }
-        save_reminders(&self.reminders)?;
+        for source in 0..self.sources.len() {
+            self.save_source(source)?;
+        }
         self.set_status("Cleared all completed reminders.");
         Ok(())
     }
@@ -207,7 +488,23 @@ impl App {
 #[tokio::main]
 async fn main() -> Result<()> {
     // 1) Parse CLI arguments (no verbose option)
-    let _args = CliArgs::parse();
+    let args = CliArgs::parse();
+    let sources = if args.files.is_empty() {
+        vec![get_reminders_file_path()?]
+    } else {
+        // The same file given twice would be loaded twice, and saving one
+        // copy would overwrite the other's edits
+        let mut sources: Vec<PathBuf> = Vec::new();
+        for path in args.files.iter().map(|p| canonical_source(p)) {
+            if !sources.contains(&path) {
+                sources.push(path);
+            }
+        }
+        sources
+    };
+
+    let config = Config::load();
+    let theme = &THEMES[config.theme_index()];
 
     // 2) Enable raw mode (RAII guard)
     let _raw_guard = RawModeGuard::new().context("Failed to enable raw mode")?;
@@ -217,7 +514,7 @@ async fn main() -> Result<()> {
     clear_screen(&mut terminal)?;
 
     // 4) Draw the welcome TUI
-    draw_welcome_screen(&mut terminal)?;
+    draw_welcome_screen(&mut terminal, theme)?;
 
     // 5) Temporarily drop raw mode (allow user to see the welcome)
     drop(_raw_guard);
@@ -235,7 +532,7 @@ async fn main() -> Result<()> {
     clear_screen(&mut terminal)?;
 
     // Create the app state
-    let mut app = App::new()?;
+    let mut app = App::new(sources, config)?;
 
     // 7) Run TUI event loop
     if let Err(e) = run_app(&mut terminal, &mut app) {
@@ -290,7 +587,10 @@ fn clear_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> R
 // Draw a small "Welcome" TUI (banner + help instructions, centered)
 ////////////////////////////////////////////////////////////////////////////////
 
-fn draw_welcome_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> Result<()> {
+fn draw_welcome_screen(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    theme: &Theme,
+) -> Result<()> {
     terminal.draw(|frame| {
         let size = frame.area();
 
@@ -301,7 +601,7 @@ fn draw_welcome_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>
             .split(size);
 
         // Banner
-        draw_banner(frame, chunks[0]);
+        draw_banner(frame, chunks[0], theme);
 
         // The main area, centered
         let main_area = centered_rect(60, 40, chunks[1]);
@@ -317,11 +617,7 @@ fn draw_welcome_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>
             Block::default()
                 .borders(Borders::ALL)
                 .title(" Quick Start ")
-                .border_style(
-                    Style::default()
-                        .fg(Color::Blue)
-                        .add_modifier(Modifier::BOLD),
-                ),
+                .border_style(theme.accent),
         );
 
         frame.render_widget(paragraph, main_area);
@@ -333,13 +629,8 @@ fn draw_welcome_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>
 // Draw a top banner
 ////////////////////////////////////////////////////////////////////////////////
 
-fn draw_banner(frame: &mut Frame<'_>, area: Rect) {
-    let line1 = Line::from(Span::styled(
-        "REMINDERS CLI",
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD),
-    ));
+fn draw_banner(frame: &mut Frame<'_>, area: Rect, theme: &Theme) {
+    let line1 = Line::from(Span::styled("REMINDERS CLI", theme.title));
     let line2 = Line::from("A Ratatui-based TUI for your reminders");
 
     let paragraph = Paragraph::new(vec![line1, line2])
@@ -347,7 +638,7 @@ fn draw_banner(frame: &mut Frame<'_>, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(" Welcome ")
-                .border_style(Style::default().fg(Color::Green)),
+                .border_style(theme.banner_border),
         )
         .alignment(Alignment::Center);
 
@@ -422,6 +713,12 @@ fn run_app(
                             app.input_buffer.clear();
                             app.set_status("Enter title, then press Enter (Esc to cancel)...");
                         }
+                        KeyCode::Char('n') => {
+                            // Quick-add: whole reminder on one line
+                            app.input_mode = InputMode::QuickAdd;
+                            app.input_buffer.clear();
+                            app.set_status("");
+                        }
                         KeyCode::Char('r') => {
                             // Remove selected
                             app.remove_selected()?;
@@ -434,6 +731,14 @@ fn run_app(
                             // Clear completed
                             app.clear_completed()?;
                         }
+                        KeyCode::Char('f') => {
+                            // Choose the file new reminders go to
+                            app.cycle_target_source();
+                        }
+                        KeyCode::Char('t') => {
+                            // Switch color theme
+                            app.cycle_theme();
+                        }
                         _ => {}
                     },
                     InputMode::AddTitle => match key.code {
@@ -477,11 +782,11 @@ fn run_app(
                             match parse_datetime(&title) {
                                 Ok(parsed_dt) => {
                                     // Valid date/time
-                                    app.add_reminder(&title, Some(parsed_dt))?;
+                                    app.add_reminder(&title, Some(parsed_dt), Vec::new(), None)?;
                                 }
                                 Err(_) => {
                                     // No valid date
-                                    app.add_reminder(&title, None)?;
+                                    app.add_reminder(&title, None, Vec::new(), None)?;
                                 }
                             }
                         }
@@ -498,6 +803,40 @@ fn run_app(
                         }
                         _ => {}
                     },
+                    InputMode::QuickAdd => match key.code {
+                        KeyCode::Enter => {
+                            let parsed = parse_quick_add(&app.input_buffer, Local::now());
+                            if let Some(bad) = parsed.due_error {
+                                app.set_status(format!(
+                                    "Could not understand due date '@{}'. Fix it or press Esc.",
+                                    bad
+                                ));
+                            } else {
+                                app.input_mode = InputMode::Normal;
+                                app.input_buffer.clear();
+                                app.add_reminder(
+                                    &parsed.title,
+                                    parsed.due,
+                                    parsed.tags,
+                                    parsed.priority,
+                                )?;
+                            }
+                        }
+                        KeyCode::Esc => {
+                            app.input_mode = InputMode::Normal;
+                            app.set_status("Quick add cancelled.");
+                            app.input_buffer.clear();
+                        }
+                        KeyCode::Backspace => {
+                            app.input_buffer.pop();
+                            app.set_status("");
+                        }
+                        KeyCode::Char(c) => {
+                            app.input_buffer.push(c);
+                            app.set_status("");
+                        }
+                        _ => {}
+                    },
                 }
             }
         }
@@ -509,6 +848,8 @@ fn run_app(
 ////////////////////////////////////////////////////////////////////////////////
 
 fn draw_main_ui(frame: &mut Frame<'_>, app: &App) {
+    let theme = app.theme();
+
     // Split screen into banner, main list, status bar
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -521,8 +862,8 @@ fn draw_main_ui(frame: &mut Frame<'_>, app: &App) {
 
     // Banner
     let banner_text = Line::from(Span::styled(
-        "Reminders CLI - [j/k: navigate] [a: add] [d: done] [r: remove] [c: clear] [q: quit]",
-        Style::default().fg(Color::Cyan),
+        "Reminders CLI - [j/k: navigate] [a: add] [n: quick add] [d: done] [r: remove] [c: clear] [f: target file] [t: theme] [q: quit]",
+        theme.help,
     ));
     let banner =
         Paragraph::new(banner_text).block(Block::default().borders(Borders::ALL).title(" Banner "));
@@ -539,22 +880,44 @@ fn draw_main_ui(frame: &mut Frame<'_>, app: &App) {
                 .due
                 .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
                 .unwrap_or_else(|| "No due date".to_string());
-            let text = format!("{} ID:{:>2} | {} | Due: {}", marker, r.id, r.title, due_str);
+            let mut text = if app.sources.len() > 1 {
+                format!(
+                    "{} {:>8} | {} | Due: {}",
+                    marker,
+                    app.reminder_ref(r),
+                    r.title,
+                    due_str
+                )
+            } else {
+                format!("{} ID:{:>2} | {} | Due: {}", marker, r.id, r.title, due_str)
+            };
+            if let Some(p) = r.priority {
+                text.push_str(&format!(" | !{}", p.label()));
+            }
+            if !r.tags.is_empty() {
+                let tags: Vec<String> = r.tags.iter().map(|t| format!("#{t}")).collect();
+                text.push_str(&format!(" | {}", tags.join(" ")));
+            }
 
             if i == app.cursor_idx {
-                ListItem::new(text).style(
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )
+                ListItem::new(text).style(theme.selected)
             } else {
                 ListItem::new(text)
             }
         })
         .collect();
 
+    let list_title = if app.sources.len() > 1 {
+        format!(
+            " Reminders ({} files, adding to {}) ",
+            app.sources.len(),
+            app.source_label(app.target_source)
+        )
+    } else {
+        " Reminders ".to_string()
+    };
     let reminders_list =
-        List::new(items).block(Block::default().borders(Borders::ALL).title(" Reminders "));
+        List::new(items).block(Block::default().borders(Borders::ALL).title(list_title));
     frame.render_widget(reminders_list, chunks[1]);
 
     // Status bar
@@ -562,45 +925,210 @@ fn draw_main_ui(frame: &mut Frame<'_>, app: &App) {
         InputMode::Normal => "Mode: Normal",
         InputMode::AddTitle => "Mode: Adding Title",
         InputMode::AddDue => "Mode: Adding Due Date",
+        InputMode::QuickAdd => "Mode: Quick Add",
     };
 
-    let status_lines = vec![
-        Line::from(Span::raw(mode_text)),
-        Line::from(Span::styled(
-            &app.status_message,
-            Style::default().fg(Color::Magenta),
-        )),
-    ];
+    let status_lines = if app.input_mode == InputMode::QuickAdd {
+        // Live preview of what the quick-add line will create
+        vec![
+            Line::from(vec![
+                Span::raw(format!("{mode_text} > ")),
+                Span::styled(&app.input_buffer, theme.input),
+            ]),
+            quick_add_preview(app),
+        ]
+    } else {
+        // Recomputed every tick, so the clock and countdown stay live
+        let now = Local::now();
+        vec![
+            Line::from(vec![
+                Span::raw(mode_text),
+                Span::raw(" | "),
+                Span::styled(now.format("%H:%M:%S").to_string(), theme.clock),
+                Span::raw(" | "),
+                Span::styled(next_due_countdown(&app.reminders, now), theme.countdown),
+            ]),
+            Line::from(Span::styled(&app.status_message, theme.status)),
+        ]
+    };
 
     let status_par = Paragraph::new(status_lines)
         .block(Block::default().borders(Borders::ALL).title(" Status "));
     frame.render_widget(status_par, chunks[2]);
 }
 
+/// "next reminder in Xh Ym" for the soonest incomplete reminder still due.
+fn next_due_countdown(reminders: &[Reminder], now: DateTime<Local>) -> String {
+    let next = reminders
+        .iter()
+        .filter(|r| !r.completed)
+        .filter_map(|r| r.due)
+        .filter(|due| *due >= now)
+        .min();
+    let Some(due) = next else {
+        return "no upcoming reminders".to_string();
+    };
+
+    let secs = (due - now).num_seconds();
+    let (hours, minutes) = (secs / 3600, secs % 3600 / 60);
+    if hours > 0 {
+        format!("next reminder in {hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("next reminder in {minutes}m")
+    } else {
+        format!("next reminder in {secs}s")
+    }
+}
+
+/// One-line summary of the parsed quick-add input (or the latest error).
+fn quick_add_preview(app: &App) -> Line<'static> {
+    let theme = app.theme();
+    if app.input_buffer.trim().is_empty() {
+        return Line::from(Span::styled(
+            "e.g. Buy milk @tomorrow 5pm #home !high  (Enter=add, Esc=cancel)",
+            theme.hint,
+        ));
+    }
+    if !app.status_message.is_empty() {
+        return Line::from(Span::styled(app.status_message.clone(), theme.error));
+    }
+
+    let parsed = parse_quick_add(&app.input_buffer, Local::now());
+    let due = match (&parsed.due, &parsed.due_error) {
+        (_, Some(bad)) => format!("?? '@{bad}'"),
+        (Some(dt), None) => dt.format("%Y-%m-%d %H:%M").to_string(),
+        (None, None) => "none".to_string(),
+    };
+    let tags = if parsed.tags.is_empty() {
+        "none".to_string()
+    } else {
+        parsed.tags.join(", ")
+    };
+    let priority = parsed.priority.map(Priority::label).unwrap_or("none");
+    let style = if parsed.due_error.is_some() {
+        theme.error
+    } else {
+        theme.success
+    };
+    Line::from(Span::styled(
+        format!(
+            "Preview: '{}' | Due: {} | Tags: {} | Priority: {}",
+            parsed.title, due, tags, priority
+        ),
+        style,
+    ))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Quick-Add Parsing
+////////////////////////////////////////////////////////////////////////////////
+
+/// Result of parsing a quick-add line like `Buy milk @tomorrow 5pm #home !high`.
+#[derive(Debug, Default)]
+struct QuickAdd {
+    title: String,
+    due: Option<DateTime<Local>>,
+    /// The `@...` text when it couldn't be understood as a date
+    due_error: Option<String>,
+    tags: Vec<String>,
+    priority: Option<Priority>,
+}
+
+/// Splits a quick-add line into title, `@due`, `#tags`, and `!priority`.
+/// The due expression greedily absorbs following words while it still parses,
+/// so `@tomorrow 5pm` works without quoting.
+fn parse_quick_add(input: &str, now: DateTime<Local>) -> QuickAdd {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut result = QuickAdd::default();
+    let mut title_words = Vec::new();
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        if let Some(expr) = word.strip_prefix('@').filter(|e| !e.is_empty()) {
+            // The date may span several words, and a prefix such as "in 2"
+            // need not parse on its own, so take the longest run that does
+            let end = words[i + 1..]
+                .iter()
+                .position(|w| w.starts_with(['@', '#', '!']))
+                .map_or(words.len(), |n| i + 1 + n);
+            let parsed = (i + 1..=end).rev().find_map(|j| {
+                let candidate = std::iter::once(expr)
+                    .chain(words[i + 1..j].iter().copied())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                parse_natural_datetime(&candidate, now)
+                    .ok()
+                    .map(|dt| (j, dt))
+            });
+            match parsed {
+                Some((j, dt)) => {
+                    result.due = Some(dt);
+                    result.due_error = None;
+                    i = j - 1;
+                }
+                None => result.due_error = Some(expr.to_string()),
+            }
+        } else if let Some(tag) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+            result.tags.push(tag.to_lowercase());
+        } else if let Some(p) = word.strip_prefix('!').and_then(Priority::parse) {
+            result.priority = Some(p);
+        } else {
+            title_words.push(word);
+        }
+        i += 1;
+    }
+
+    result.title = title_words.join(" ");
+    result
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // File & Data Logic
 ////////////////////////////////////////////////////////////////////////////////
 
-fn load_reminders() -> Result<Vec<Reminder>> {
-    let file_path = get_reminders_file_path()?;
+fn load_reminders(file_path: &Path) -> Result<Vec<Reminder>> {
     if !file_path.exists() {
         return Ok(Vec::new());
     }
     let file =
-        File::open(&file_path).with_context(|| format!("Unable to open file {:?}", file_path))?;
+        File::open(file_path).with_context(|| format!("Unable to open file {:?}", file_path))?;
     let reader = BufReader::new(file);
-    let reminders: Vec<Reminder> =
-        serde_json::from_reader(reader).with_context(|| "Failed to parse JSON")?;
+    let reminders: Vec<Reminder> = serde_json::from_reader(reader)
+        .with_context(|| format!("Failed to parse {:?}", file_path))?;
     Ok(reminders)
 }
 
-fn save_reminders(reminders: &[Reminder]) -> Result<()> {
-    let file_path = get_reminders_file_path()?;
+/// A file's name without extension (or leading dot), for source labels.
+fn file_label(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().trim_start_matches('.').to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Resolves a reminders file given on the command line to one canonical
+/// path, so `a.json` and `./a.json` are recognized as the same file. A file
+/// that doesn't exist yet is resolved through its directory.
+fn canonical_source(path: &Path) -> PathBuf {
+    if let Ok(path) = std::fs::canonicalize(path) {
+        return path;
+    }
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    match (std::fs::canonicalize(parent), path.file_name()) {
+        (Ok(dir), Some(name)) => dir.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn save_reminders(file_path: &Path, reminders: &[&Reminder]) -> Result<()> {
     let file = OpenOptions::new()
         .write(true)
         .create(true)
         .truncate(true)
-        .open(&file_path)
+        .open(file_path)
         .with_context(|| format!("Unable to open file for writing {:?}", file_path))?;
     let writer = BufWriter::new(file);
     serde_json::to_writer_pretty(writer, reminders).with_context(|| "Failed to write JSON")?;
@@ -640,3 +1168,127 @@ fn parse_datetime(input: &str) -> Result<DateTime<Local>> {
 
     bail!("Could not parse date/time string: {}", input)
 }
+
+/// Parses friendly date/time phrases relative to `now`, such as `tomorrow 5pm`,
+/// `fri 9:30`, `today noon`, `in 2 hours`, or `2025-03-01`.
+/// Falls back to the strict formats accepted by `parse_datetime`.
+fn parse_natural_datetime(input: &str, now: DateTime<Local>) -> Result<DateTime<Local>> {
+    if let Ok(dt) = parse_datetime(input) {
+        return Ok(dt);
+    }
+
+    let lower = input.trim().to_lowercase();
+    let words: Vec<&str> = lower
+        .split_whitespace()
+        .filter(|w| !matches!(*w, "at" | "on"))
+        .collect();
+    if words.is_empty() {
+        bail!("Empty date/time");
+    }
+
+    // Relative offsets: "in 30 minutes", "in 2 hours", "in 3 days", "in 1 week"
+    if words[0] == "in" && words.len() == 3 {
+        let n: i64 = words[1].parse().context("Expected a number after 'in'")?;
+        let delta = match words[2].trim_end_matches('s') {
+            "minute" | "min" => ChronoDuration::minutes(n),
+            "hour" | "hr" => ChronoDuration::hours(n),
+            "day" => ChronoDuration::days(n),
+            "week" => ChronoDuration::weeks(n),
+            other => bail!("Unknown unit: {}", other),
+        };
+        return Ok(now + delta);
+    }
+
+    let today = now.date_naive();
+    let mut date: Option<NaiveDate> = None;
+    let mut time: Option<NaiveTime> = None;
+    for word in words {
+        if let Some(d) = parse_date_word(word, today) {
+            if date.replace(d).is_some() {
+                bail!("More than one date in '{}'", input);
+            }
+        } else if let Some(t) = parse_time_word(word) {
+            if time.replace(t).is_some() {
+                bail!("More than one time in '{}'", input);
+            }
+        } else {
+            bail!("Unrecognized date/time word: {}", word);
+        }
+    }
+
+    let default_time = NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default();
+    let naive = match (date, time) {
+        (Some(d), t) => d.and_time(t.unwrap_or(default_time)),
+        // A bare time means the next occurrence of it
+        (None, Some(t)) => {
+            let candidate = today.and_time(t);
+            if candidate > now.naive_local() {
+                candidate
+            } else {
+                (today + ChronoDuration::days(1)).and_time(t)
+            }
+        }
+        (None, None) => bail!("No date or time in '{}'", input),
+    };
+
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .context("Date/time does not exist in the local time zone")
+}
+
+/// Recognizes `today`, `tonight`, `tomorrow`, weekday names, and `YYYY-mm-dd`.
+fn parse_date_word(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match word {
+        "today" | "tonight" => return Some(today),
+        "tomorrow" | "tmr" | "tmrw" => return Some(today + ChronoDuration::days(1)),
+        _ => {}
+    }
+    if let Ok(d) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        return Some(d);
+    }
+    let weekday = match word {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    // Next occurrence strictly after today
+    let ahead = (7 + weekday.num_days_from_monday() as i64
+        - today.weekday().num_days_from_monday() as i64)
+        % 7;
+    let ahead = if ahead == 0 { 7 } else { ahead };
+    Some(today + ChronoDuration::days(ahead))
+}
+
+/// Recognizes `5pm`, `5:30pm`, `17:30`, `noon`, and `midnight`.
+fn parse_time_word(word: &str) -> Option<NaiveTime> {
+    match word {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+    let (clock, meridiem) = if let Some(c) = word.strip_suffix("am") {
+        (c, Some(false))
+    } else if let Some(c) = word.strip_suffix("pm") {
+        (c, Some(true))
+    } else {
+        (word, None)
+    };
+    let (h, m) = match clock.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        // A bare number is only a time when it carries am/pm
+        None if meridiem.is_some() => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+    let hour = match meridiem {
+        Some(pm) if (1..=12).contains(&h) => (h % 12) + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None => h,
+    };
+    NaiveTime::from_hms_opt(hour, m, 0)
+}
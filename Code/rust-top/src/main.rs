@@ -10,13 +10,19 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use anyhow::{anyhow, Context, Result};
+use chrono::Local;
 use clap::Parser;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
+    fs::OpenOptions,
     io::{self, Write},
-    time::Duration,
+    path::PathBuf,
+    time::{Duration, Instant},
 };
 
+#[cfg(target_os = "linux")]
+use std::sync::OnceLock;
+
 use crossterm::{
     cursor::MoveTo,
     event::{
@@ -31,7 +37,7 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Row, Table},
+    widgets::{Block, Borders, Gauge, Paragraph, Row, Table, TableState, Wrap},
     Frame, Terminal,
 };
 
@@ -67,6 +73,34 @@ struct CliArgs {
     /// Enable mouse capture (default: false)
     #[arg(long)]
     mouse: bool,
+
+    /// Maximum number of process rows to show (default: fit to terminal height)
+    #[arg(long)]
+    rows: Option<usize>,
+
+    /// Log processes whose CPU usage exceeds this percentage
+    #[arg(long, value_name = "PERCENT")]
+    cpu_threshold: Option<f32>,
+
+    /// Log processes whose resident memory exceeds this many MiB
+    #[arg(long, value_name = "MIB")]
+    mem_threshold: Option<u64>,
+
+    /// File that threshold trips are appended to
+    #[arg(long, default_value = "rust-top-thresholds.log")]
+    threshold_log: PathBuf,
+
+    /// Quit immediately on 'q'/'Esc', even with a process selected or pinned
+    #[arg(long)]
+    no_confirm_quit: bool,
+
+    /// File that recorded system metrics are appended to (`.jsonl` for JSON Lines, otherwise CSV)
+    #[arg(long, default_value = "rust-top-metrics.csv")]
+    metrics_file: PathBuf,
+
+    /// Start recording metrics immediately (toggle with 'r')
+    #[arg(long)]
+    record: bool,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -80,8 +114,19 @@ struct ProcessInfo {
     name: String,
     state: String,
     ppid: u32,
+    /// Resident set size
     memory_kb: u64,
+    /// Virtual memory size
+    vsz_kb: u64,
+    /// Resident shared pages (file-backed and shared memory)
+    shared_kb: u64,
     cpu_percent: f32,
+    /// Cumulative user + system CPU time over the process's lifetime
+    cpu_time_secs: u64,
+    /// Real user ID from `/proc/<pid>/status`; `None` when it can't be read
+    uid: Option<u32>,
+    /// Owner's login name (or the numeric uid when it has no passwd entry)
+    user: String,
 }
 
 /// Tracks CPU usage per PID (last known jiffies) plus system total jiffies.
@@ -93,6 +138,876 @@ struct CpuTracker {
     last_total_jiffies: u64,
 }
 
+/// Combined charge of the laptop batteries in `/sys/class/power_supply`.
+#[derive(Debug, Clone)]
+struct BatteryStatus {
+    /// Charge level, 0-100
+    percent: f64,
+    /// Kernel status text: "Charging", "Discharging", "Full", "Not charging", ...
+    state: String,
+    /// Time until empty (discharging) or full (charging), when the rate is known
+    time_remaining_secs: Option<u64>,
+}
+
+impl BatteryStatus {
+    /// Gauge label, e.g. `83% Discharging, 2:41 left`.
+    fn label(&self) -> String {
+        let mut label = format!("{:.0}% {}", self.percent, self.state);
+        if let Some(secs) = self.time_remaining_secs {
+            let clock = format!("{}:{:02}", secs / 3_600, (secs % 3_600) / 60);
+            if self.state == "Charging" {
+                label.push_str(&format!(", {clock} until full"));
+            } else {
+                label.push_str(&format!(", {clock} left"));
+            }
+        }
+        label
+    }
+}
+
+/// One interface's traffic since the previous refresh, in bytes per second.
+#[derive(Debug, Clone)]
+struct InterfaceRate {
+    name: String,
+    rx_rate: f64,
+    tx_rate: f64,
+}
+
+/// Turns the cumulative byte counters in `/proc/net/dev` into per-interface rates.
+#[derive(Debug, Default)]
+struct NetworkTracker {
+    // Key: interface, Value: (rx bytes, tx bytes) at the previous refresh
+    last_bytes: HashMap<String, (u64, u64)>,
+    // When `last_bytes` was read
+    last_sample: Option<Instant>,
+    /// Rates over the last refresh, busiest interface first
+    rates: Vec<InterfaceRate>,
+    /// Highest single-direction rate of each refresh within `NETWORK_PEAK_WINDOW`
+    recent_peaks: VecDeque<(Instant, f64)>,
+    /// Highest of `recent_peaks`; scales the gauges
+    peak_rate: f64,
+}
+
+/// Interfaces listed in the network panel; quieter ones are summarized as "+N more".
+const NETWORK_PANEL_ROWS: usize = 4;
+
+/// Width of each rx/tx gauge bar in the network panel.
+const NETWORK_GAUGE_WIDTH: usize = 20;
+
+/// How far back the gauge scale looks, so one burst doesn't flatten the bars for good.
+const NETWORK_PEAK_WINDOW: Duration = Duration::from_secs(60);
+
+impl NetworkTracker {
+    /// Records a new counter sample and recomputes rates against the previous one.
+    fn update(&mut self, counters: Vec<(String, u64, u64)>, now: Instant) {
+        let elapsed = self
+            .last_sample
+            .map(|then| now.duration_since(then).as_secs_f64())
+            .filter(|secs| *secs > 0.0);
+
+        // Interfaces without a previous sample (first tick, or just appeared) wait a tick
+        self.rates = counters
+            .iter()
+            .filter_map(|(name, rx, tx)| {
+                let secs = elapsed?;
+                let (last_rx, last_tx) = self.last_bytes.get(name)?;
+                // Counters that went backwards (interface reset) count as idle
+                Some(InterfaceRate {
+                    name: name.clone(),
+                    rx_rate: rx.saturating_sub(*last_rx) as f64 / secs,
+                    tx_rate: tx.saturating_sub(*last_tx) as f64 / secs,
+                })
+            })
+            .collect();
+        self.rates.sort_by(|a, b| {
+            (b.rx_rate + b.tx_rate)
+                .total_cmp(&(a.rx_rate + a.tx_rate))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let tick_peak = self
+            .rates
+            .iter()
+            .fold(0.0_f64, |peak, r| peak.max(r.rx_rate).max(r.tx_rate));
+        self.recent_peaks.push_back((now, tick_peak));
+        while self
+            .recent_peaks
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > NETWORK_PEAK_WINDOW)
+        {
+            self.recent_peaks.pop_front();
+        }
+        self.peak_rate = self
+            .recent_peaks
+            .iter()
+            .fold(0.0, |peak, (_, rate)| peak.max(*rate));
+
+        self.last_bytes = counters
+            .into_iter()
+            .map(|(name, rx, tx)| (name, (rx, tx)))
+            .collect();
+        self.last_sample = Some(now);
+    }
+
+    /// Combined receive and transmit rate over all interfaces.
+    fn totals(&self) -> (f64, f64) {
+        self.rates
+            .iter()
+            .fold((0.0, 0.0), |(rx, tx), r| (rx + r.rx_rate, tx + r.tx_rate))
+    }
+
+    /// Rows the network panel needs (0 hides it when no interfaces are known).
+    fn panel_height(&self) -> u16 {
+        if self.last_bytes.is_empty() {
+            return 0;
+        }
+        let lines = match self.rates.len() {
+            0 => 1, // "measuring..."
+            n if n > NETWORK_PANEL_ROWS => NETWORK_PANEL_ROWS + 1,
+            n => n,
+        };
+        lines as u16 + 2
+    }
+}
+
+/// Runtime state for the process monitor.
+#[derive(Debug, Default)]
+struct App {
+    cpu_tracker: CpuTracker,
+    /// Per-interface network rates from `/proc/net/dev`
+    network: NetworkTracker,
+    /// Latest process snapshot, sorted for display
+    processes: Vec<ProcessInfo>,
+    /// Selected row in the main process table
+    selected: Option<usize>,
+    /// Index of the first process row shown in the main table
+    scroll_offset: usize,
+    /// Process rows that fit in the main table (updated on every draw)
+    page_rows: usize,
+    /// User cap on visible rows (`--rows`); `None` fits the terminal height
+    row_limit: Option<usize>,
+    /// PIDs pinned to the watchlist, in the order they were added
+    watchlist: Vec<u32>,
+    /// Latest snapshot of the pinned processes, taken before `only_mine` filters the list
+    watched: Vec<ProcessInfo>,
+    /// Show the extra VSZ and shared-memory columns
+    show_memory_details: bool,
+    /// Logs CPU/memory threshold trips when any threshold is configured
+    threshold_log: Option<ThresholdLog>,
+    /// Appends system-level metrics to a file while recording is on
+    metrics: MetricsRecorder,
+    /// Saved display preferences (banner visibility, ...)
+    prefs: Preferences,
+    /// System uptime in seconds, from `/proc/uptime`
+    uptime_secs: Option<u64>,
+    /// 1, 5 and 15-minute load averages, from `/proc/loadavg`
+    load_avg: Option<[f64; 3]>,
+    /// Detail popup for one process; refreshed each tick while open
+    detail: Option<ProcessDetail>,
+    /// Set once the user moves the selection, i.e. has picked a target process
+    selection_moved: bool,
+    /// Whether 'q'/'Esc' ask before quitting when there is work to lose
+    confirm_quit: bool,
+    /// The quit-confirmation dialog is showing
+    quit_prompt: bool,
+    /// Show only processes owned by the current user
+    only_mine: bool,
+    /// Login names by uid, read from `/etc/passwd` on the first refresh
+    user_names: HashMap<u32, String>,
+    /// Battery charge and state; `None` hides the battery panel
+    battery: Option<BatteryStatus>,
+    /// Row under the cursor while the column-configuration popup is open
+    column_cursor: Option<usize>,
+    /// Process the signal confirmation dialog is asking about
+    signal_prompt: Option<ProcessInfo>,
+    /// Outcome of the last signal sent, shown under the process table for a while
+    signal_status: Option<(SignalOutcome, Instant)>,
+}
+
+/// Everything we can learn about one PID, for the detail popup.
+#[derive(Debug, Clone)]
+struct ProcessDetail {
+    /// The table row this popup was opened from
+    info: ProcessInfo,
+    command_line: String,
+    /// Fields below are read from `/proc/<pid>/...` and hold a short reason
+    /// (e.g. "permission denied") when that file can't be read
+    cwd: String,
+    environment: String,
+    open_fds: String,
+    threads: Option<u64>,
+    started: Option<String>,
+    user_cpu_secs: f64,
+    system_cpu_secs: f64,
+    rss_anon_kb: Option<u64>,
+    rss_file_kb: Option<u64>,
+    rss_shmem_kb: Option<u64>,
+    swap_kb: Option<u64>,
+    /// Set once the process is gone; the popup keeps its last snapshot
+    exited: bool,
+}
+
+/// Appends processes that exceed the configured CPU/memory limits to a log file.
+#[derive(Debug)]
+struct ThresholdLog {
+    cpu_percent: Option<f32>,
+    memory_kb: Option<u64>,
+    path: PathBuf,
+    /// Total trips recorded since startup
+    trips: usize,
+    /// Short description of the most recent trip, for the on-screen indicator
+    last_trip: Option<String>,
+    /// Last write failure, shown instead of silently dropping entries
+    error: Option<String>,
+}
+
+impl ThresholdLog {
+    /// Builds a logger from the CLI flags; `None` when no threshold is set.
+    fn from_args(args: &CliArgs) -> Option<Self> {
+        if args.cpu_threshold.is_none() && args.mem_threshold.is_none() {
+            return None;
+        }
+        Some(Self {
+            cpu_percent: args.cpu_threshold,
+            memory_kb: args.mem_threshold.map(|mib| mib * 1024),
+            path: args.threshold_log.clone(),
+            trips: 0,
+            last_trip: None,
+            error: None,
+        })
+    }
+
+    /// Logs every process currently over a threshold, one line per process.
+    fn record(&mut self, processes: &[ProcessInfo]) {
+        let now = Local::now();
+        let timestamp = now.format("%Y-%m-%d %H:%M:%S");
+        let mut entries = Vec::new();
+        for p in processes {
+            let mut reasons = Vec::new();
+            if self.cpu_percent.is_some_and(|limit| p.cpu_percent > limit) {
+                reasons.push("cpu");
+            }
+            if self.memory_kb.is_some_and(|limit| p.memory_kb > limit) {
+                reasons.push("mem");
+            }
+            if reasons.is_empty() {
+                continue;
+            }
+            entries.push(format!(
+                "{timestamp} pid={} name={} cpu={:.1}% mem={} exceeded={}",
+                p.pid,
+                p.name,
+                p.cpu_percent,
+                human_readable_mem(p.memory_kb),
+                reasons.join(",")
+            ));
+            self.last_trip = Some(format!(
+                "{} ({}) at {}",
+                p.name,
+                p.pid,
+                now.format("%H:%M:%S")
+            ));
+        }
+        if entries.is_empty() {
+            return;
+        }
+
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| {
+                entries
+                    .iter()
+                    .try_for_each(|line| write!(file, "{line}{LINE_ENDING}"))
+            });
+        match result {
+            Ok(()) => {
+                self.trips += entries.len();
+                self.error = None;
+            }
+            Err(e) => self.error = Some(format!("{}: {e}", self.path.display())),
+        }
+    }
+
+    /// One-line status for the banner.
+    fn indicator(&self) -> Option<Span<'static>> {
+        if let Some(err) = &self.error {
+            return Some(Span::styled(
+                format!("  [threshold log error: {err}]"),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        self.last_trip.as_ref().map(|last| {
+            Span::styled(
+                format!("  [! {} threshold trips logged, last: {last}]", self.trips),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        })
+    }
+}
+
+/// File (in the home directory) holding display preferences as `key=value` lines.
+const PREFS_FILE: &str = ".rust_top_prefs";
+
+/// Display settings remembered between runs.
+#[derive(Debug, Clone)]
+struct Preferences {
+    /// Show the header banner; off gives the whole screen to the tables
+    show_banner: bool,
+    /// Unit used for the memory columns of the process tables
+    memory_unit: MemoryUnit,
+    /// Columns of the process tables, in display order
+    columns: Vec<Column>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            show_banner: true,
+            memory_unit: MemoryUnit::Auto,
+            columns: DEFAULT_COLUMNS.to_vec(),
+        }
+    }
+}
+
+impl Preferences {
+    fn path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(PREFS_FILE))
+    }
+
+    /// Loads saved preferences; a missing file or unknown keys keep the defaults.
+    fn load() -> Self {
+        let mut prefs = Self::default();
+        let contents = Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .unwrap_or_default();
+        for (key, value) in contents.lines().filter_map(|l| l.split_once('=')) {
+            match key.trim() {
+                "show_banner" => prefs.show_banner = value.trim() != "false",
+                "memory_unit" => {
+                    if let Some(unit) = MemoryUnit::parse(value.trim()) {
+                        prefs.memory_unit = unit;
+                    }
+                }
+                "columns" => {
+                    let mut columns: Vec<Column> = Vec::new();
+                    for column in value.split(',').filter_map(|c| Column::parse(c.trim())) {
+                        if !columns.contains(&column) {
+                            columns.push(column);
+                        }
+                    }
+                    if !columns.is_empty() {
+                        prefs.columns = columns;
+                    }
+                }
+                _ => {}
+            }
+        }
+        prefs
+    }
+
+    /// Saves the preferences; failures are ignored since they are only a convenience.
+    fn save(&self) {
+        if let Some(path) = Self::path() {
+            let columns: Vec<&str> = self.columns.iter().map(|c| c.key()).collect();
+            let _ = std::fs::write(
+                path,
+                format!(
+                    "show_banner={}{LINE_ENDING}memory_unit={}{LINE_ENDING}columns={}{LINE_ENDING}",
+                    self.show_banner,
+                    self.memory_unit.label(),
+                    columns.join(",")
+                ),
+            );
+        }
+    }
+}
+
+/// How memory values are shown in the process tables: scaled per value, or
+/// all in one unit so a column can be compared at a glance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MemoryUnit {
+    Auto,
+    Kb,
+    Mb,
+    Gb,
+}
+
+impl MemoryUnit {
+    /// Cycles Auto -> KB -> MB -> GB -> Auto.
+    fn next(self) -> Self {
+        match self {
+            MemoryUnit::Auto => MemoryUnit::Kb,
+            MemoryUnit::Kb => MemoryUnit::Mb,
+            MemoryUnit::Mb => MemoryUnit::Gb,
+            MemoryUnit::Gb => MemoryUnit::Auto,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            MemoryUnit::Auto => "auto",
+            MemoryUnit::Kb => "KB",
+            MemoryUnit::Mb => "MB",
+            MemoryUnit::Gb => "GB",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        [
+            MemoryUnit::Auto,
+            MemoryUnit::Kb,
+            MemoryUnit::Mb,
+            MemoryUnit::Gb,
+        ]
+        .into_iter()
+        .find(|unit| unit.label().eq_ignore_ascii_case(value))
+    }
+
+    /// Formats a KiB amount in this unit. A fixed unit is named in the column
+    /// header rather than repeated on every row.
+    fn format(self, kb: u64) -> String {
+        match self {
+            MemoryUnit::Auto => human_readable_mem(kb),
+            MemoryUnit::Kb => kb.to_string(),
+            MemoryUnit::Mb => format!("{:.1}", kb as f64 / 1024.0),
+            MemoryUnit::Gb => format!("{:.2}", kb as f64 / (1024.0 * 1024.0)),
+        }
+    }
+}
+
+/// Appends one row of system-level metrics per refresh to a CSV or JSONL file.
+#[derive(Debug, Default)]
+struct MetricsRecorder {
+    path: PathBuf,
+    /// Write JSON Lines instead of CSV (chosen from the file extension)
+    jsonl: bool,
+    /// Whether rows are currently being written
+    active: bool,
+    /// Rows written since startup
+    rows: usize,
+    /// `(total, idle)` jiffies from the previous sample, for total CPU%
+    last_cpu: Option<(u64, u64)>,
+    /// Last write failure; recording stops until toggled on again
+    error: Option<String>,
+}
+
+/// Column order shared by the CSV header and each JSONL object.
+const METRICS_FIELDS: [&str; 8] = [
+    "timestamp",
+    "cpu_percent",
+    "mem_used_kb",
+    "mem_total_kb",
+    "load_1",
+    "load_5",
+    "load_15",
+    "processes",
+];
+
+impl MetricsRecorder {
+    fn from_args(args: &CliArgs) -> Self {
+        let jsonl = args.metrics_file.extension().is_some_and(|ext| {
+            ext.eq_ignore_ascii_case("jsonl") || ext.eq_ignore_ascii_case("json")
+        });
+        Self {
+            path: args.metrics_file.clone(),
+            jsonl,
+            active: args.record,
+            ..Self::default()
+        }
+    }
+
+    fn toggle(&mut self) {
+        self.active = !self.active;
+        self.error = None;
+    }
+
+    /// Samples CPU and memory and, while recording, appends a row. CPU is
+    /// sampled even when not recording so the first recorded row has a value.
+    fn record(&mut self, load_avg: Option<[f64; 3]>, process_count: usize) {
+        #[cfg(target_os = "linux")]
+        let cpu_now = read_cpu_jiffies().ok();
+        #[cfg(not(target_os = "linux"))]
+        let cpu_now: Option<(u64, u64)> = None;
+
+        let cpu_percent = match (self.last_cpu, cpu_now) {
+            (Some((total_prev, idle_prev)), Some((total, idle))) if total > total_prev => {
+                let busy = (total - total_prev).saturating_sub(idle.saturating_sub(idle_prev));
+                Some(busy as f64 * 100.0 / (total - total_prev) as f64)
+            }
+            _ => None,
+        };
+        self.last_cpu = cpu_now;
+        if !self.active {
+            return;
+        }
+
+        #[cfg(target_os = "linux")]
+        let memory = read_memory_kb().ok();
+        #[cfg(not(target_os = "linux"))]
+        let memory: Option<(u64, u64)> = None;
+
+        let values = [
+            cpu_percent.map(|v| format!("{v:.1}")),
+            memory.map(|(used, _)| used.to_string()),
+            memory.map(|(_, total)| total.to_string()),
+            load_avg.map(|l| format!("{:.2}", l[0])),
+            load_avg.map(|l| format!("{:.2}", l[1])),
+            load_avg.map(|l| format!("{:.2}", l[2])),
+            Some(process_count.to_string()),
+        ];
+        let timestamp = Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+
+        let line = if self.jsonl {
+            let fields: Vec<String> = METRICS_FIELDS[1..]
+                .iter()
+                .zip(&values)
+                .map(|(name, value)| format!("\"{name}\":{}", value.as_deref().unwrap_or("null")))
+                .collect();
+            format!("{{\"timestamp\":\"{timestamp}\",{}}}", fields.join(","))
+        } else {
+            let fields: Vec<&str> = values.iter().map(|v| v.as_deref().unwrap_or("")).collect();
+            format!("{timestamp},{}", fields.join(","))
+        };
+
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| {
+                // A new CSV file starts with its header
+                if !self.jsonl && file.metadata()?.len() == 0 {
+                    write!(file, "{}{LINE_ENDING}", METRICS_FIELDS.join(","))?;
+                }
+                write!(file, "{line}{LINE_ENDING}")
+            });
+        match result {
+            Ok(()) => self.rows += 1,
+            Err(e) => {
+                self.error = Some(format!("{}: {e}", self.path.display()));
+                self.active = false;
+            }
+        }
+    }
+
+    /// REC indicator (or the last write error) for the banner.
+    fn indicator(&self) -> Option<Span<'static>> {
+        if let Some(err) = &self.error {
+            return Some(Span::styled(
+                format!("  [recording stopped: {err}]"),
+                Style::default().fg(Color::Red),
+            ));
+        }
+        self.active.then(|| {
+            Span::styled(
+                format!("  [● REC {} rows -> {}]", self.rows, self.path.display()),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            )
+        })
+    }
+}
+
+impl App {
+    /// Takes a fresh process snapshot and keeps selection and watchlist in sync.
+    fn refresh(&mut self) {
+        #[cfg(target_os = "linux")]
+        let total_jiffies_now = read_total_jiffies().unwrap_or(0);
+        #[cfg(not(target_os = "linux"))]
+        let total_jiffies_now = 0;
+
+        #[cfg(target_os = "linux")]
+        let mut processes =
+            read_process_list(&mut self.cpu_tracker, total_jiffies_now).unwrap_or_default();
+        #[cfg(not(target_os = "linux"))]
+        let mut processes = Vec::new();
+
+        #[cfg(target_os = "linux")]
+        {
+            self.uptime_secs = read_uptime_secs().ok();
+            self.load_avg = read_load_avg().ok();
+            if let Ok(counters) = read_net_dev() {
+                self.network.update(counters, Instant::now());
+            }
+            self.battery = read_battery();
+        }
+
+        // Sort by memory usage descending
+        processes.sort_by_key(|p| std::cmp::Reverse(p.memory_kb));
+
+        if self.user_names.is_empty() {
+            self.user_names = read_user_names();
+        }
+        for p in &mut processes {
+            p.user = match p.uid {
+                Some(uid) => self
+                    .user_names
+                    .get(&uid)
+                    .cloned()
+                    .unwrap_or_else(|| uid.to_string()),
+                None => "?".to_string(),
+            };
+        }
+
+        // Logs and metrics always cover the whole system, whatever is shown
+        if let Some(log) = &mut self.threshold_log {
+            log.record(&processes);
+        }
+        self.metrics.record(self.load_avg, processes.len());
+
+        // Pins and the detail popup follow processes that exist, not ones that
+        // happen to be shown, so they are checked before any filtering
+        self.watchlist
+            .retain(|pid| processes.iter().any(|p| p.pid == *pid));
+        self.watched = self
+            .watchlist
+            .iter()
+            .filter_map(|pid| processes.iter().find(|p| p.pid == *pid))
+            .cloned()
+            .collect();
+
+        if let Some(detail) = &mut self.detail {
+            match processes.iter().find(|p| p.pid == detail.info.pid) {
+                Some(info) if !detail.exited => {
+                    if let Ok(fresh) = read_process_detail(info, self.uptime_secs) {
+                        *detail = fresh;
+                    }
+                }
+                _ => detail.exited = true,
+            }
+        }
+
+        // Processes whose owner can't be read are never counted as ours
+        if self.only_mine {
+            let uid = current_uid();
+            processes.retain(|p| p.uid.is_some() && p.uid == uid);
+        }
+
+        // Keep the same PID selected across refreshes when it still exists
+        let selected_pid = self.selected_process().map(|p| p.pid);
+        self.processes = processes;
+        let index = selected_pid
+            .and_then(|pid| self.processes.iter().position(|p| p.pid == pid))
+            .or(if self.processes.is_empty() {
+                None
+            } else {
+                Some(0)
+            })
+            .map(|i| i.min(self.processes.len().saturating_sub(1)));
+        self.selected = index;
+    }
+
+    /// Opens the detail popup for the selected process.
+    fn open_detail(&mut self) {
+        let Some(info) = self.selected_process() else {
+            return;
+        };
+        self.detail = read_process_detail(info, self.uptime_secs).ok();
+    }
+
+    /// Columns shown in the process tables, in display order: the configured
+    /// columns, plus VSZ and Shared while `v` is on.
+    fn visible_columns(&self) -> Vec<Column> {
+        let mut columns = self.prefs.columns.clone();
+        if self.show_memory_details {
+            for column in [Column::Vsz, Column::Shared] {
+                if !columns.contains(&column) {
+                    columns.push(column);
+                }
+            }
+        }
+        columns
+    }
+
+    /// Rows of the column popup: shown columns in display order, then the
+    /// hidden ones. The flag says whether the column is shown.
+    fn column_rows(&self) -> Vec<(Column, bool)> {
+        let hidden = Column::ALL
+            .into_iter()
+            .filter(|c| !self.prefs.columns.contains(c));
+        self.prefs
+            .columns
+            .iter()
+            .map(|c| (*c, true))
+            .chain(hidden.map(|c| (c, false)))
+            .collect()
+    }
+
+    /// Shows or hides the column under the cursor. A newly shown column goes
+    /// last; the final remaining column can't be hidden.
+    fn toggle_column(&mut self) {
+        let Some(cursor) = self.column_cursor else {
+            return;
+        };
+        let (column, shown) = self.column_rows()[cursor];
+        if shown {
+            if self.prefs.columns.len() == 1 {
+                return;
+            }
+            self.prefs.columns.retain(|c| *c != column);
+        } else {
+            self.prefs.columns.push(column);
+        }
+        // Keep the cursor on the column that was toggled
+        self.column_cursor = self.column_rows().iter().position(|(c, _)| *c == column);
+    }
+
+    /// Moves the shown column under the cursor `delta` places left or right.
+    fn move_column(&mut self, delta: isize) {
+        let Some(cursor) = self.column_cursor else {
+            return;
+        };
+        if cursor >= self.prefs.columns.len() {
+            return;
+        }
+        let target = cursor as isize + delta;
+        if target < 0 || target as usize >= self.prefs.columns.len() {
+            return;
+        }
+        self.prefs.columns.swap(cursor, target as usize);
+        self.column_cursor = Some(target as usize);
+    }
+
+    /// Handles a key in the column popup; closing it saves the layout.
+    fn column_popup_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        let Some(cursor) = self.column_cursor else {
+            return;
+        };
+        let last = Column::ALL.len() - 1;
+        let shift = modifiers.contains(KeyModifiers::SHIFT);
+        match code {
+            KeyCode::Up | KeyCode::Char('K') if shift => self.move_column(-1),
+            KeyCode::Down | KeyCode::Char('J') if shift => self.move_column(1),
+            KeyCode::Up | KeyCode::Char('k') => self.column_cursor = Some(cursor.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.column_cursor = Some((cursor + 1).min(last)),
+            KeyCode::Char(' ') => self.toggle_column(),
+            KeyCode::Char('d') => {
+                self.prefs.columns = DEFAULT_COLUMNS.to_vec();
+                self.column_cursor = Some(0);
+            }
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('c') => {
+                self.column_cursor = None;
+                self.prefs.save();
+            }
+            _ => {}
+        }
+    }
+
+    fn selected_process(&self) -> Option<&ProcessInfo> {
+        self.selected.and_then(|i| self.processes.get(i))
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.processes.is_empty() {
+            return;
+        }
+        self.selection_moved = true;
+        let current = self.selected.unwrap_or(0) as isize;
+        let last = self.processes.len() as isize - 1;
+        self.selected = Some((current + delta).clamp(0, last) as usize);
+    }
+
+    /// Moves the selection by one page of visible rows.
+    fn page(&mut self, pages: isize) {
+        self.move_selection(pages * self.page_rows.max(1) as isize);
+    }
+
+    /// Sets how many rows fit and scrolls so the selected row stays visible.
+    fn fit_rows(&mut self, available: usize) {
+        self.page_rows = self
+            .row_limit
+            .map_or(available, |limit| limit.min(available))
+            .max(1);
+        let max_offset = self.processes.len().saturating_sub(self.page_rows);
+        if let Some(sel) = self.selected {
+            if sel < self.scroll_offset {
+                self.scroll_offset = sel;
+            } else if sel >= self.scroll_offset + self.page_rows {
+                self.scroll_offset = sel + 1 - self.page_rows;
+            }
+        }
+        self.scroll_offset = self.scroll_offset.min(max_offset);
+    }
+
+    /// Whether quitting now would lose a carefully chosen selection or watchlist.
+    fn quit_needs_confirmation(&self) -> bool {
+        self.confirm_quit
+            && ((self.selection_moved && self.selected_process().is_some())
+                || !self.watchlist.is_empty())
+    }
+
+    /// Handles 'q'/'Esc': quits at once, or opens the confirmation dialog.
+    /// Returns `true` when the app should exit.
+    fn request_quit(&mut self) -> bool {
+        if self.quit_needs_confirmation() {
+            self.quit_prompt = true;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Shows or hides the banner and remembers the choice.
+    fn toggle_banner(&mut self) {
+        self.prefs.show_banner = !self.prefs.show_banner;
+        self.prefs.save();
+    }
+
+    /// Switches the memory columns to the next unit and remembers the choice.
+    fn cycle_memory_unit(&mut self) {
+        self.prefs.memory_unit = self.prefs.memory_unit.next();
+        self.prefs.save();
+    }
+
+    /// Switches between all processes and only the current user's, and
+    /// re-reads the list so the change shows immediately.
+    fn toggle_only_mine(&mut self) {
+        self.only_mine = !self.only_mine;
+        self.refresh();
+    }
+
+    /// Opens the signal confirmation dialog for the selected process.
+    fn open_signal_prompt(&mut self) {
+        self.signal_prompt = self.selected_process().cloned();
+    }
+
+    /// Handles a key while the signal dialog is open: `t` sends SIGTERM,
+    /// `K` sends SIGKILL, and `n`/Esc cancel.
+    fn signal_prompt_key(&mut self, code: KeyCode) {
+        let signal = match code {
+            KeyCode::Char('t') | KeyCode::Char('T') => Signal::Term,
+            KeyCode::Char('K') => Signal::Kill,
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                self.signal_prompt = None;
+                return;
+            }
+            _ => return,
+        };
+        let Some(target) = self.signal_prompt.take() else {
+            return;
+        };
+        let outcome = send_signal(&target, signal);
+        self.signal_status = Some((outcome, Instant::now()));
+        // Pick up the exit (if any) right away
+        self.refresh();
+    }
+
+    /// Pins the selected process to the watchlist, or unpins it if already there.
+    fn toggle_watch(&mut self) {
+        let Some(process) = self.selected_process().cloned() else {
+            return;
+        };
+        if let Some(pos) = self.watchlist.iter().position(|p| *p == process.pid) {
+            self.watchlist.remove(pos);
+            self.watched.retain(|p| p.pid != process.pid);
+        } else {
+            self.watchlist.push(process.pid);
+            self.watched.push(process);
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // RAII Guard for Raw Mode
 ////////////////////////////////////////////////////////////////////////////////
@@ -133,8 +1048,9 @@ async fn main() -> Result<()> {
     let mut terminal = setup_terminal().context("Failed to create terminal")?;
     clear_screen(&mut terminal).context("Failed to clear terminal")?;
 
-    // 4) Draw welcome screen (Ratatui banner, instructions)
-    draw_welcome_screen(&mut terminal).context("Failed to draw welcome screen")?;
+    // 4) Draw welcome screen (Ratatui banner, machine summary, instructions)
+    let machine = MachineSummary::read();
+    draw_welcome_screen(&mut terminal, &machine).context("Failed to draw welcome screen")?;
 
     // 5) Temporarily drop raw mode so the user can press Enter to continue
     drop(_raw_guard);
@@ -201,9 +1117,101 @@ fn clear_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> R
 // "Welcome" TUI Screen
 ////////////////////////////////////////////////////////////////////////////////
 
-/// Draws a Ratatui welcome banner with steps to continue.
-/// Shown once before the user presses Enter to proceed.
-fn draw_welcome_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> Result<()> {
+/// What the welcome screen tells the user about the machine. Each field is
+/// read once at startup and is `None` when it isn't available.
+struct MachineSummary {
+    hostname: Option<String>,
+    kernel: Option<String>,
+    cpu_model: Option<String>,
+    cores: Option<usize>,
+    total_mem_kb: Option<u64>,
+    processes: Option<usize>,
+}
+
+impl MachineSummary {
+    #[cfg(target_os = "linux")]
+    fn read() -> Self {
+        let read_trimmed = |path: &str| {
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        let cpu_model = std::fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|info| {
+                info.lines()
+                    .filter_map(|l| l.split_once(':'))
+                    .find(|(key, _)| key.trim() == "model name")
+                    .map(|(_, value)| value.trim().to_string())
+            });
+        let processes = std::fs::read_dir("/proc").ok().map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.file_name().to_string_lossy().parse::<u32>().is_ok())
+                .count()
+        });
+        Self {
+            hostname: read_trimmed("/proc/sys/kernel/hostname"),
+            kernel: read_trimmed("/proc/sys/kernel/osrelease"),
+            cpu_model,
+            cores: std::thread::available_parallelism().ok().map(|n| n.get()),
+            total_mem_kb: read_memory_kb().ok().map(|(_, total)| total),
+            processes,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn read() -> Self {
+        Self {
+            hostname: None,
+            kernel: None,
+            cpu_model: None,
+            cores: std::thread::available_parallelism().ok().map(|n| n.get()),
+            total_mem_kb: None,
+            processes: None,
+        }
+    }
+
+    /// `label: value` rows for the welcome block, with `?` for anything unknown.
+    fn lines(&self) -> Vec<Line<'static>> {
+        let unknown = || "?".to_string();
+        let cpu = match (&self.cpu_model, self.cores) {
+            (Some(model), Some(cores)) => format!("{model} ({cores} cores)"),
+            (Some(model), None) => model.clone(),
+            (None, Some(cores)) => format!("{cores} cores"),
+            (None, None) => unknown(),
+        };
+        let rows = [
+            ("Host", self.hostname.clone().unwrap_or_else(unknown)),
+            ("Kernel", self.kernel.clone().unwrap_or_else(unknown)),
+            ("CPU", cpu),
+            (
+                "Memory",
+                self.total_mem_kb.map_or_else(unknown, human_readable_mem),
+            ),
+            (
+                "Processes",
+                self.processes.map_or_else(unknown, |n| n.to_string()),
+            ),
+        ];
+        rows.into_iter()
+            .map(|(label, value)| {
+                Line::from(vec![
+                    Span::styled(format!("{label}: "), Style::default().fg(Color::Cyan)),
+                    Span::raw(value),
+                ])
+            })
+            .collect()
+    }
+}
+
+/// Draws a Ratatui welcome banner with a summary of the machine and steps
+/// to continue. Shown once before the user presses Enter to proceed.
+fn draw_welcome_screen(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    machine: &MachineSummary,
+) -> Result<()> {
     terminal.draw(|frame| {
         let screen = frame.area();
 
@@ -219,15 +1227,18 @@ fn draw_welcome_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>
         draw_banner(frame, chunks[0]);
 
         // (B) Center instructions in chunk 1
-        let centered = centered_rect(60, 40, chunks[1]);
+        let centered = centered_rect(70, 60, chunks[1]);
 
-        // For the text, we can either use a Paragraph or a List.
-        // We'll just do a short welcome paragraph:
-        let lines = vec![
+        // A short welcome, what we're running on, then how to continue:
+        let mut lines = vec![
             Line::from(Span::raw("Welcome to rust-top!")),
             Line::from(""),
-            Line::from(Span::raw("Press Enter to launch the Task Manager...")),
         ];
+        lines.extend(machine.lines());
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::raw(
+            "Press Enter to launch the Task Manager...",
+        )));
 
         let block = Block::default()
             .title(" Welcome ")
@@ -303,102 +1314,21 @@ async fn run_task_manager_tui(
     args: &CliArgs,
 ) -> Result<()> {
     let mut refresh_interval = interval(Duration::from_millis(args.refresh_ms));
-    let mut cpu_tracker = CpuTracker::default();
+    let mut app = App {
+        row_limit: args.rows,
+        threshold_log: ThresholdLog::from_args(args),
+        metrics: MetricsRecorder::from_args(args),
+        prefs: Preferences::load(),
+        confirm_quit: !args.no_confirm_quit,
+        ..App::default()
+    };
 
     loop {
         tokio::select! {
             // On interval tick, gather process info and redraw
             _ = refresh_interval.tick() => {
-                #[cfg(target_os = "linux")]
-                let total_jiffies_now = read_total_jiffies().unwrap_or(0);
-                #[cfg(not(target_os = "linux"))]
-                let total_jiffies_now = 0;
-
-                #[cfg(target_os = "linux")]
-                let mut processes = read_process_list(&mut cpu_tracker, total_jiffies_now).unwrap_or_default();
-                #[cfg(not(target_os = "linux"))]
-                let mut processes = Vec::new();
-
-                // Sort by memory usage descending
-                processes.sort_by(|a, b| b.memory_kb.cmp(&a.memory_kb));
-
-                // Redraw TUI
-                terminal.draw(|frame| {
-                    let screen = frame.area();
-
-                    // We create three main chunks:
-                    // 1) a small chunk for the top banner
-                    // 2) a 1-line blank spacer
-                    // 3) the rest for the process table
-                    let layout = Layout::default()
-                        .direction(Direction::Vertical)
-                        .margin(1)
-                        .constraints([
-                            Constraint::Length(3),  // banner area
-                            Constraint::Length(1),  // blank spacer
-                            Constraint::Min(5),     // table area
-                        ])
-                        .split(screen);
-
-                    // (1) A top banner line
-                    let banner_lines = vec![
-                        Line::from(Span::styled(
-                            "rust-top (press 'q', 'Esc', or Ctrl-C to quit)",
-                            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-                        ))
-                    ];
-                    let banner_par = Paragraph::new(banner_lines)
-                        .alignment(Alignment::Left)
-                        .block(Block::default().borders(Borders::NONE));
-                    frame.render_widget(banner_par, layout[0]);
-
-                    // (2) Blank spacer
-                    let blank_par = Paragraph::new(Line::from(""));
-                    frame.render_widget(blank_par, layout[1]);
-
-                    // (3) Process Table
-                    let table_block = Block::default()
-                        .borders(Borders::ALL)
-                        .title(" Process List ");
-
-                    let header = Row::new(vec![
-                        Span::styled("PID", Style::default().fg(Color::Yellow)),
-                        Span::styled("Name", Style::default().fg(Color::Yellow)),
-                        Span::styled("State", Style::default().fg(Color::Yellow)),
-                        Span::styled("PPID", Style::default().fg(Color::Yellow)),
-                        Span::styled("CPU%", Style::default().fg(Color::Yellow)),
-                        Span::styled("Memory", Style::default().fg(Color::Yellow)),
-                    ]);
-
-                    let rows: Vec<Row> = processes.into_iter().map(|p| {
-                        let mem_str = human_readable_mem(p.memory_kb);
-                        Row::new(vec![
-                            Span::raw(p.pid.to_string()),
-                            Span::raw(p.name),
-                            Span::raw(p.state),
-                            Span::raw(p.ppid.to_string()),
-                            Span::raw(format!("{:.1}", p.cpu_percent)),
-                            Span::raw(mem_str),
-                        ])
-                    }).collect();
-
-                    let table = Table::new(
-                        rows,
-                        &[
-                            Constraint::Length(6),   // PID
-                            Constraint::Length(20),  // Name
-                            Constraint::Length(6),   // State
-                            Constraint::Length(6),   // PPID
-                            Constraint::Length(6),   // CPU%
-                            Constraint::Length(12),  // Memory
-                        ],
-                    )
-                    .header(header)
-                    .block(table_block)
-                    .column_spacing(1);
-
-                    frame.render_widget(table, layout[2]);
-                })?;
+                app.refresh();
+                terminal.draw(|frame| draw_task_manager(frame, &mut app))?;
             },
 
             // Check for keyboard input with short timeout
@@ -406,11 +1336,7 @@ async fn run_task_manager_tui(
                 // Poll for an event (non-async, hence spawn_blocking)
                 if poll(Duration::from_millis(100)).unwrap_or(false) {
                     // If an event is available, read it
-                    if let Ok(ev) = read() {
-                        Some(ev)
-                    } else {
-                        None
-                    }
+                    read().ok()
                 } else {
                     None
                 }
@@ -418,15 +1344,65 @@ async fn run_task_manager_tui(
                 let maybe_event = event_result?;
                 // ** Fixed: Replace nested match with if let **
                 if let Some(Event::Key(KeyEvent { code, modifiers, .. })) = maybe_event {
+                    // The quit dialog: y/q/Enter quits, n/Esc stays
+                    if app.quit_prompt {
+                        match code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Char('q')
+                            | KeyCode::Char('Q') | KeyCode::Enter => break,
+                            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => break,
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                                app.quit_prompt = false;
+                            }
+                            _ => {}
+                        }
+                    }
+                    // The detail popup takes all keys except Ctrl-C until dismissed
+                    else if app.detail.is_some() {
+                        if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
+                            break;
+                        }
+                        if matches!(code, KeyCode::Esc | KeyCode::Enter) {
+                            app.detail = None;
+                        }
+                    }
+                    // So does the signal confirmation dialog
+                    else if app.signal_prompt.is_some() {
+                        if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
+                            break;
+                        }
+                        app.signal_prompt_key(code);
+                    }
+                    // The column popup likewise keeps the keys while open
+                    else if app.column_cursor.is_some() {
+                        app.column_popup_key(code, modifiers);
+                    }
                     // Normal keys
-                    if modifiers.is_empty() {
+                    else if modifiers.is_empty() {
                         match code {
-                            KeyCode::Char('q') | KeyCode::Esc => break,
+                            // Asks first when a selection or watchlist would be lost
+                            KeyCode::Char('q') | KeyCode::Esc if app.request_quit() => break,
+                            KeyCode::Down | KeyCode::Char('j') => app.move_selection(1),
+                            KeyCode::Up | KeyCode::Char('k') => app.move_selection(-1),
+                            KeyCode::PageDown => app.page(1),
+                            KeyCode::PageUp => app.page(-1),
+                            KeyCode::Home => app.move_selection(isize::MIN / 2),
+                            KeyCode::End => app.move_selection(isize::MAX / 2),
+                            KeyCode::Char('w') => app.toggle_watch(),
+                            KeyCode::Char('v') => app.show_memory_details = !app.show_memory_details,
+                            KeyCode::Char('m') => app.cycle_memory_unit(),
+                            KeyCode::Char('r') => app.metrics.toggle(),
+                            KeyCode::Char('b') => app.toggle_banner(),
+                            KeyCode::Char('u') => app.toggle_only_mine(),
+                            KeyCode::Char('c') => app.column_cursor = Some(0),
+                            KeyCode::Char('x') => app.open_signal_prompt(),
+                            KeyCode::Enter => app.open_detail(),
                             _ => {}
                         }
                     }
-                    // SHIFT+Q or Ctrl-C
-                    if modifiers.contains(KeyModifiers::SHIFT)
+                    // SHIFT+Q or Ctrl-C force-quit without confirmation
+                    if app.detail.is_none() && app.column_cursor.is_none()
+                        && app.signal_prompt.is_none()
+                        && modifiers.contains(KeyModifiers::SHIFT)
                         && code == KeyCode::Char('Q') {
                         break;
                     }
@@ -434,6 +1410,7 @@ async fn run_task_manager_tui(
                         && code == KeyCode::Char('c') {
                         break;
                     }
+                    terminal.draw(|frame| draw_task_manager(frame, &mut app))?;
                 }
             }
         }
@@ -442,26 +1419,749 @@ async fn run_task_manager_tui(
     Ok(())
 }
 
+/// Draws the full task manager screen: banner, optional watchlist, and process table.
+fn draw_task_manager(frame: &mut Frame, app: &mut App) {
+    let screen = frame.area();
+    let columns = app.visible_columns();
+    let widths: Vec<Constraint> = columns.iter().map(|c| c.width()).collect();
+
+    // Watchlist gets one row per pinned PID plus header and borders; hidden when empty
+    let watch_height = if app.watchlist.is_empty() {
+        0
+    } else {
+        app.watchlist.len() as u16 + 3
+    };
+
+    // Battery gauge plus borders; hidden on machines without a battery
+    let battery_height = if app.battery.is_some() { 3 } else { 0 };
+
+    // We create six main chunks:
+    // 1) a small chunk for the top banner
+    // 2) a 1-line blank spacer
+    // 3) the network rates panel (zero-height when no interfaces are known)
+    // 4) the battery gauge (zero-height without a battery)
+    // 5) the pinned-process watchlist (may be zero-height)
+    // 6) the rest for the process table
+    // With the banner collapsed, (1) and (2) and the outer margin shrink to nothing.
+    let (margin, banner_height, spacer_height) = if app.prefs.show_banner {
+        (1, 4, 1)
+    } else {
+        (0, 0, 0)
+    };
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(margin)
+        .constraints([
+            Constraint::Length(banner_height),              // banner area
+            Constraint::Length(spacer_height),              // blank spacer
+            Constraint::Length(app.network.panel_height()), // network area
+            Constraint::Length(battery_height),             // battery area
+            Constraint::Length(watch_height),               // watchlist area
+            Constraint::Min(5),                             // table area
+        ])
+        .split(screen);
+
+    // (1) Top banner: title, uptime/load, process states, key hints
+    let mut title_spans = vec![Span::styled(
+        "rust-top (press 'q', 'Esc', or Ctrl-C to quit)",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if let Some(indicator) = app.threshold_log.as_ref().and_then(|log| log.indicator()) {
+        title_spans.push(indicator);
+    }
+    if let Some(indicator) = app.metrics.indicator() {
+        title_spans.push(indicator);
+    }
+    let banner_lines = vec![
+        Line::from(title_spans),
+        Line::from(Span::styled(
+            system_summary(app.uptime_secs, app.load_avg),
+            Style::default().fg(Color::White),
+        )),
+        state_summary(&app.processes),
+        Line::from(Span::styled(
+            "[j/k/arrows, PgUp/PgDn, Home/End: select] [Enter: details] [w: pin/unpin to watchlist] [v: VSZ/shared columns] [m: memory unit] [r: record metrics] [u: only my processes] [c: columns] [x: kill/signal] [b: hide banner]",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let banner_par = Paragraph::new(banner_lines)
+        .alignment(Alignment::Left)
+        .block(Block::default().borders(Borders::NONE));
+    frame.render_widget(banner_par, layout[0]);
+
+    // (2) Blank spacer
+    let blank_par = Paragraph::new(Line::from(""));
+    frame.render_widget(blank_par, layout[1]);
+
+    // (3) Network rates per interface
+    if app.network.panel_height() > 0 {
+        draw_network_panel(frame, &app.network, layout[2]);
+    }
+
+    // (4) Battery charge
+    if let Some(battery) = &app.battery {
+        draw_battery_panel(frame, battery, layout[3]);
+    }
+
+    // (5) Watchlist of pinned processes, in pin order
+    if !app.watchlist.is_empty() {
+        let rows: Vec<Row> = app
+            .watched
+            .iter()
+            .map(|p| process_row(p, &columns, app.prefs.memory_unit))
+            .collect();
+        let watch_table = Table::new(rows, widths.clone())
+            .header(process_header(&columns, app.prefs.memory_unit))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Watchlist ")
+                    .border_style(Style::default().fg(Color::Green)),
+            )
+            .column_spacing(1);
+        frame.render_widget(watch_table, layout[4]);
+    }
+
+    // (6) Process Table, showing one page of rows around the selection
+    // (the table area loses two lines to borders and one to the header)
+    app.fit_rows(layout[5].height.saturating_sub(3) as usize);
+    let start = app.scroll_offset;
+    let end = (start + app.page_rows).min(app.processes.len());
+    let list_name = if app.only_mine {
+        "My Processes"
+    } else {
+        "Process List"
+    };
+    let title = if app.processes.is_empty() {
+        format!(" {list_name} ")
+    } else {
+        format!(
+            " {list_name} (showing {}–{} of {}) ",
+            start + 1,
+            end,
+            app.processes.len()
+        )
+    };
+    let mut table_block = Block::default().borders(Borders::ALL).title(title);
+    if !app.prefs.show_banner {
+        // Keep the recording state visible and say how to get the banner back
+        let hint = if app.metrics.active {
+            " ● REC | b: show banner "
+        } else {
+            " b: show banner "
+        };
+        table_block = table_block.title(Line::from(hint).right_aligned());
+    }
+    if let Some((outcome, _)) = app
+        .signal_status
+        .as_ref()
+        .filter(|(_, at)| at.elapsed() < SIGNAL_STATUS_DURATION)
+    {
+        table_block = table_block.title_bottom(Line::from(Span::styled(
+            format!(" {} ", outcome.message),
+            Style::default()
+                .fg(outcome.kind.color())
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    let rows: Vec<Row> = app.processes[start..end]
+        .iter()
+        .map(|p| {
+            let row = process_row(p, &columns, app.prefs.memory_unit);
+            if app.watchlist.contains(&p.pid) {
+                row.style(Style::default().fg(Color::Green))
+            } else {
+                row
+            }
+        })
+        .collect();
+
+    let table = Table::new(rows, widths)
+        .header(process_header(&columns, app.prefs.memory_unit))
+        .block(table_block)
+        .column_spacing(1)
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+    let mut table_state =
+        TableState::default().with_selected(app.selected.map(|i| i.saturating_sub(start)));
+    frame.render_stateful_widget(table, layout[4], &mut table_state);
+
+    if let Some(detail) = &app.detail {
+        draw_process_detail(frame, detail, screen);
+    }
+    if let Some(cursor) = app.column_cursor {
+        draw_column_popup(frame, &app.column_rows(), cursor, screen);
+    }
+    if let Some(target) = &app.signal_prompt {
+        draw_signal_prompt(frame, target, screen);
+    }
+    if app.quit_prompt {
+        draw_quit_prompt(frame, app, screen);
+    }
+}
+
+/// Dialog confirming which signal to send to a process.
+fn draw_signal_prompt(frame: &mut Frame, target: &ProcessInfo, screen: Rect) {
+    let area = centered_rect(50, 30, screen);
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Send a signal?",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(format!(
+            "{} (PID {}, user {})",
+            target.name, target.pid, target.user
+        )),
+    ];
+    if target.uid.is_some() && target.uid != current_uid() && current_uid() != Some(0) {
+        lines.push(Line::from(Span::styled(
+            "Owned by another user: this needs root and will likely be refused.",
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "[t: SIGTERM, ask to exit] [Shift+K: SIGKILL, force] [n/Esc: cancel]",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let dialog = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Kill Process ")
+                .border_style(Style::default().fg(Color::Red)),
+        );
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(dialog, area);
+}
+
+/// Receive/transmit rate and gauge per interface, with the totals in the title.
+fn draw_network_panel(frame: &mut Frame, network: &NetworkTracker, area: Rect) {
+    let (total_rx, total_tx) = network.totals();
+    let title = format!(
+        " Network (↓ {} ↑ {}) ",
+        format_rate(total_rx),
+        format_rate(total_tx)
+    );
+
+    let mut lines: Vec<Line> = network
+        .rates
+        .iter()
+        .take(NETWORK_PANEL_ROWS)
+        .map(|rate| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<12}", rate.name),
+                    Style::default().fg(Color::White),
+                ),
+                Span::raw(format!("↓ {:>12} ", format_rate(rate.rx_rate))),
+                Span::styled(
+                    rate_gauge(rate.rx_rate, network.peak_rate),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::raw(format!("  ↑ {:>12} ", format_rate(rate.tx_rate))),
+                Span::styled(
+                    rate_gauge(rate.tx_rate, network.peak_rate),
+                    Style::default().fg(Color::Blue),
+                ),
+            ])
+        })
+        .collect();
+    if network.rates.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "measuring...",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else if network.rates.len() > NETWORK_PANEL_ROWS {
+        lines.push(Line::from(Span::styled(
+            format!("+{} more", network.rates.len() - NETWORK_PANEL_ROWS),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let panel = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(panel, area);
+}
+
+fn draw_battery_panel(frame: &mut Frame, battery: &BatteryStatus, area: Rect) {
+    let color = if battery.state == "Charging" || battery.state == "Full" {
+        Color::Green
+    } else if battery.percent <= 20.0 {
+        Color::Red
+    } else if battery.percent <= 50.0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+    let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::ALL).title(" Battery "))
+        .gauge_style(Style::default().fg(color).bg(Color::Black))
+        .ratio((battery.percent / 100.0).clamp(0.0, 1.0))
+        .label(battery.label());
+    frame.render_widget(gauge, area);
+}
+
+/// Text bar for `rate` relative to the busiest rate seen so far.
+fn rate_gauge(rate: f64, peak: f64) -> String {
+    let filled = if peak > 0.0 {
+        ((rate / peak) * NETWORK_GAUGE_WIDTH as f64).ceil() as usize
+    } else {
+        0
+    }
+    .min(NETWORK_GAUGE_WIDTH);
+    format!(
+        "{}{}",
+        "█".repeat(filled),
+        "░".repeat(NETWORK_GAUGE_WIDTH - filled)
+    )
+}
+
+/// Small dialog asking whether to quit, naming what would be lost.
+fn draw_quit_prompt(frame: &mut Frame, app: &App, screen: Rect) {
+    let area = centered_rect(50, 25, screen);
+    let mut lines = vec![Line::from(Span::styled(
+        "Quit rust-top?",
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    if let Some(p) = app.selected_process().filter(|_| app.selection_moved) {
+        lines.push(Line::from(format!("Selected: {} (PID {})", p.name, p.pid)));
+    }
+    if !app.watchlist.is_empty() {
+        lines.push(Line::from(format!(
+            "Watchlist: {} pinned process(es)",
+            app.watchlist.len()
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "[y/Enter: quit] [n/Esc: stay] [Shift+Q: always quits]",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let dialog = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Confirm Quit ")
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(dialog, area);
+}
+
+/// Checklist of the table columns in display order, for showing, hiding, and
+/// reordering them.
+fn draw_column_popup(frame: &mut Frame, rows: &[(Column, bool)], cursor: usize, screen: Rect) {
+    let area = centered_rect(40, 60, screen);
+    let mut lines: Vec<Line> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, (column, shown))| {
+            let mark = if *shown { "[x]" } else { "[ ]" };
+            let mut style = if *shown {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            if i == cursor {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Line::from(Span::styled(
+                format!(" {mark} {:<8}", column.title()),
+                style,
+            ))
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "[Space: show/hide] [Shift+Up/Down or J/K: move] [d: defaults] [Esc/Enter: done]",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Columns ")
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Popup with the full `/proc` picture of one process, over the table.
+fn draw_process_detail(frame: &mut Frame, detail: &ProcessDetail, screen: Rect) {
+    let area = centered_rect(70, 70, screen);
+    let info = &detail.info;
+    let kb = |value: Option<u64>| value.map_or_else(|| "n/a".to_string(), human_readable_mem);
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{label:<14}"), Style::default().fg(Color::Yellow)),
+            Span::raw(value),
+        ])
+    };
+
+    let mut lines = vec![
+        field("Command line", detail.command_line.clone()),
+        field("State", info.state.clone()),
+        field("Parent PID", info.ppid.to_string()),
+        field("Working dir", detail.cwd.clone()),
+        field("Environment", detail.environment.clone()),
+        field("Open fds", detail.open_fds.clone()),
+        field(
+            "Threads",
+            detail
+                .threads
+                .map_or_else(|| "n/a".to_string(), |t| t.to_string()),
+        ),
+        field(
+            "Started",
+            detail.started.clone().unwrap_or_else(|| "n/a".to_string()),
+        ),
+        Line::from(""),
+        field("CPU", format!("{:.1}%", info.cpu_percent)),
+        field("  user time", format!("{:.2}s", detail.user_cpu_secs)),
+        field("  system time", format!("{:.2}s", detail.system_cpu_secs)),
+        field("Memory (RSS)", human_readable_mem(info.memory_kb)),
+        field("  anonymous", kb(detail.rss_anon_kb)),
+        field("  file-backed", kb(detail.rss_file_kb)),
+        field("  shared mem", kb(detail.rss_shmem_kb)),
+        field("  swapped out", kb(detail.swap_kb)),
+        field("Virtual (VSZ)", human_readable_mem(info.vsz_kb)),
+    ];
+    if detail.exited {
+        lines.insert(
+            0,
+            Line::from(Span::styled(
+                "Process has exited; showing its last snapshot.",
+                Style::default().fg(Color::Red),
+            )),
+        );
+    }
+
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} (PID {}) - Esc to close ", info.name, info.pid))
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Columns shown until the user configures their own.
+const DEFAULT_COLUMNS: [Column; 8] = [
+    Column::Pid,
+    Column::User,
+    Column::Name,
+    Column::State,
+    Column::Ppid,
+    Column::Cpu,
+    Column::CpuTime,
+    Column::Memory,
+];
+
+/// A column in the process tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Pid,
+    User,
+    Name,
+    State,
+    Ppid,
+    Cpu,
+    CpuTime,
+    Memory,
+    Vsz,
+    Shared,
+}
+
+impl Column {
+    const ALL: [Column; 10] = [
+        Column::Pid,
+        Column::User,
+        Column::Name,
+        Column::State,
+        Column::Ppid,
+        Column::Cpu,
+        Column::CpuTime,
+        Column::Memory,
+        Column::Vsz,
+        Column::Shared,
+    ];
+
+    /// Name used for the column in the preferences file.
+    fn key(self) -> &'static str {
+        match self {
+            Column::Pid => "pid",
+            Column::User => "user",
+            Column::Name => "name",
+            Column::State => "state",
+            Column::Ppid => "ppid",
+            Column::Cpu => "cpu",
+            Column::CpuTime => "time",
+            Column::Memory => "memory",
+            Column::Vsz => "vsz",
+            Column::Shared => "shared",
+        }
+    }
+
+    fn parse(key: &str) -> Option<Self> {
+        Column::ALL.into_iter().find(|c| c.key() == key)
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Column::Pid => "PID",
+            Column::User => "User",
+            Column::Name => "Name",
+            Column::State => "State",
+            Column::Ppid => "PPID",
+            Column::Cpu => "CPU%",
+            Column::CpuTime => "CPU Time",
+            Column::Memory => "Memory",
+            Column::Vsz => "VSZ",
+            Column::Shared => "Shared",
+        }
+    }
+
+    fn width(self) -> Constraint {
+        match self {
+            Column::Pid | Column::State | Column::Ppid | Column::Cpu => Constraint::Length(6),
+            Column::User | Column::CpuTime => Constraint::Length(10),
+            Column::Name => Constraint::Length(20),
+            Column::Memory | Column::Vsz | Column::Shared => Constraint::Length(12),
+        }
+    }
+
+    /// Header text; memory columns name the unit when it is fixed.
+    fn header(self, unit: MemoryUnit) -> String {
+        match self {
+            Column::Memory | Column::Vsz | Column::Shared if unit != MemoryUnit::Auto => {
+                format!("{} ({})", self.title(), unit.label())
+            }
+            _ => self.title().to_string(),
+        }
+    }
+
+    fn cell(self, p: &ProcessInfo, unit: MemoryUnit) -> String {
+        match self {
+            Column::Pid => p.pid.to_string(),
+            Column::User => p.user.clone(),
+            Column::Name => p.name.clone(),
+            Column::State => p.state.clone(),
+            Column::Ppid => p.ppid.to_string(),
+            Column::Cpu => format!("{:.1}", p.cpu_percent),
+            Column::CpuTime => format_cpu_time(p.cpu_time_secs),
+            Column::Memory => unit.format(p.memory_kb),
+            Column::Vsz => unit.format(p.vsz_kb),
+            Column::Shared => unit.format(p.shared_kb),
+        }
+    }
+}
+
+/// Header row for process tables.
+fn process_header(columns: &[Column], unit: MemoryUnit) -> Row<'static> {
+    Row::new(
+        columns
+            .iter()
+            .map(|c| Span::styled(c.header(unit), Style::default().fg(Color::Yellow)))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Builds a table row for a single process.
+fn process_row(p: &ProcessInfo, columns: &[Column], unit: MemoryUnit) -> Row<'static> {
+    Row::new(
+        columns
+            .iter()
+            .map(|c| Span::raw(c.cell(p, unit)))
+            .collect::<Vec<_>>(),
+    )
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Linux-Specific: Reading /proc for CPU & Process Info
 ////////////////////////////////////////////////////////////////////////////////
 
 #[cfg(target_os = "linux")]
 fn read_total_jiffies() -> Result<u64> {
+    read_cpu_jiffies().map(|(total, _)| total)
+}
+
+/// Total and idle (idle + iowait) jiffies from the aggregate `cpu` line of `/proc/stat`.
+#[cfg(target_os = "linux")]
+fn read_cpu_jiffies() -> Result<(u64, u64)> {
     let contents = std::fs::read_to_string("/proc/stat")?;
     let line = contents
         .lines()
         .find(|l| l.starts_with("cpu "))
         .ok_or_else(|| anyhow!("Could not find 'cpu ' line in /proc/stat"))?;
 
-    let parts: Vec<&str> = line.split_whitespace().skip(1).collect();
-    let mut total: u64 = 0;
-    for val in parts {
-        if let Ok(num) = val.parse::<u64>() {
-            total += num;
+    let values: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    let idle = values.get(3).copied().unwrap_or(0) + values.get(4).copied().unwrap_or(0);
+    Ok((values.iter().sum(), idle))
+}
+
+/// Used and total memory in KiB from `/proc/meminfo` (used = total - available).
+#[cfg(target_os = "linux")]
+fn read_memory_kb() -> Result<(u64, u64)> {
+    let contents = std::fs::read_to_string("/proc/meminfo")?;
+    let field = |name: &str| -> Result<u64> {
+        contents
+            .lines()
+            .find_map(|l| l.strip_prefix(name))
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| anyhow!("Missing {name} in /proc/meminfo"))
+    };
+    let total = field("MemTotal:")?;
+    let available = field("MemAvailable:")?;
+    Ok((total.saturating_sub(available), total))
+}
+
+/// Received and transmitted byte counters per interface from `/proc/net/dev`,
+/// leaving out the loopback interface.
+#[cfg(target_os = "linux")]
+fn read_net_dev() -> Result<Vec<(String, u64, u64)>> {
+    let contents = std::fs::read_to_string("/proc/net/dev")?;
+    let mut counters = Vec::new();
+    // The first two lines are column headers
+    for line in contents.lines().skip(2) {
+        let Some((name, fields)) = line.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+        if name == "lo" {
+            continue;
+        }
+        // Receive bytes is the first field, transmit bytes the ninth
+        let fields: Vec<u64> = fields
+            .split_whitespace()
+            .filter_map(|v| v.parse().ok())
+            .collect();
+        if let (Some(rx), Some(tx)) = (fields.first(), fields.get(8)) {
+            counters.push((name.to_string(), *rx, *tx));
+        }
+    }
+    Ok(counters)
+}
+
+/// Reads every battery under `/sys/class/power_supply` and combines them into
+/// one status. Returns `None` when the machine has no battery.
+#[cfg(target_os = "linux")]
+fn read_battery() -> Option<BatteryStatus> {
+    let read = |dir: &std::path::Path, name: &str| {
+        std::fs::read_to_string(dir.join(name))
+            .ok()
+            .map(|v| v.trim().to_string())
+    };
+    let read_num = |dir: &std::path::Path, name: &str| -> Option<f64> {
+        read(dir, name).and_then(|v| v.parse().ok())
+    };
+
+    let (mut now, mut full, mut rate) = (0.0, 0.0, 0.0);
+    let mut capacities = Vec::new();
+    let mut states = Vec::new();
+    for entry in std::fs::read_dir("/sys/class/power_supply").ok()?.flatten() {
+        let dir = entry.path();
+        // Mains adapters and peripheral batteries (mice, headsets) don't count
+        if read(&dir, "type").as_deref() != Some("Battery")
+            || read(&dir, "scope").as_deref() == Some("Device")
+        {
+            continue;
+        }
+        // Energy is reported in µWh/µW, or charge in µAh/µA; either gives hours
+        let sample = match (read_num(&dir, "energy_now"), read_num(&dir, "energy_full")) {
+            (Some(n), Some(f)) => Some((n, f, read_num(&dir, "power_now"))),
+            _ => match (read_num(&dir, "charge_now"), read_num(&dir, "charge_full")) {
+                (Some(n), Some(f)) => Some((n, f, read_num(&dir, "current_now"))),
+                _ => None,
+            },
+        };
+        if let Some((n, f, r)) = sample {
+            now += n;
+            full += f;
+            rate += r.unwrap_or(0.0).abs();
+        }
+        if let Some(capacity) = read_num(&dir, "capacity") {
+            capacities.push(capacity);
+        }
+        states.push(read(&dir, "status").unwrap_or_else(|| "Unknown".to_string()));
+    }
+    if states.is_empty() {
+        return None;
+    }
+
+    let percent = if full > 0.0 {
+        now / full * 100.0
+    } else if !capacities.is_empty() {
+        capacities.iter().sum::<f64>() / capacities.len() as f64
+    } else {
+        return None;
+    };
+    // With several batteries, any one charging or draining is the interesting state
+    let state = ["Charging", "Discharging"]
+        .iter()
+        .find(|s| states.iter().any(|state| state == *s))
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| states[0].clone());
+    let time_remaining_secs = if rate > 0.0 {
+        match state.as_str() {
+            "Discharging" => Some(now / rate),
+            "Charging" => Some((full - now).max(0.0) / rate),
+            _ => None,
         }
+        .map(|hours| (hours * 3_600.0) as u64)
+    } else {
+        None
+    };
+
+    Some(BatteryStatus {
+        percent: percent.clamp(0.0, 100.0),
+        state,
+        time_remaining_secs,
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn read_uptime_secs() -> Result<u64> {
+    let contents = std::fs::read_to_string("/proc/uptime")?;
+    let secs: f64 = contents
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| anyhow!("Empty /proc/uptime"))?
+        .parse()?;
+    Ok(secs as u64)
+}
+
+#[cfg(target_os = "linux")]
+fn read_load_avg() -> Result<[f64; 3]> {
+    let contents = std::fs::read_to_string("/proc/loadavg")?;
+    let mut fields = contents.split_whitespace();
+    let mut load = [0.0; 3];
+    for value in &mut load {
+        *value = fields
+            .next()
+            .ok_or_else(|| anyhow!("Malformed /proc/loadavg"))?
+            .parse()?;
     }
-    Ok(total)
+    Ok(load)
 }
 
 #[cfg(target_os = "linux")]
@@ -518,9 +2218,22 @@ fn parse_proc_stat(
 
     // RSS from /proc/<PID>/stat (23rd field)
     let rss: i64 = parts[23].parse().unwrap_or(0);
-    let page_size_kb = 4; // typical 4KB
+    let page_size_kb = page_size_kb();
     let memory_kb = (rss.max(0) as u64).saturating_mul(page_size_kb);
 
+    // Virtual size and shared pages from /proc/<PID>/statm (all counts in pages)
+    let (vsz_kb, shared_kb) = std::fs::read_to_string(format!("/proc/{pid}/statm"))
+        .ok()
+        .map(|statm| {
+            let pages: Vec<u64> = statm
+                .split_whitespace()
+                .map(|v| v.parse().unwrap_or(0))
+                .collect();
+            let field = |i: usize| pages.get(i).copied().unwrap_or(0) * page_size_kb;
+            (field(0), field(2))
+        })
+        .unwrap_or((0, 0));
+
     let (old_jiffies_proc, old_jiffies_total) = cpu_tracker
         .per_pid_cpu
         .get(&pid)
@@ -540,13 +2253,291 @@ fn parse_proc_stat(
         .per_pid_cpu
         .insert(pid, (proc_total, total_jiffies_now));
 
+    // Real uid is the first of the four values on the `Uid:` line
+    let uid = std::fs::read_to_string(format!("/proc/{pid}/status"))
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find_map(|l| l.strip_prefix("Uid:"))
+                .and_then(|ids| ids.split_whitespace().next())
+                .and_then(|id| id.parse().ok())
+        });
+
     Ok(ProcessInfo {
         pid,
         name,
         state,
         ppid,
         memory_kb,
+        vsz_kb,
+        shared_kb,
         cpu_percent,
+        cpu_time_secs: proc_total / clock_ticks_per_sec(),
+        uid,
+        user: String::new(),
+    })
+}
+
+/// How long the result of sending a signal stays under the process table.
+const SIGNAL_STATUS_DURATION: Duration = Duration::from_secs(8);
+
+/// Signals offered by the kill dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Signal {
+    /// Asks the process to exit; it may clean up, or ignore it
+    Term,
+    /// Ends the process immediately; it can't be caught
+    Kill,
+}
+
+impl Signal {
+    fn name(self) -> &'static str {
+        match self {
+            Signal::Term => "SIGTERM",
+            Signal::Kill => "SIGKILL",
+        }
+    }
+}
+
+/// What happened when a signal was sent, so each case gets its own message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SignalResult {
+    Sent,
+    /// ESRCH: the process had already exited
+    NoSuchProcess,
+    /// EPERM: the process belongs to another user and we aren't root
+    NotPermitted,
+    Failed,
+}
+
+impl SignalResult {
+    fn color(self) -> Color {
+        match self {
+            SignalResult::Sent => Color::Green,
+            SignalResult::NoSuchProcess => Color::Yellow,
+            SignalResult::NotPermitted | SignalResult::Failed => Color::Red,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SignalOutcome {
+    kind: SignalResult,
+    message: String,
+}
+
+/// Sends `signal` to `target` and describes the result in plain words.
+fn send_signal(target: &ProcessInfo, signal: Signal) -> SignalOutcome {
+    let who = format!("{} (PID {})", target.name, target.pid);
+    let (kind, message) = match deliver_signal(target.pid, signal) {
+        Ok(()) if signal == Signal::Term => (
+            SignalResult::Sent,
+            format!("Sent SIGTERM to {who}; it may take a moment to exit"),
+        ),
+        Ok(()) => (SignalResult::Sent, format!("Sent SIGKILL to {who}")),
+        Err(e) => match errno_result(&e) {
+            SignalResult::NoSuchProcess => (
+                SignalResult::NoSuchProcess,
+                format!("{who} no longer exists; it had already exited"),
+            ),
+            SignalResult::NotPermitted => (
+                SignalResult::NotPermitted,
+                format!(
+                    "Not permitted to signal {who} (owned by {}); run rust-top as root or as that user",
+                    target.user
+                ),
+            ),
+            _ => (
+                SignalResult::Failed,
+                format!("Failed to send {} to {who}: {e}", signal.name()),
+            ),
+        },
+    };
+    SignalOutcome { kind, message }
+}
+
+/// Maps the errno from a failed `kill` to the cases worth telling apart.
+#[cfg(target_os = "linux")]
+fn errno_result(e: &io::Error) -> SignalResult {
+    match e.raw_os_error() {
+        Some(libc::ESRCH) => SignalResult::NoSuchProcess,
+        Some(libc::EPERM) => SignalResult::NotPermitted,
+        _ => SignalResult::Failed,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn errno_result(_e: &io::Error) -> SignalResult {
+    SignalResult::Failed
+}
+
+#[cfg(target_os = "linux")]
+fn deliver_signal(pid: u32, signal: Signal) -> io::Result<()> {
+    let signal = match signal {
+        Signal::Term => libc::SIGTERM,
+        Signal::Kill => libc::SIGKILL,
+    };
+    let pid = libc::pid_t::try_from(pid).map_err(|_| io::Error::from_raw_os_error(libc::ESRCH))?;
+    // SAFETY: kill has no memory-safety preconditions; a stale or invalid pid
+    // is reported through errno.
+    if unsafe { libc::kill(pid, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn deliver_signal(_pid: u32, _signal: Signal) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "sending signals is only supported on Linux",
+    ))
+}
+
+/// Maps uids to login names from `/etc/passwd` (empty if it can't be read).
+fn read_user_names() -> HashMap<u32, String> {
+    std::fs::read_to_string("/etc/passwd")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let uid = fields.nth(1)?.parse().ok()?;
+            Some((uid, name.to_string()))
+        })
+        .collect()
+}
+
+/// The uid rust-top runs as.
+#[cfg(target_os = "linux")]
+fn current_uid() -> Option<u32> {
+    // SAFETY: getuid has no preconditions and cannot fail.
+    Some(unsafe { libc::getuid() })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn current_uid() -> Option<u32> {
+    None
+}
+
+/// Reads the detail-popup fields for `info.pid` from `/proc`.
+#[cfg(target_os = "linux")]
+fn read_process_detail(info: &ProcessInfo, uptime_secs: Option<u64>) -> Result<ProcessDetail> {
+    let pid = info.pid;
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat"))?;
+    // Fields after the parenthesised name; `comm` itself may contain spaces
+    let after_name = stat
+        .rsplit_once(')')
+        .map(|(_, rest)| rest)
+        .ok_or_else(|| anyhow!("Invalid stat format for pid: {pid}"))?;
+    let fields: Vec<u64> = after_name
+        .split_whitespace()
+        .map(|v| v.parse().unwrap_or(0))
+        .collect();
+    // Indices are shifted by the two leading fields (pid, comm)
+    let stat_field = |n: usize| fields.get(n - 2).copied().unwrap_or(0);
+    let ticks = clock_ticks_per_sec() as f64;
+
+    let started = uptime_secs.map(|uptime| {
+        let age = uptime as f64 - stat_field(21) as f64 / ticks;
+        (Local::now() - chrono::Duration::seconds(age.max(0.0) as i64))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    });
+
+    let command_line = match std::fs::read(format!("/proc/{pid}/cmdline")) {
+        Ok(raw) if !raw.is_empty() => raw
+            .split(|b| *b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(String::from_utf8_lossy)
+            .collect::<Vec<_>>()
+            .join(" "),
+        // Kernel threads have no command line
+        Ok(_) => format!("[{}]", info.name),
+        Err(e) => describe_io_error(&e),
+    };
+    let cwd = std::fs::read_link(format!("/proc/{pid}/cwd"))
+        .map_or_else(|e| describe_io_error(&e), |p| p.display().to_string());
+    let environment = std::fs::read(format!("/proc/{pid}/environ")).map_or_else(
+        |e| describe_io_error(&e),
+        |raw| {
+            let count = raw.split(|b| *b == 0).filter(|v| !v.is_empty()).count();
+            format!("{count} variables")
+        },
+    );
+    let open_fds = std::fs::read_dir(format!("/proc/{pid}/fd"))
+        .map_or_else(|e| describe_io_error(&e), |dir| dir.count().to_string());
+
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).unwrap_or_default();
+    let status_value = |key: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|v| v.split_whitespace().next()?.parse::<u64>().ok())
+    };
+
+    Ok(ProcessDetail {
+        info: info.clone(),
+        command_line,
+        cwd,
+        environment,
+        open_fds,
+        threads: status_value("Threads"),
+        started,
+        user_cpu_secs: stat_field(13) as f64 / ticks,
+        system_cpu_secs: stat_field(14) as f64 / ticks,
+        rss_anon_kb: status_value("RssAnon"),
+        rss_file_kb: status_value("RssFile"),
+        rss_shmem_kb: status_value("RssShmem"),
+        swap_kb: status_value("VmSwap"),
+        exited: false,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_process_detail(_info: &ProcessInfo, _uptime_secs: Option<u64>) -> Result<ProcessDetail> {
+    Err(anyhow!("Process details are only available on Linux"))
+}
+
+/// Short reason for an unreadable `/proc` entry.
+#[cfg(target_os = "linux")]
+fn describe_io_error(e: &io::Error) -> String {
+    match e.kind() {
+        io::ErrorKind::PermissionDenied => "permission denied".to_string(),
+        io::ErrorKind::NotFound => "unavailable".to_string(),
+        _ => e.to_string(),
+    }
+}
+
+/// Clock ticks per second (`USER_HZ`), the unit of `/proc/<pid>/stat` times.
+#[cfg(target_os = "linux")]
+fn clock_ticks_per_sec() -> u64 {
+    static CLOCK_TICKS: OnceLock<u64> = OnceLock::new();
+    *CLOCK_TICKS.get_or_init(|| {
+        // SAFETY: sysconf has no preconditions and only reads system configuration.
+        let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        if ticks > 0 {
+            ticks as u64
+        } else {
+            100
+        }
+    })
+}
+
+/// The system page size in KB, queried once via `sysconf`.
+#[cfg(target_os = "linux")]
+fn page_size_kb() -> u64 {
+    static PAGE_SIZE_KB: OnceLock<u64> = OnceLock::new();
+    *PAGE_SIZE_KB.get_or_init(|| {
+        // SAFETY: sysconf has no preconditions and only reads system configuration.
+        let bytes = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if bytes > 0 {
+            bytes as u64 / 1024
+        } else {
+            4 // typical 4KB fallback
+        }
     })
 }
 
@@ -554,6 +2545,107 @@ fn parse_proc_stat(
 // Utility: Memory Format
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Header line with uptime and load average, e.g. `up 3 days, 4:12 | load average: 0.52, 0.48, 0.40`.
+fn system_summary(uptime_secs: Option<u64>, load_avg: Option<[f64; 3]>) -> String {
+    let uptime = uptime_secs.map_or_else(|| "up ?".to_string(), format_uptime);
+    let load = load_avg.map_or_else(
+        || "?".to_string(),
+        |[one, five, fifteen]| format!("{one:.2}, {five:.2}, {fifteen:.2}"),
+    );
+    format!("{uptime} | load average: {load}")
+}
+
+/// Process count by state, e.g. `312 total, 2 running, 298 sleeping, 1 zombie`.
+/// Running and sleeping are always shown; other states only when present,
+/// with zombies highlighted.
+fn state_summary(processes: &[ProcessInfo]) -> Line<'static> {
+    // (state codes, label) in display order
+    const STATES: [(&str, &str); 7] = [
+        ("R", "running"),
+        ("S", "sleeping"),
+        ("D", "disk sleep"),
+        ("I", "idle"),
+        ("Tt", "stopped"),
+        ("Z", "zombie"),
+        ("XxWPK", "other"),
+    ];
+    let mut counts = [0usize; STATES.len()];
+    for p in processes {
+        let slot = STATES
+            .iter()
+            .position(|(codes, _)| codes.contains(p.state.as_str()))
+            .unwrap_or(STATES.len() - 1);
+        counts[slot] += 1;
+    }
+
+    let mut spans = vec![Span::styled(
+        format!("{} total", processes.len()),
+        Style::default().fg(Color::White),
+    )];
+    for (i, ((_, label), count)) in STATES.iter().zip(counts).enumerate() {
+        if count == 0 && i > 1 {
+            continue;
+        }
+        let style = if *label == "zombie" {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        spans.push(Span::raw(", "));
+        spans.push(Span::styled(format!("{count} {label}"), style));
+    }
+    Line::from(spans)
+}
+
+/// Formats uptime like `top`: `up 3 days, 4:12`, `up 1 day, 0:05`, or `up 17 min`.
+fn format_uptime(secs: u64) -> String {
+    let days = secs / 86_400;
+    let hours = (secs % 86_400) / 3_600;
+    let minutes = (secs % 3_600) / 60;
+    let clock = if hours == 0 {
+        format!("{minutes} min")
+    } else {
+        format!("{hours}:{minutes:02}")
+    };
+    match days {
+        0 => format!("up {clock}"),
+        1 => format!("up 1 day, {clock}"),
+        _ => format!("up {days} days, {clock}"),
+    }
+}
+
+/// Formats cumulative CPU time like `ps`: `[DD-]HH:MM:SS`.
+fn format_cpu_time(secs: u64) -> String {
+    let days = secs / 86_400;
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        (secs % 86_400) / 3_600,
+        (secs % 3_600) / 60,
+        secs % 60
+    );
+    if days > 0 {
+        format!("{days}-{clock}")
+    } else {
+        clock
+    }
+}
+
+/// Formats a byte rate with binary units, e.g. `1.25 MiB/s`.
+fn format_rate(bytes_per_sec: f64) -> String {
+    const UNITS: [&str; 4] = ["B/s", "KiB/s", "MiB/s", "GiB/s"];
+    let mut value = bytes_per_sec;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{value:.0} {}", UNITS[unit])
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
 /// Convert memory from KB to a human-readable string (e.g., "32.0 MB").
 fn human_readable_mem(kb: u64) -> String {
     let bytes = kb.saturating_mul(1024);
@@ -7,13 +7,17 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use anyhow::{anyhow, Context, Result};
+use chrono::Local;
 use clap::Parser;
+use pulldown_cmark::{Event as MdEvent, HeadingLevel, Parser as MdParser, Tag, TagEnd};
 use ring::{aead, pbkdf2, rand as ring_rand};
 use serde::{Deserialize, Serialize};
 
 use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
     fs::OpenOptions,
-    io::{self, Read, Write},
+    hash::{Hash, Hasher},
+    io::{self, IsTerminal, Read, Write},
     num::NonZeroU32,
     path::Path,
     time::Duration,
@@ -21,7 +25,7 @@ use std::{
 
 use crossterm::{
     cursor::MoveTo,
-    event::{self, Event, KeyCode, KeyEvent},
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
 };
@@ -32,11 +36,12 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame, Terminal,
 };
 
 use rand::Rng;
+use zeroize::{Zeroize, Zeroizing};
 
 ////////////////////////////////////////////////////////////////////////////////
 // Cross-Platform Line Endings
@@ -58,6 +63,18 @@ struct CliArgs {
     /// Optional path to the encrypted notes file
     #[arg(long, short, default_value = "secure_notes.json.enc")]
     file: String,
+
+    /// Move the vault to this path after unlocking (asks whether to delete the old file)
+    #[arg(long, value_name = "NEW_PATH")]
+    change_file: Option<String>,
+
+    /// Check that the vault decrypts and parses, print the note count, and exit (no TUI)
+    #[arg(long, conflicts_with = "change_file")]
+    verify: bool,
+
+    /// Append a note with this text (titled with the current time) and exit (no TUI)
+    #[arg(long, value_name = "TEXT", conflicts_with_all = ["change_file", "verify"])]
+    quick: Option<String>,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -79,11 +96,93 @@ fn generate_user_friendly_id() -> String {
     format!("{:06}", number)
 }
 
+/// A note in plaintext form, as stored inside the encrypted notes file.
+/// Only exists transiently while loading, saving, or sealing; content is wiped on drop.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 struct Note {
     id: String,
     title: String,
     content: String,
+    /// Previous versions, newest first (absent in files from older versions)
+    #[serde(default)]
+    history: Vec<NoteVersion>,
+}
+
+impl Drop for Note {
+    fn drop(&mut self) {
+        self.content.zeroize();
+    }
+}
+
+/// A superseded version of a note's content, kept so edits can be reverted.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct NoteVersion {
+    /// When this version was replaced by a newer one (local time)
+    replaced_at: String,
+    content: String,
+}
+
+impl Drop for NoteVersion {
+    fn drop(&mut self) {
+        self.content.zeroize();
+    }
+}
+
+/// Maximum number of previous versions kept per note.
+const MAX_NOTE_HISTORY: usize = 10;
+
+/// Rows moved by PageUp/PageDown on the view-notes list.
+const NOTES_PAGE_ROWS: usize = 10;
+
+/// A note as held in memory: ID and title in the clear, content encrypted with
+/// the session key. Only the note being viewed or edited is ever decrypted.
+#[derive(Debug, Clone)]
+struct SealedNote {
+    id: String,
+    title: String,
+    sealed_content: Vec<u8>,
+    /// Previous versions, newest first, each sealed like the current content
+    history: Vec<SealedVersion>,
+}
+
+#[derive(Debug, Clone)]
+struct SealedVersion {
+    replaced_at: String,
+    sealed_content: Vec<u8>,
+}
+
+impl SealedNote {
+    /// Swaps in new (already sealed) content, pushing the old content onto
+    /// the bounded version history.
+    fn replace_content(&mut self, sealed_content: Vec<u8>) {
+        let previous = std::mem::replace(&mut self.sealed_content, sealed_content);
+        self.history.insert(
+            0,
+            SealedVersion {
+                replaced_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                sealed_content: previous,
+            },
+        );
+        self.history.truncate(MAX_NOTE_HISTORY);
+    }
+
+    /// Makes history entry `index` current again; the content it replaces
+    /// becomes the newest history entry, so a restore can itself be undone.
+    fn restore_version(&mut self, index: usize) -> Option<String> {
+        if index >= self.history.len() {
+            return None;
+        }
+        let version = self.history.remove(index);
+        self.replace_content(version.sealed_content);
+        Some(version.replaced_at)
+    }
+}
+
+/// The single note currently decrypted for the view screen.
+#[derive(Debug)]
+struct DecryptedNote {
+    id: String,
+    content: Zeroizing<String>,
 }
 
 /// The different TUI screens.
@@ -97,7 +196,13 @@ enum Screen {
     EditNote,
     DeleteNote,
     OpenNote,
+    NoteView,
+    NoteHistory,
     DeleteAll,
+    ImportNotes,
+    MoveVault,
+    ConfirmMoveVault,
+    Duplicates,
     Exit,
 }
 
@@ -105,19 +210,332 @@ enum Screen {
 #[derive(Debug, Clone)]
 struct EditState {
     note_id: Option<String>,
-    buffer: String,
+    buffer: Zeroizing<String>,
 }
 
 /// Main TUI App State.
 struct App {
-    password: String,      // Master password
-    key: [u8; 32],         // Derived encryption key
-    notes: Vec<Note>,      // All notes
-    screen: Screen,        // Current screen
-    input_buffer: String,  // Generic input buffer (prompt usage, etc.)
-    edit_state: EditState, // For note creation & editing
-    error_message: String, // Displayable error message
-    file_path: String,     // The file path where notes are stored
+    /// Master password
+    password: String,
+    /// Derived encryption key
+    key: [u8; 32],
+    /// All notes, content sealed in memory
+    notes: Vec<SealedNote>,
+    /// Current screen
+    screen: Screen,
+    /// Generic input buffer (prompt usage, etc.)
+    input_buffer: String,
+    /// For note creation & editing
+    edit_state: EditState,
+    /// Displayable error message
+    error_message: String,
+    /// Non-error feedback (e.g. import summary)
+    status_message: String,
+    /// The file path where notes are stored
+    file_path: String,
+    /// Note currently shown on the view screen
+    viewing_note: Option<DecryptedNote>,
+    /// Render Markdown (true) or show raw text (false)
+    render_markdown: bool,
+    /// Highlighted row on the view-notes list
+    notes_selected: usize,
+    /// Selected row on the version history screen
+    history_selected: usize,
+    /// Decrypted preview of that version
+    history_preview: Option<Zeroizing<String>>,
+    /// Validated destination for a vault move
+    move_target: Option<String>,
+    /// Likely duplicates found by the last scan
+    duplicates: Vec<DuplicatePair>,
+    /// Selected pair on the duplicates screen
+    duplicate_selected: usize,
+    /// Decrypted contents of that pair
+    duplicate_preview: Option<[Zeroizing<String>; 2]>,
+    /// Action waiting for a y/n confirmation
+    duplicate_pending: Option<DuplicateAction>,
+}
+
+impl App {
+    /// Wipes any decrypted note content so only sealed notes remain in memory.
+    /// Called whenever the user leaves a screen that shows or edits a note.
+    fn scrub_plaintext(&mut self) {
+        self.edit_state.buffer.zeroize();
+        self.edit_state.note_id = None;
+        self.viewing_note = None;
+        self.history_preview = None;
+        self.duplicate_preview = None;
+    }
+
+    /// Decrypts a note and shows it on the view screen. On failure the error
+    /// is reported and `false` is returned, leaving the screen unchanged.
+    fn open_note(&mut self, id: &str) -> bool {
+        let Some(note) = self.notes.iter().find(|n| n.id == id) else {
+            self.error_message = format!("No note found with ID {id}.");
+            return false;
+        };
+        match unseal_content(note, &self.key) {
+            Ok(content) => {
+                self.viewing_note = Some(DecryptedNote {
+                    id: id.to_string(),
+                    content,
+                });
+                self.screen = Screen::NoteView;
+                true
+            }
+            Err(e) => {
+                self.error_message = format!("Note {id}: {e}");
+                false
+            }
+        }
+    }
+
+    /// Loads a note's decrypted content into the editor buffer.
+    /// Returns `false` (with an error shown) if it can't be read.
+    fn start_editing(&mut self, id: &str) -> bool {
+        let Some(note) = self.notes.iter().find(|n| n.id == id) else {
+            self.error_message = format!("Note ID {id} not found.");
+            return false;
+        };
+        match unseal_content(note, &self.key) {
+            Ok(content) => {
+                self.edit_state.note_id = Some(note.id.clone());
+                self.edit_state.buffer = content;
+                true
+            }
+            Err(e) => {
+                self.error_message = format!("Note {id}: {e}");
+                false
+            }
+        }
+    }
+
+    /// ID of the note highlighted on the view-notes list.
+    fn selected_note_id(&self) -> Option<String> {
+        self.notes.get(self.notes_selected).map(|n| n.id.clone())
+    }
+
+    /// Fingerprints every note (decrypting one at a time) and lists the
+    /// likely duplicates on the duplicates screen.
+    fn scan_duplicates(&mut self) -> Result<()> {
+        let mut fingerprints = Vec::with_capacity(self.notes.len());
+        for note in &self.notes {
+            let content =
+                unseal_content(note, &self.key).with_context(|| format!("Note {}", note.id))?;
+            fingerprints.push(NoteFingerprint::new(&note.id, &note.title, &content));
+        }
+        self.duplicates = find_duplicates(&fingerprints);
+        self.duplicate_selected = 0;
+        self.duplicate_pending = None;
+        self.load_duplicate_preview();
+        self.screen = Screen::Duplicates;
+        Ok(())
+    }
+
+    /// Decrypts the two notes of the selected pair for the side-by-side view.
+    fn load_duplicate_preview(&mut self) {
+        self.duplicate_preview = None;
+        let Some(pair) = self.duplicates.get(self.duplicate_selected) else {
+            return;
+        };
+        let unseal = |id: &str| {
+            let note = self.notes.iter().find(|n| n.id == id)?;
+            unseal_content(note, &self.key).ok()
+        };
+        if let (Some(left), Some(right)) = (unseal(&pair.left), unseal(&pair.right)) {
+            self.duplicate_preview = Some([left, right]);
+        }
+    }
+
+    /// Deletes one note of the selected pair, or merges the right note into
+    /// the left one, then saves and drops every pair that involved the
+    /// removed note.
+    fn resolve_duplicate(&mut self, action: DuplicateAction) -> Result<()> {
+        let Some(pair) = self.duplicates.get(self.duplicate_selected).cloned() else {
+            return Ok(());
+        };
+        let removed = match action {
+            DuplicateAction::DeleteLeft => pair.left.clone(),
+            DuplicateAction::DeleteRight | DuplicateAction::Merge => pair.right.clone(),
+        };
+
+        if action == DuplicateAction::Merge {
+            let Some([left, right]) = &self.duplicate_preview else {
+                self.error_message = "Could not decrypt this pair to merge it.".to_string();
+                return Ok(());
+            };
+            if let Some(merged) = merge_contents(left, right) {
+                let sealed = encrypt_data(merged.as_bytes(), &self.key)?;
+                if let Some(note) = self.notes.iter_mut().find(|n| n.id == pair.left) {
+                    // The pre-merge content stays in the note's version history
+                    note.replace_content(sealed);
+                }
+                self.status_message = format!(
+                    "Merged note {} into {} and deleted it.",
+                    pair.right, pair.left
+                );
+            } else {
+                self.status_message = format!(
+                    "Note {} added nothing to {}; deleted it.",
+                    pair.right, pair.left
+                );
+            }
+        } else {
+            self.status_message = format!("Deleted note {removed}.");
+        }
+
+        self.notes.retain(|n| n.id != removed);
+        save_notes(&self.file_path, &self.notes, &self.key)?;
+        self.duplicates
+            .retain(|p| p.left != removed && p.right != removed);
+        self.duplicate_selected = self
+            .duplicate_selected
+            .min(self.duplicates.len().saturating_sub(1));
+        self.load_duplicate_preview();
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Duplicate Detection
+////////////////////////////////////////////////////////////////////////////////
+
+/// Word overlap (Jaccard index) at or above which two notes count as near-duplicates.
+const DUPLICATE_SIMILARITY: f64 = 0.8;
+
+/// Two notes that look like copies of each other. `left` is the older one
+/// (earlier in the vault).
+#[derive(Debug, Clone)]
+struct DuplicatePair {
+    left: String,
+    right: String,
+    left_title: String,
+    right_title: String,
+    /// Why they were paired, e.g. "identical content" or "87% similar"
+    reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DuplicateAction {
+    DeleteLeft,
+    DeleteRight,
+    Merge,
+}
+
+impl DuplicateAction {
+    /// The question asked before the action is carried out on `pair`.
+    fn prompt(self, pair: &DuplicatePair) -> String {
+        match self {
+            DuplicateAction::DeleteLeft => format!("Delete note {}?", pair.left),
+            DuplicateAction::DeleteRight => format!("Delete note {}?", pair.right),
+            DuplicateAction::Merge => format!(
+                "Merge note {} into {} and delete {}?",
+                pair.right, pair.left, pair.right
+            ),
+        }
+    }
+}
+
+/// What duplicate detection needs to know about a note's content. Only
+/// hashes are kept, so no plaintext outlives the decryption of each note.
+#[derive(Debug)]
+struct NoteFingerprint {
+    id: String,
+    title: String,
+    /// Hash of the content with surrounding whitespace trimmed
+    content_hash: u64,
+    /// Hashes of the distinct lowercase words
+    words: HashSet<u64>,
+}
+
+impl NoteFingerprint {
+    fn new(id: &str, title: &str, content: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        content.trim().hash(&mut hasher);
+        let words = content
+            .split_whitespace()
+            .map(|word| {
+                let mut hasher = DefaultHasher::new();
+                for c in word
+                    .chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .flat_map(char::to_lowercase)
+                {
+                    c.hash(&mut hasher);
+                }
+                hasher.finish()
+            })
+            .collect();
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            content_hash: hasher.finish(),
+            words,
+        }
+    }
+
+    /// Shared words as a fraction of all distinct words in either note.
+    fn similarity(&self, other: &Self) -> f64 {
+        let union = self.words.union(&other.words).count();
+        if union == 0 {
+            return 0.0;
+        }
+        self.words.intersection(&other.words).count() as f64 / union as f64
+    }
+}
+
+/// Pairs notes with identical content, a shared title (other than the
+/// default "(Untitled)"), or mostly the same words, most similar first.
+fn find_duplicates(notes: &[NoteFingerprint]) -> Vec<DuplicatePair> {
+    let mut pairs = Vec::new();
+    for (i, left) in notes.iter().enumerate() {
+        for right in &notes[i + 1..] {
+            let similarity = left.similarity(right);
+            let same_title = !left.title.trim().is_empty()
+                && left.title != "(Untitled)"
+                && left.title.trim().eq_ignore_ascii_case(right.title.trim());
+            let (rank, reason) = if left.content_hash == right.content_hash {
+                (2.0, "identical content".to_string())
+            } else if similarity >= DUPLICATE_SIMILARITY {
+                (similarity, format!("{:.0}% similar", similarity * 100.0))
+            } else if same_title {
+                (
+                    similarity,
+                    format!("same title, {:.0}% similar", similarity * 100.0),
+                )
+            } else {
+                continue;
+            };
+            pairs.push((
+                rank,
+                DuplicatePair {
+                    left: left.id.clone(),
+                    right: right.id.clone(),
+                    left_title: left.title.clone(),
+                    right_title: right.title.clone(),
+                    reason,
+                },
+            ));
+        }
+    }
+    pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+    pairs.into_iter().map(|(_, pair)| pair).collect()
+}
+
+/// The left note followed by the lines of the right note it doesn't already
+/// contain. `None` when the right note adds nothing.
+fn merge_contents(left: &str, right: &str) -> Option<Zeroizing<String>> {
+    let existing: HashSet<&str> = left.lines().map(str::trim).collect();
+    let extra: Vec<&str> = right
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !existing.contains(line.trim()))
+        .collect();
+    if extra.is_empty() {
+        return None;
+    }
+    let mut merged = Zeroizing::new(left.trim_end().to_string());
+    merged.push_str("\n\n");
+    merged.push_str(&extra.join("\n"));
+    Some(merged)
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -152,6 +570,17 @@ async fn main() -> Result<()> {
     // 1) Parse CLI arguments
     let args = CliArgs::parse();
 
+    // Health check mode never enters the TUI
+    if args.verify {
+        let code = run_verify(&args.file)?;
+        std::process::exit(code);
+    }
+
+    // Quick capture appends one note and exits, also without the TUI
+    if let Some(text) = &args.quick {
+        return run_quick_note(&args.file, text);
+    }
+
     // 2) Enable raw mode via our RAII guard
     let _raw_guard = RawModeGuard::new().context("Failed to enable raw mode")?;
 
@@ -170,14 +599,26 @@ async fn main() -> Result<()> {
         input_buffer: String::new(),
         edit_state: EditState {
             note_id: None,
-            buffer: String::new(),
+            buffer: Zeroizing::new(String::new()),
         },
         error_message: String::new(),
+        status_message: String::new(),
         file_path: args.file,
+        viewing_note: None,
+        notes_selected: 0,
+        history_selected: 0,
+        history_preview: None,
+        render_markdown: true,
+        move_target: None,
+        duplicates: Vec::new(),
+        duplicate_selected: 0,
+        duplicate_preview: None,
+        duplicate_pending: None,
     };
+    let change_file = args.change_file;
 
     // 6) Launch the main TUI loop
-    if let Err(e) = run_app(&mut terminal, app) {
+    if let Err(e) = run_app(&mut terminal, app, change_file) {
         // If the app errored, restore terminal and show the error
         finalize_terminal(&mut terminal)?;
         eprintln!("Error: {e}");
@@ -236,8 +677,20 @@ fn finalize_terminal(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>)
 // Main TUI Loop
 ////////////////////////////////////////////////////////////////////////////////
 
-fn run_app(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>, mut app: App) -> Result<()> {
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    mut app: App,
+    mut change_file: Option<String>,
+) -> Result<()> {
     loop {
+        // `--change-file`: once unlocked, jump to the move prompt with the path filled in
+        if app.screen == Screen::Menu {
+            if let Some(path) = change_file.take() {
+                app.input_buffer = path;
+                app.screen = Screen::MoveVault;
+            }
+        }
+
         // 1) Draw current TUI
         terminal.draw(|frame| draw_ui(frame, &app))?;
 
@@ -288,9 +741,15 @@ fn draw_ui(frame: &mut Frame, app: &App) {
         Screen::Menu => draw_main_menu(frame, chunks[1]),
         Screen::ViewNotes => draw_view_notes(frame, app, chunks[1]),
         Screen::CreateNote | Screen::EditNote => draw_note_editor(frame, app, chunks[1]),
-        Screen::DeleteNote | Screen::OpenNote | Screen::DeleteAll => {
-            draw_simple_input(frame, app, chunks[1])
-        }
+        Screen::DeleteNote
+        | Screen::OpenNote
+        | Screen::DeleteAll
+        | Screen::ImportNotes
+        | Screen::MoveVault => draw_simple_input(frame, app, chunks[1]),
+        Screen::ConfirmMoveVault => draw_move_confirm(frame, app, chunks[1]),
+        Screen::NoteView => draw_note_view(frame, app, chunks[1]),
+        Screen::NoteHistory => draw_note_history(frame, app, chunks[1]),
+        Screen::Duplicates => draw_duplicates(frame, app, chunks[1]),
         Screen::Exit => {
             // Nothing special to draw
         }
@@ -306,6 +765,15 @@ fn draw_ui(frame: &mut Frame, app: &App) {
             .block(block)
             .style(Style::default().fg(Color::Red));
         frame.render_widget(paragraph, chunks[2]);
+    } else if !app.status_message.is_empty() {
+        let block = Block::default()
+            .title("Status")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green));
+        let paragraph = Paragraph::new(Text::raw(&app.status_message))
+            .block(block)
+            .style(Style::default().fg(Color::Green));
+        frame.render_widget(paragraph, chunks[2]);
     }
 }
 
@@ -372,7 +840,10 @@ fn draw_main_menu(frame: &mut Frame, area: Rect) {
         "4) Delete Note",
         "5) Open Note",
         "6) Delete ALL Notes",
-        "7) Exit",
+        "7) Import Plaintext Files",
+        "8) Move Vault to New File",
+        "D) Find Duplicate Notes",
+        "9) Exit",
     ];
     let items: Vec<ListItem> = options
         .into_iter()
@@ -404,23 +875,35 @@ fn draw_view_notes(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
+    // Content stays sealed here; only IDs and titles are listed.
     let mut items = Vec::new();
     for note in &app.notes {
         let text = format!(
-            "ID: {} | Title: {} | Content (truncated): {}",
+            "ID: {} | Title: {} | {} bytes encrypted",
             note.id,
             note.title,
-            note.content.chars().take(30).collect::<String>()
+            note.sealed_content.len()
         );
         items.push(ListItem::new(Span::raw(text)));
     }
 
-    let block = Block::default().title("View Notes").borders(Borders::ALL);
+    // A deletion may have left the saved selection past the end
+    let selected = app.notes_selected.min(app.notes.len() - 1);
+    let title = format!(
+        "View Notes {}/{} (Up/Down, Enter=open, E=edit, D=delete, Esc=back)",
+        selected + 1,
+        app.notes.len()
+    );
+    let block = Block::default().title(title).borders(Borders::ALL);
     let list = List::new(items)
         .block(block)
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(Color::White))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
 
-    frame.render_widget(list, area);
+    // The list scrolls to keep the highlighted note in view
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
 }
 
 fn draw_note_editor(frame: &mut Frame, app: &App, area: Rect) {
@@ -438,11 +921,191 @@ fn draw_note_editor(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+/// Shows a single note, either as raw text or with Markdown styling applied.
+fn draw_note_view(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(note) = app.viewing_note.as_ref() else {
+        let block = Block::default().title("View Note").borders(Borders::ALL);
+        let paragraph = Paragraph::new("Note not found.")
+            .block(block)
+            .style(Style::default().fg(Color::Yellow));
+        frame.render_widget(paragraph, area);
+        return;
+    };
+
+    let mode = if app.render_markdown {
+        "Rendered"
+    } else {
+        "Raw"
+    };
+    let title = format!(
+        "Note {} [{}] (M=toggle Markdown, H=history, Esc=back)",
+        note.id, mode
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+
+    let text = if app.render_markdown {
+        Text::from(render_markdown(&note.content))
+    } else {
+        Text::raw(note.content.as_str())
+    };
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Left)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Lists the stored versions of the open note, with a preview of the selected one.
+fn draw_note_history(frame: &mut Frame, app: &App, area: Rect) {
+    let note = app
+        .viewing_note
+        .as_ref()
+        .and_then(|v| app.notes.iter().find(|n| n.id == v.id));
+    let Some(note) = note else {
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    let title = format!(
+        "History of note {} (Up/Down, Enter=preview, R=restore, Esc=back)",
+        note.id
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+    if note.history.is_empty() {
+        let paragraph = Paragraph::new("No previous versions yet.")
+            .block(block)
+            .style(Style::default().fg(Color::Yellow));
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = note
+        .history
+        .iter()
+        .enumerate()
+        .map(|(i, version)| {
+            let text = format!(
+                "{}) replaced {} | {} bytes encrypted",
+                i + 1,
+                version.replaced_at,
+                version.sealed_content.len()
+            );
+            let style = if i == app.history_selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Span::styled(text, style))
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(block), chunks[0]);
+
+    let preview = Paragraph::new(
+        app.history_preview
+            .as_ref()
+            .map_or("Press Enter to preview this version.", |p| p.as_str()),
+    )
+    .block(Block::default().title("Preview").borders(Borders::ALL))
+    .wrap(Wrap { trim: false });
+    frame.render_widget(preview, chunks[1]);
+}
+
+/// Likely duplicate pairs on top, the selected pair's notes side by side below.
+fn draw_duplicates(frame: &mut Frame, app: &App, area: Rect) {
+    let pending = app
+        .duplicate_pending
+        .zip(app.duplicates.get(app.duplicate_selected))
+        .map(|(action, pair)| format!("{} (Y=yes, N/Esc=no)", action.prompt(pair)));
+    let title = pending.unwrap_or_else(|| {
+        "Duplicates (Up/Down, M=merge right into left, L/R=delete left/right, Esc=back)".to_string()
+    });
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+    if app.duplicates.is_empty() {
+        let paragraph = Paragraph::new("No duplicate notes found.")
+            .block(block)
+            .style(Style::default().fg(Color::Yellow))
+            .alignment(Alignment::Center);
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let list_height = (app.duplicates.len() as u16 + 2)
+        .min(area.height / 3)
+        .max(3);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(list_height), Constraint::Min(0)])
+        .split(area);
+
+    let items: Vec<ListItem> = app
+        .duplicates
+        .iter()
+        .map(|pair| {
+            ListItem::new(Span::raw(format!(
+                "{} \"{}\"  /  {} \"{}\"  ({})",
+                pair.left, pair.left_title, pair.right, pair.right_title, pair.reason
+            )))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    let mut state = ListState::default().with_selected(Some(app.duplicate_selected));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    let pair = &app.duplicates[app.duplicate_selected];
+    for (i, (id, title)) in [
+        (&pair.left, &pair.left_title),
+        (&pair.right, &pair.right_title),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let side = if i == 0 { "Left" } else { "Right" };
+        let content = app
+            .duplicate_preview
+            .as_ref()
+            .map_or("(could not decrypt)", |preview| preview[i].as_str());
+        let paragraph = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(format!("{side}: {id} | {title}"))
+                    .borders(Borders::ALL),
+            )
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, halves[i]);
+    }
+}
+
 fn draw_simple_input(frame: &mut Frame, app: &App, area: Rect) {
     let title = match app.screen {
         Screen::DeleteNote => "Enter Note ID to delete (ENTER=confirm, ESC=cancel)",
         Screen::OpenNote => "Enter Note ID to open (ENTER=confirm, ESC=cancel)",
         Screen::DeleteAll => "Type YES to confirm (ENTER=confirm, ESC=cancel)",
+        Screen::ImportNotes => {
+            "File path or glob to import, e.g. ~/notes/*.txt (ENTER=import, ESC=cancel)"
+        }
+        Screen::MoveVault => "New path for the vault file (ENTER=continue, ESC=cancel)",
         _ => "",
     };
 
@@ -455,6 +1118,33 @@ fn draw_simple_input(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+/// Last step of a vault move: shows both paths and asks about the old file.
+fn draw_move_confirm(frame: &mut Frame, app: &App, area: Rect) {
+    let target = app.move_target.as_deref().unwrap_or_default();
+    let lines = vec![
+        Line::from(format!("From: {}", app.file_path)),
+        Line::from(format!("To:   {target}")),
+        Line::from(""),
+        Line::from(
+            "The vault is written to the new path and read back before anything is deleted.",
+        ),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Y = move (delete the old file)   N = copy (keep the old file)   ESC = cancel",
+            Style::default().fg(Color::Yellow),
+        )),
+    ];
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title("Move Vault")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green)),
+        )
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Input Handling
 ////////////////////////////////////////////////////////////////////////////////
@@ -481,8 +1171,9 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
             KeyCode::Enter => {
                 // Derive key
                 app.password = app.input_buffer.clone();
-                app.input_buffer.clear();
+                app.input_buffer.zeroize();
                 app.key = derive_key_from_password(&app.password, SALT, PBKDF2_ITERATIONS)?;
+                app.password.zeroize();
 
                 // Try loading notes
                 if let Ok(notes) = load_notes(&app.file_path, &app.key) {
@@ -532,7 +1223,23 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
                 app.input_buffer.clear();
                 app.screen = Screen::DeleteAll;
             }
-            KeyCode::Char('7') => app.screen = Screen::Exit,
+            KeyCode::Char('7') => {
+                app.input_buffer.clear();
+                app.status_message.clear();
+                app.screen = Screen::ImportNotes;
+            }
+            KeyCode::Char('8') => {
+                app.input_buffer.clear();
+                app.status_message.clear();
+                app.screen = Screen::MoveVault;
+            }
+            KeyCode::Char('d') | KeyCode::Char('D') => {
+                app.status_message.clear();
+                if let Err(e) = app.scan_duplicates() {
+                    app.error_message = format!("Duplicate scan failed: {e:#}");
+                }
+            }
+            KeyCode::Char('9') => app.screen = Screen::Exit,
             _ => {}
         },
 
@@ -540,9 +1247,44 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
         // VIEW NOTES
         // --------------------------------------------------------------------
         Screen::ViewNotes => {
-            // On Enter/Esc, go back to menu
-            if matches!(key_event.code, KeyCode::Enter | KeyCode::Esc) {
-                app.screen = Screen::Menu;
+            let last = app.notes.len().saturating_sub(1);
+            app.notes_selected = app.notes_selected.min(last);
+            match key_event.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    app.notes_selected = app.notes_selected.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    app.notes_selected = (app.notes_selected + 1).min(last);
+                }
+                KeyCode::PageUp => {
+                    app.notes_selected = app.notes_selected.saturating_sub(NOTES_PAGE_ROWS);
+                }
+                KeyCode::PageDown => {
+                    app.notes_selected = (app.notes_selected + NOTES_PAGE_ROWS).min(last);
+                }
+                KeyCode::Home => app.notes_selected = 0,
+                KeyCode::End => app.notes_selected = last,
+                KeyCode::Enter | KeyCode::Char('o') | KeyCode::Char('O') => {
+                    if let Some(id) = app.selected_note_id() {
+                        app.open_note(&id);
+                    }
+                }
+                KeyCode::Char('e') | KeyCode::Char('E') => {
+                    if let Some(id) = app.selected_note_id() {
+                        if app.start_editing(&id) {
+                            app.screen = Screen::EditNote;
+                        }
+                    }
+                }
+                KeyCode::Char('d') | KeyCode::Char('D') => {
+                    // Pre-fill the delete prompt so Enter confirms
+                    if let Some(id) = app.selected_note_id() {
+                        app.input_buffer = id;
+                        app.screen = Screen::DeleteNote;
+                    }
+                }
+                KeyCode::Esc => app.screen = Screen::Menu,
+                _ => {}
             }
         }
 
@@ -555,14 +1297,17 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
                 let new_note = Note {
                     id: generate_user_friendly_id(),
                     title: "(Untitled)".to_string(),
-                    content: app.edit_state.buffer.clone(),
+                    content: app.edit_state.buffer.to_string(),
+                    history: Vec::new(),
                 };
-                app.notes.push(new_note);
+                app.notes.push(seal_note(&new_note, &app.key)?);
                 save_notes(&app.file_path, &app.notes, &app.key)?;
+                app.scrub_plaintext();
                 app.screen = Screen::Menu;
             }
             KeyCode::F(2) => {
                 // Discard
+                app.scrub_plaintext();
                 app.screen = Screen::Menu;
             }
             KeyCode::Backspace => {
@@ -584,12 +1329,7 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
                     KeyCode::Enter => {
                         let id = app.input_buffer.trim().to_string();
                         app.input_buffer.clear();
-                        if let Some(note) = app.notes.iter().find(|n| n.id == id) {
-                            app.edit_state.note_id = Some(note.id.clone());
-                            app.edit_state.buffer = note.content.clone();
-                        } else {
-                            app.error_message = format!("Note ID {id} not found.");
-                        }
+                        app.start_editing(&id);
                     }
                     KeyCode::Esc => {
                         app.screen = Screen::Menu;
@@ -609,14 +1349,22 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
                         // Save changes
                         if let Some(id) = &app.edit_state.note_id {
                             if let Some(n) = app.notes.iter_mut().find(|x| &x.id == id) {
-                                n.content = app.edit_state.buffer.clone();
+                                // Unchanged saves don't add a history entry
+                                if *unseal_content(n, &app.key)? != *app.edit_state.buffer {
+                                    n.replace_content(encrypt_data(
+                                        app.edit_state.buffer.as_bytes(),
+                                        &app.key,
+                                    )?);
+                                }
                             }
                             save_notes(&app.file_path, &app.notes, &app.key)?;
                         }
+                        app.scrub_plaintext();
                         app.screen = Screen::Menu;
                     }
                     KeyCode::F(2) => {
                         // Discard changes
+                        app.scrub_plaintext();
                         app.screen = Screen::Menu;
                     }
                     KeyCode::Backspace => {
@@ -663,15 +1411,11 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
         // --------------------------------------------------------------------
         Screen::OpenNote => match key_event.code {
             KeyCode::Enter => {
-                let id = app.input_buffer.trim();
-                if let Some(n) = app.notes.iter().find(|x| x.id == id) {
-                    // Place the full content in error_message as a quick display
-                    app.error_message = format!("Full Note: {}", n.content);
-                } else {
-                    app.error_message = format!("No note found with ID {id}.");
-                }
+                let id = app.input_buffer.trim().to_string();
                 app.input_buffer.clear();
-                app.screen = Screen::Menu;
+                if !app.open_note(&id) {
+                    app.screen = Screen::Menu;
+                }
             }
             KeyCode::Esc => {
                 app.screen = Screen::Menu;
@@ -685,6 +1429,76 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
             _ => {}
         },
 
+        // --------------------------------------------------------------------
+        // NOTE VIEW
+        // --------------------------------------------------------------------
+        Screen::NoteView => match key_event.code {
+            KeyCode::Char('m') | KeyCode::Char('M') => {
+                app.render_markdown = !app.render_markdown;
+            }
+            KeyCode::Char('h') | KeyCode::Char('H') => {
+                app.history_selected = 0;
+                app.history_preview = None;
+                app.screen = Screen::NoteHistory;
+            }
+            KeyCode::Enter | KeyCode::Esc => {
+                app.scrub_plaintext();
+                app.screen = Screen::Menu;
+            }
+            _ => {}
+        },
+
+        // --------------------------------------------------------------------
+        // NOTE HISTORY
+        // --------------------------------------------------------------------
+        Screen::NoteHistory => {
+            let Some(id) = app.viewing_note.as_ref().map(|v| v.id.clone()) else {
+                app.screen = Screen::Menu;
+                return Ok(());
+            };
+            let Some(index) = app.notes.iter().position(|n| n.id == id) else {
+                app.screen = Screen::Menu;
+                return Ok(());
+            };
+            let versions = app.notes[index].history.len();
+            match key_event.code {
+                KeyCode::Up => {
+                    app.history_selected = app.history_selected.saturating_sub(1);
+                    app.history_preview = None;
+                }
+                KeyCode::Down => {
+                    app.history_selected =
+                        (app.history_selected + 1).min(versions.saturating_sub(1));
+                    app.history_preview = None;
+                }
+                KeyCode::Enter => {
+                    if let Some(version) = app.notes[index].history.get(app.history_selected) {
+                        match unseal_version(version, &app.key) {
+                            Ok(content) => app.history_preview = Some(content),
+                            Err(e) => app.error_message = format!("Version: {e}"),
+                        }
+                    }
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') => {
+                    let note = &mut app.notes[index];
+                    if let Some(replaced_at) = note.restore_version(app.history_selected) {
+                        let content = unseal_content(note, &app.key)?;
+                        save_notes(&app.file_path, &app.notes, &app.key)?;
+                        app.viewing_note = Some(DecryptedNote { id, content });
+                        app.history_preview = None;
+                        app.status_message =
+                            format!("Restored the version replaced at {replaced_at}.");
+                        app.screen = Screen::NoteView;
+                    }
+                }
+                KeyCode::Esc => {
+                    app.history_preview = None;
+                    app.screen = Screen::NoteView;
+                }
+                _ => {}
+            }
+        }
+
         // --------------------------------------------------------------------
         // DELETE ALL
         // --------------------------------------------------------------------
@@ -712,6 +1526,135 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
             _ => {}
         },
 
+        // --------------------------------------------------------------------
+        // IMPORT PLAINTEXT FILES
+        // --------------------------------------------------------------------
+        Screen::ImportNotes => match key_event.code {
+            KeyCode::Enter => {
+                let pattern = expand_home(app.input_buffer.trim());
+                app.input_buffer.clear();
+                match import_plaintext_notes(&pattern, &app.key) {
+                    Ok(report) => {
+                        if !report.imported.is_empty() {
+                            app.notes.extend(report.imported.iter().cloned());
+                            save_notes(&app.file_path, &app.notes, &app.key)?;
+                        }
+                        app.status_message = report.summary();
+                    }
+                    Err(e) => app.error_message = format!("Import failed: {e:#}"),
+                }
+                app.screen = Screen::Menu;
+            }
+            KeyCode::Esc => {
+                app.screen = Screen::Menu;
+            }
+            KeyCode::Backspace => {
+                app.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                app.input_buffer.push(c);
+            }
+            _ => {}
+        },
+
+        // --------------------------------------------------------------------
+        // MOVE VAULT (destination path, then confirmation)
+        // --------------------------------------------------------------------
+        Screen::MoveVault => match key_event.code {
+            KeyCode::Enter => {
+                let target = expand_home(app.input_buffer.trim());
+                app.input_buffer.clear();
+                match check_move_target(&app.file_path, &target) {
+                    Ok(()) => {
+                        app.move_target = Some(target);
+                        app.screen = Screen::ConfirmMoveVault;
+                    }
+                    Err(e) => {
+                        app.error_message = format!("Move: {e:#}");
+                        app.screen = Screen::Menu;
+                    }
+                }
+            }
+            KeyCode::Esc => {
+                app.input_buffer.clear();
+                app.screen = Screen::Menu;
+            }
+            KeyCode::Backspace => {
+                app.input_buffer.pop();
+            }
+            KeyCode::Char(c) => {
+                app.input_buffer.push(c);
+            }
+            _ => {}
+        },
+
+        Screen::ConfirmMoveVault => {
+            let delete_old = match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => Some(true),
+                KeyCode::Char('n') | KeyCode::Char('N') => Some(false),
+                _ => None,
+            };
+            if let (Some(delete_old), Some(target)) = (delete_old, app.move_target.take()) {
+                match move_vault(&app.file_path, &target, &app.notes, &app.key, delete_old) {
+                    Ok(summary) => {
+                        app.status_message = summary;
+                        app.file_path = target;
+                    }
+                    Err(e) => app.error_message = format!("Move failed: {e:#}"),
+                }
+                app.screen = Screen::Menu;
+            } else if key_event.code == KeyCode::Esc {
+                app.move_target = None;
+                app.screen = Screen::Menu;
+            }
+        }
+
+        // --------------------------------------------------------------------
+        // DUPLICATES
+        // --------------------------------------------------------------------
+        Screen::Duplicates => {
+            // Deletes and merges can't be undone from here, so each one is confirmed
+            if let Some(action) = app.duplicate_pending {
+                match key_event.code {
+                    KeyCode::Char('y') | KeyCode::Char('Y') => {
+                        app.duplicate_pending = None;
+                        app.resolve_duplicate(action)?
+                    }
+                    KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                        app.duplicate_pending = None
+                    }
+                    _ => {}
+                }
+                return Ok(());
+            }
+            let last = app.duplicates.len().saturating_sub(1);
+            match key_event.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    app.duplicate_selected = app.duplicate_selected.saturating_sub(1);
+                    app.load_duplicate_preview();
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    app.duplicate_selected = (app.duplicate_selected + 1).min(last);
+                    app.load_duplicate_preview();
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') if !app.duplicates.is_empty() => {
+                    app.duplicate_pending = Some(DuplicateAction::Merge)
+                }
+                KeyCode::Char('l') | KeyCode::Char('L') if !app.duplicates.is_empty() => {
+                    app.duplicate_pending = Some(DuplicateAction::DeleteLeft)
+                }
+                KeyCode::Char('r') | KeyCode::Char('R') if !app.duplicates.is_empty() => {
+                    app.duplicate_pending = Some(DuplicateAction::DeleteRight)
+                }
+                KeyCode::Esc => {
+                    app.scrub_plaintext();
+                    app.duplicates.clear();
+                    app.screen = Screen::Menu;
+                }
+                _ => {}
+            }
+        }
+
         // --------------------------------------------------------------------
         // EXIT
         // --------------------------------------------------------------------
@@ -720,6 +1663,502 @@ fn handle_key_event(key_event: KeyEvent, app: &mut App) -> Result<()> {
     Ok(())
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Markdown Rendering
+////////////////////////////////////////////////////////////////////////////////
+
+/// Converts Markdown into styled lines: headers, emphasis, code, and lists.
+fn render_markdown(source: &str) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut current: Vec<Span<'static>> = Vec::new();
+    let mut styles: Vec<Style> = vec![Style::default()];
+    // One entry per open list: `Some(n)` for ordered lists, `None` for bullets.
+    let mut lists: Vec<Option<u64>> = Vec::new();
+
+    fn flush(lines: &mut Vec<Line<'static>>, current: &mut Vec<Span<'static>>) {
+        if !current.is_empty() {
+            lines.push(Line::from(std::mem::take(current)));
+        }
+    }
+
+    for event in MdParser::new(source) {
+        let style = *styles.last().unwrap_or(&Style::default());
+        match event {
+            MdEvent::Start(Tag::Heading { level, .. }) => {
+                flush(&mut lines, &mut current);
+                let color = match level {
+                    HeadingLevel::H1 => Color::Magenta,
+                    HeadingLevel::H2 => Color::Cyan,
+                    _ => Color::Blue,
+                };
+                let mut heading = style.fg(color).add_modifier(Modifier::BOLD);
+                if level == HeadingLevel::H1 {
+                    heading = heading.add_modifier(Modifier::UNDERLINED);
+                }
+                styles.push(heading);
+            }
+            MdEvent::End(TagEnd::Heading(_)) => {
+                styles.pop();
+                flush(&mut lines, &mut current);
+                lines.push(Line::from(""));
+            }
+            MdEvent::Start(Tag::Emphasis) => styles.push(style.add_modifier(Modifier::ITALIC)),
+            MdEvent::Start(Tag::Strong) => styles.push(style.add_modifier(Modifier::BOLD)),
+            MdEvent::Start(Tag::Strikethrough) => {
+                styles.push(style.add_modifier(Modifier::CROSSED_OUT))
+            }
+            MdEvent::Start(Tag::CodeBlock(_)) => {
+                flush(&mut lines, &mut current);
+                styles.push(style.fg(Color::Yellow));
+            }
+            MdEvent::End(TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough) => {
+                styles.pop();
+            }
+            MdEvent::End(TagEnd::CodeBlock) => {
+                styles.pop();
+                flush(&mut lines, &mut current);
+                lines.push(Line::from(""));
+            }
+            MdEvent::Start(Tag::List(start)) => {
+                flush(&mut lines, &mut current);
+                lists.push(start);
+            }
+            MdEvent::End(TagEnd::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    lines.push(Line::from(""));
+                }
+            }
+            MdEvent::Start(Tag::Item) => {
+                flush(&mut lines, &mut current);
+                let indent = "  ".repeat(lists.len().saturating_sub(1));
+                let marker = match lists.last_mut() {
+                    Some(Some(n)) => {
+                        let marker = format!("{indent}{n}. ");
+                        *n += 1;
+                        marker
+                    }
+                    _ => format!("{indent}• "),
+                };
+                current.push(Span::styled(marker, Style::default().fg(Color::Green)));
+            }
+            MdEvent::End(TagEnd::Item) => flush(&mut lines, &mut current),
+            MdEvent::End(TagEnd::Paragraph) => {
+                flush(&mut lines, &mut current);
+                if lists.is_empty() {
+                    lines.push(Line::from(""));
+                }
+            }
+            MdEvent::Text(text) => {
+                // Code blocks arrive as multi-line text; keep their line breaks.
+                let mut parts = text.split('\n').peekable();
+                while let Some(part) = parts.next() {
+                    if !part.is_empty() {
+                        current.push(Span::styled(part.to_string(), style));
+                    }
+                    if parts.peek().is_some() {
+                        flush(&mut lines, &mut current);
+                    }
+                }
+            }
+            MdEvent::Code(code) => {
+                current.push(Span::styled(code.to_string(), style.fg(Color::Yellow)));
+            }
+            MdEvent::SoftBreak => current.push(Span::raw(" ")),
+            MdEvent::HardBreak => flush(&mut lines, &mut current),
+            MdEvent::Rule => {
+                flush(&mut lines, &mut current);
+                lines.push(Line::from(Span::styled(
+                    "─".repeat(40),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            _ => {}
+        }
+    }
+    flush(&mut lines, &mut current);
+
+    // Drop trailing blank lines left over from the last block.
+    while lines.last().is_some_and(|l| l.width() == 0) {
+        lines.pop();
+    }
+    lines
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Moving the Vault
+////////////////////////////////////////////////////////////////////////////////
+
+/// Rejects destinations that are empty, the current file, already taken, or
+/// in a directory that doesn't exist.
+fn check_move_target(current: &str, target: &str) -> Result<()> {
+    if target.is_empty() {
+        return Err(anyhow!("No path given"));
+    }
+    let target_path = Path::new(target);
+    let same_file = match (
+        Path::new(current).canonicalize(),
+        target_path.canonicalize(),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => current == target,
+    };
+    if same_file {
+        return Err(anyhow!("That is the current vault file"));
+    }
+    if target_path.exists() {
+        return Err(anyhow!(
+            "{target} already exists; choose a new file name so nothing is overwritten"
+        ));
+    }
+    let parent = target_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    if !parent.is_dir() {
+        return Err(anyhow!("Directory {} does not exist", parent.display()));
+    }
+    Ok(())
+}
+
+/// Re-saves the vault at `target` and optionally deletes the old file.
+///
+/// Nothing is deleted unless the old file decrypts with the current key and
+/// the new file reads back with the same notes. If the new file can't be
+/// verified it is removed again and the old one is left untouched.
+fn move_vault(
+    current: &str,
+    target: &str,
+    notes: &[SealedNote],
+    key: &[u8],
+    delete_old: bool,
+) -> Result<String> {
+    let old_exists = Path::new(current).exists();
+    if old_exists {
+        let on_disk = load_notes(current, key)
+            .context("The current vault does not decrypt with this password; nothing was moved")?;
+        if on_disk.len() != notes.len() {
+            return Err(anyhow!(
+                "The current vault holds {} note(s) but {} are loaded; nothing was moved",
+                on_disk.len(),
+                notes.len()
+            ));
+        }
+    }
+
+    save_notes(target, notes, key).with_context(|| format!("Could not write {target}"))?;
+    let verified = load_notes(target, key).is_ok_and(|copy| {
+        copy.len() == notes.len() && copy.iter().zip(notes).all(|(a, b)| a.id == b.id)
+    });
+    if !verified {
+        let _ = std::fs::remove_file(target);
+        return Err(anyhow!(
+            "{target} could not be read back; the old vault is unchanged"
+        ));
+    }
+
+    if delete_old && old_exists {
+        std::fs::remove_file(current).with_context(|| {
+            format!("Vault copied to {target}, but {current} could not be deleted")
+        })?;
+        Ok(format!("Vault moved to {target}."))
+    } else if old_exists {
+        Ok(format!("Vault copied to {target}; {current} was kept."))
+    } else {
+        Ok(format!("Vault will now be saved to {target}."))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Plaintext Import
+////////////////////////////////////////////////////////////////////////////////
+
+/// Files larger than this are imported with a warning.
+const IMPORT_WARN_BYTES: u64 = 1024 * 1024;
+
+/// Files larger than this are skipped to keep the vault manageable.
+const IMPORT_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Outcome of a plaintext import: the new (sealed) notes plus anything worth telling the user.
+#[derive(Default)]
+struct ImportReport {
+    imported: Vec<SealedNote>,
+    skipped: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl ImportReport {
+    fn summary(&self) -> String {
+        let mut parts = vec![format!("Imported {} note(s).", self.imported.len())];
+        if !self.skipped.is_empty() {
+            parts.push(format!("Skipped: {}.", self.skipped.join("; ")));
+        }
+        if !self.warnings.is_empty() {
+            parts.push(format!("Warning: {}.", self.warnings.join("; ")));
+        }
+        parts.join(" ")
+    }
+}
+
+/// Expands a leading `~` to the user's home directory.
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{home}{rest}")
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// Reads every file matching `pattern` (a path or glob) and seals each one as
+/// a new note titled after its file name. Binary files (NUL bytes or invalid
+/// UTF-8), oversized and unreadable files are skipped.
+fn import_plaintext_notes(pattern: &str, key: &[u8]) -> Result<ImportReport> {
+    if pattern.is_empty() {
+        return Err(anyhow!("No path given"));
+    }
+    let mut paths: Vec<_> = glob::glob(pattern)
+        .context("Invalid path or glob pattern")?
+        .filter_map(|p| p.ok())
+        .filter(|p| p.is_file())
+        .collect();
+    paths.sort();
+    if paths.is_empty() {
+        return Err(anyhow!("No files match {pattern}"));
+    }
+
+    let mut report = ImportReport::default();
+    for path in paths {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+
+        // One unreadable file is reported and skipped, not fatal to the batch
+        let size = match std::fs::metadata(&path) {
+            Ok(meta) => meta.len(),
+            Err(e) => {
+                report.skipped.push(format!("{name} (unreadable: {e})"));
+                continue;
+            }
+        };
+        if size > IMPORT_MAX_BYTES {
+            report.skipped.push(format!(
+                "{name} (too large, {:.1} MB)",
+                size as f64 / 1_048_576.0
+            ));
+            continue;
+        }
+
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => Zeroizing::new(bytes),
+            Err(e) => {
+                report.skipped.push(format!("{name} (unreadable: {e})"));
+                continue;
+            }
+        };
+        if bytes.contains(&0) {
+            report.skipped.push(format!("{name} (binary)"));
+            continue;
+        }
+        let Ok(text) = std::str::from_utf8(&bytes) else {
+            report.skipped.push(format!("{name} (not UTF-8 text)"));
+            continue;
+        };
+        if size > IMPORT_WARN_BYTES {
+            report.warnings.push(format!(
+                "{name} is large ({:.1} MB)",
+                size as f64 / 1_048_576.0
+            ));
+        }
+
+        let note = Note {
+            id: generate_user_friendly_id(),
+            title: name,
+            content: text.to_string(),
+            history: Vec::new(),
+        };
+        report.imported.push(seal_note(&note, key)?);
+    }
+    Ok(report)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Vault Verification (--verify)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Result of checking a vault without opening it in the TUI.
+enum VaultCheck {
+    /// Decrypted and parsed; holds the note count
+    Ok(usize),
+    /// No file at the given path
+    Missing,
+    /// The file exists but could not be read
+    Unreadable(String),
+    /// Authentication failed. With an AEAD cipher a wrong password and a
+    /// modified file look identical, so both end up here.
+    WrongPasswordOrTampered,
+    /// Decrypted fine but the contents are not a notes list, or the file is
+    /// too short to have been written by this program
+    Corrupt(String),
+}
+
+impl VaultCheck {
+    /// Process exit code (1 is left for unexpected errors).
+    fn exit_code(&self) -> i32 {
+        match self {
+            VaultCheck::Ok(_) => 0,
+            VaultCheck::Missing => 2,
+            VaultCheck::WrongPasswordOrTampered => 3,
+            VaultCheck::Unreadable(_) | VaultCheck::Corrupt(_) => 4,
+        }
+    }
+
+    fn message(&self, path: &str) -> String {
+        match self {
+            VaultCheck::Ok(count) => format!("OK, {count} notes"),
+            VaultCheck::Missing => format!("Error: vault {path:?} does not exist"),
+            VaultCheck::Unreadable(e) => format!("Error: cannot read {path:?}: {e}"),
+            VaultCheck::WrongPasswordOrTampered => {
+                "Error: wrong password, or the vault file has been modified".to_string()
+            }
+            VaultCheck::Corrupt(e) => format!("Error: vault is corrupt: {e}"),
+        }
+    }
+}
+
+/// Prompts for the password, checks the vault, prints the outcome, and
+/// returns the exit code.
+fn run_verify(path: &str) -> Result<i32> {
+    // Don't ask for a password just to report a missing file
+    let check = if !Path::new(path).exists() {
+        VaultCheck::Missing
+    } else {
+        let password = read_password("Password: ")?;
+        let key = Zeroizing::new(derive_key_from_password(
+            &password,
+            SALT,
+            PBKDF2_ITERATIONS,
+        )?);
+        verify_vault(path, &key[..])
+    };
+
+    let message = check.message(path);
+    if matches!(check, VaultCheck::Ok(_)) {
+        println!("{message}");
+    } else {
+        eprintln!("{message}");
+    }
+    Ok(check.exit_code())
+}
+
+/// Reads and decrypts the vault in stages, so each kind of failure can be told apart.
+fn verify_vault(path: &str, key: &[u8]) -> VaultCheck {
+    let ciphertext = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return VaultCheck::Missing,
+        Err(e) => return VaultCheck::Unreadable(e.to_string()),
+    };
+    // 12-byte nonce plus 16-byte authentication tag
+    if ciphertext.len() < 12 + aead::CHACHA20_POLY1305.tag_len() {
+        return VaultCheck::Corrupt(format!(
+            "file is only {} bytes, too short to be a vault",
+            ciphertext.len()
+        ));
+    }
+    let decrypted = match decrypt_data(&ciphertext, key) {
+        Ok(bytes) => Zeroizing::new(bytes),
+        Err(_) => return VaultCheck::WrongPasswordOrTampered,
+    };
+    match parse_notes(&decrypted) {
+        Ok(notes) => VaultCheck::Ok(notes.len()),
+        Err(e) => VaultCheck::Corrupt(format!("decrypted, but not a notes list ({e})")),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Quick Notes (--quick)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Prompts for the password, appends a note holding `text` to the vault, and
+/// saves it. A vault that doesn't decrypt is left untouched, so a mistyped
+/// password can never replace it with a one-note vault.
+fn run_quick_note(path: &str, text: &str) -> Result<()> {
+    if text.trim().is_empty() {
+        return Err(anyhow!("Nothing to save: the note text is empty"));
+    }
+    let exists = Path::new(path).exists();
+    let password = read_password("Password: ")?;
+    // A new vault is locked with this password from now on; make sure of it
+    if !exists && io::stdin().is_terminal() {
+        eprintln!("No vault at {path:?} yet; a new one will be created.");
+        if read_password("Confirm password: ")? != password {
+            return Err(anyhow!("Passwords do not match; nothing was saved"));
+        }
+    }
+    let key = Zeroizing::new(derive_key_from_password(
+        &password,
+        SALT,
+        PBKDF2_ITERATIONS,
+    )?);
+    drop(password);
+
+    let mut notes = load_notes(path, &key[..]).with_context(|| {
+        format!("Could not open {path:?} (wrong password, or the file has been modified); nothing was saved")
+    })?;
+    let note = Note {
+        id: generate_user_friendly_id(),
+        title: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        content: text.to_string(),
+        history: Vec::new(),
+    };
+    notes.push(seal_note(&note, &key[..])?);
+    save_notes(path, &notes, &key[..]).with_context(|| format!("Failed to save {path:?}"))?;
+    println!("Added note {} ({} notes in {path})", note.id, notes.len());
+    Ok(())
+}
+
+/// Reads a password without echoing it. When stdin is not a terminal (e.g. a
+/// script piping it in), the first line of stdin is used instead.
+fn read_password(prompt: &str) -> Result<Zeroizing<String>> {
+    let mut password = Zeroizing::new(String::new());
+    if !io::stdin().is_terminal() {
+        io::stdin().read_line(&mut password)?;
+        let trimmed = password.trim_end_matches(['\r', '\n']).len();
+        password.truncate(trimmed);
+        return Ok(password);
+    }
+
+    eprint!("{prompt}");
+    io::stderr().flush()?;
+    let guard = RawModeGuard::new()?;
+    loop {
+        if let Event::Key(key) = event::read()? {
+            match key.code {
+                KeyCode::Enter => break,
+                KeyCode::Backspace => {
+                    password.pop();
+                }
+                KeyCode::Esc => {
+                    drop(guard);
+                    eprint!("{LINE_ENDING}");
+                    return Err(anyhow!("Password entry cancelled"));
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    drop(guard);
+                    eprint!("{LINE_ENDING}");
+                    return Err(anyhow!("Password entry cancelled"));
+                }
+                KeyCode::Char(c) => password.push(c),
+                _ => {}
+            }
+        }
+    }
+    drop(guard);
+    eprint!("{LINE_ENDING}");
+    Ok(password)
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Encryption + Persistence
 ////////////////////////////////////////////////////////////////////////////////
@@ -747,8 +2186,8 @@ fn encrypt_data(plaintext: &[u8], key: &[u8]) -> Result<Vec<u8>> {
         .expose();
     let nonce = aead::Nonce::assume_unique_for_key(nonce_bytes);
 
+    // `seal_in_place_append_tag` appends the tag itself, so no padding is needed.
     let mut in_out = plaintext.to_vec();
-    in_out.resize(in_out.len() + sealing_key.algorithm().tag_len(), 0);
     sealing_key
         .seal_in_place_append_tag(nonce, aead::Aad::empty(), &mut in_out)
         .map_err(|_| anyhow!("Encryption failed"))?;
@@ -772,27 +2211,95 @@ fn decrypt_data(ciphertext: &[u8], key: &[u8]) -> Result<Vec<u8>> {
             .map_err(|_| anyhow!("Failed to create decryption key"))?,
     );
 
+    // Decrypt in place and hand back the same buffer, so no stray plaintext copy is left behind.
     let mut in_out = encrypted.to_vec();
-    let decrypted_data = opening_key
+    let plaintext_len = opening_key
         .open_in_place(nonce, aead::Aad::empty(), &mut in_out)
-        .map_err(|_| anyhow!("Decryption failed"))?;
-    Ok(decrypted_data.to_vec())
+        .map_err(|_| anyhow!("Decryption failed"))?
+        .len();
+    in_out.truncate(plaintext_len);
+    Ok(in_out)
+}
+
+/// Encrypts a note's content with the session key for in-memory storage.
+fn seal_note(note: &Note, key: &[u8]) -> Result<SealedNote> {
+    Ok(SealedNote {
+        id: note.id.clone(),
+        title: note.title.clone(),
+        sealed_content: encrypt_data(note.content.as_bytes(), key)?,
+        history: note
+            .history
+            .iter()
+            .map(|v| {
+                Ok(SealedVersion {
+                    replaced_at: v.replaced_at.clone(),
+                    sealed_content: encrypt_data(v.content.as_bytes(), key)?,
+                })
+            })
+            .collect::<Result<_>>()?,
+    })
 }
 
-fn load_notes<P: AsRef<Path>>(path: P, key: &[u8]) -> Result<Vec<Note>> {
+/// Decrypts a sealed history entry (the same sealing as note content).
+fn unseal_version(version: &SealedVersion, key: &[u8]) -> Result<Zeroizing<String>> {
+    let bytes = Zeroizing::new(decrypt_data(&version.sealed_content, key)?);
+    let content = std::str::from_utf8(&bytes).context("Note version is not valid UTF-8")?;
+    Ok(Zeroizing::new(content.to_string()))
+}
+
+/// Decrypts one note's content; the plaintext is wiped when the result is dropped.
+fn unseal_content(note: &SealedNote, key: &[u8]) -> Result<Zeroizing<String>> {
+    let bytes = Zeroizing::new(decrypt_data(&note.sealed_content, key)?);
+    let content = std::str::from_utf8(&bytes).context("Note content is not valid UTF-8")?;
+    Ok(Zeroizing::new(content.to_string()))
+}
+
+/// Loads the notes file and re-seals each note individually, so the decrypted
+/// file contents are wiped before this returns.
+fn load_notes<P: AsRef<Path>>(path: P, key: &[u8]) -> Result<Vec<SealedNote>> {
     if !path.as_ref().exists() {
         return Ok(Vec::new()); // No file yet -> empty list
     }
     let mut file = OpenOptions::new().read(true).open(path)?;
     let mut ciphertext = Vec::new();
     file.read_to_end(&mut ciphertext)?;
-    let decrypted_bytes = decrypt_data(&ciphertext, key)?;
-    let notes: Vec<Note> = serde_json::from_slice(&decrypted_bytes)?;
-    Ok(notes)
+    let decrypted_bytes = Zeroizing::new(decrypt_data(&ciphertext, key)?);
+    let notes = parse_notes(&decrypted_bytes)?;
+    notes.iter().map(|note| seal_note(note, key)).collect()
+}
+
+/// Parses decrypted vault contents into notes.
+fn parse_notes(decrypted: &[u8]) -> Result<Vec<Note>> {
+    // Files written by older versions carry zero-byte padding after the JSON.
+    let json_len = decrypted.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    Ok(serde_json::from_slice(&decrypted[..json_len])?)
 }
 
-fn save_notes<P: AsRef<Path>>(path: P, notes: &[Note], key: &[u8]) -> Result<()> {
-    let json_data = serde_json::to_vec(notes)?;
+/// Writes all notes as one encrypted blob (the on-disk format is unchanged).
+/// Each note is decrypted only for the duration of the write.
+fn save_notes<P: AsRef<Path>>(path: P, notes: &[SealedNote], key: &[u8]) -> Result<()> {
+    let plaintext = notes
+        .iter()
+        .map(|n| {
+            Ok(Note {
+                id: n.id.clone(),
+                title: n.title.clone(),
+                content: unseal_content(n, key)?.to_string(),
+                history: n
+                    .history
+                    .iter()
+                    .map(|v| {
+                        Ok(NoteVersion {
+                            replaced_at: v.replaced_at.clone(),
+                            content: unseal_version(v, key)?.to_string(),
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            })
+        })
+        .collect::<Result<Vec<Note>>>()?;
+    let json_data = Zeroizing::new(serde_json::to_vec(&plaintext)?);
+    drop(plaintext);
     let ciphertext = encrypt_data(&json_data, key)?;
     let mut file = OpenOptions::new()
         .write(true)
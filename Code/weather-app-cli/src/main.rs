@@ -2,8 +2,17 @@ use anyhow::{anyhow, Result};
 use reqwest::blocking::Client;
 use serde::Deserialize;
 use std::env;
+use std::fmt;
+use std::thread;
+use std::time::Duration;
 use chrono::{TimeZone, Utc};
 
+/// How many times a request is attempted before giving up on transient errors.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled for each further attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
 /// Full response from OpenWeatherMap (partial subset of fields).
 #[derive(Debug, Deserialize)]
 struct WeatherResponse {
@@ -55,6 +64,62 @@ struct SysData {
     sunset: Option<u64>,
 }
 
+/// Why a weather request failed. Each kind exits with its own code so
+/// scripts can tell a bad key from a typo or a network outage.
+#[derive(Debug)]
+enum FetchError {
+    /// 401: the API key was rejected
+    InvalidApiKey,
+    /// 404: OpenWeatherMap has no such city
+    CityNotFound(String),
+    /// The request never got a response (DNS, connection, timeout)
+    Network(String),
+    /// Any other HTTP error status, or a 5xx that persisted through the retries
+    Http(reqwest::StatusCode),
+    /// The response body was not the JSON we expected
+    InvalidResponse(String),
+}
+
+impl FetchError {
+    /// Process exit code for this failure (1 is left for usage/setup errors).
+    fn exit_code(&self) -> i32 {
+        match self {
+            FetchError::InvalidApiKey => 2,
+            FetchError::CityNotFound(_) => 3,
+            FetchError::Network(_) => 4,
+            FetchError::Http(_) | FetchError::InvalidResponse(_) => 5,
+        }
+    }
+
+    /// Whether trying again might succeed.
+    fn is_transient(&self) -> bool {
+        match self {
+            FetchError::Network(_) => true,
+            FetchError::Http(status) => {
+                status.is_server_error() || *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::InvalidApiKey => write!(
+                f,
+                "The API key was rejected (401). Check OWM_API_KEY; new keys can take a couple of hours to activate."
+            ),
+            FetchError::CityNotFound(city) => write!(f, "City not found (404): {:?}", city),
+            FetchError::Network(e) => write!(f, "Network error: {}", e),
+            FetchError::Http(status) => write!(f, "OpenWeatherMap returned HTTP {}", status),
+            FetchError::InvalidResponse(e) => write!(f, "Failed to parse the response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
 fn main() -> Result<()> {
     // Load .env if available (comment out if using system environment variables)
     dotenv::dotenv().ok();
@@ -71,8 +136,14 @@ fn main() -> Result<()> {
     }
     let city_name = &args[1];
 
-    // Fetch weather data
-    let weather = fetch_weather(city_name, &api_key)?;
+    // Fetch weather data; each kind of failure gets its own exit code
+    let weather = match fetch_weather(city_name, &api_key) {
+        Ok(weather) => weather,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    };
 
     // Print general weather info
     println!(
@@ -147,8 +218,9 @@ fn main() -> Result<()> {
 ///
 /// # Errors
 ///
-/// Returns an `anyhow::Error` if the request fails or if the JSON is invalid.
-fn fetch_weather(city: &str, api_key: &str) -> Result<WeatherResponse> {
+/// Returns a `FetchError` describing the failure. Network errors, 5xx and 429
+/// responses are retried up to `MAX_ATTEMPTS` times with a growing delay.
+fn fetch_weather(city: &str, api_key: &str) -> Result<WeatherResponse, FetchError> {
     let client = Client::new();
 
     // Construct the request URL with "imperial" unit system
@@ -157,17 +229,42 @@ fn fetch_weather(city: &str, api_key: &str) -> Result<WeatherResponse> {
         city, api_key
     );
 
-    // Perform the GET request and parse JSON
+    let mut delay = RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        match fetch_once(&client, &url, city) {
+            Err(e) if e.is_transient() && attempt < MAX_ATTEMPTS => {
+                eprintln!(
+                    "{} - retrying in {}s (attempt {} of {})",
+                    e,
+                    delay.as_secs(),
+                    attempt + 1,
+                    MAX_ATTEMPTS
+                );
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Performs a single GET request and classifies any failure.
+fn fetch_once(client: &Client, url: &str, city: &str) -> Result<WeatherResponse, FetchError> {
     let resp = client
-        .get(&url)
+        .get(url)
         .send()
-        .map_err(|e| anyhow!("Failed to send request: {}", e))?
-        .error_for_status() // convert HTTP errors into a Rust error
-        .map_err(|e| anyhow!("Received an error HTTP status code: {}", e))?
-        .json::<WeatherResponse>()
-        .map_err(|e| anyhow!("Failed to parse JSON: {}", e))?;
+        .map_err(|e| FetchError::Network(e.without_url().to_string()))?;
 
-    Ok(resp)
+    match resp.status() {
+        status if status.is_success() => resp
+            .json::<WeatherResponse>()
+            .map_err(|e| FetchError::InvalidResponse(e.without_url().to_string())),
+        reqwest::StatusCode::UNAUTHORIZED => Err(FetchError::InvalidApiKey),
+        reqwest::StatusCode::NOT_FOUND => Err(FetchError::CityNotFound(city.to_string())),
+        status => Err(FetchError::Http(status)),
+    }
 }
 
 /// Helper function to format a Unix timestamp into a readable UTC time without deprecation warnings.
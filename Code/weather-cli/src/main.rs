@@ -7,18 +7,21 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use anyhow::{Context, Result};
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, Local, TimeZone, Utc};
 use clap::Parser;
 use dotenv::dotenv;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{
-    env,
-    io::{self, Write},
+    collections::BTreeMap,
+    env, fs,
+    io::{self, IsTerminal, Write},
+    path::PathBuf,
 };
 
 use crossterm::{
     cursor::MoveTo,
+    event::{self, Event, KeyCode, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType},
 };
@@ -28,7 +31,7 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap},
     Frame, Terminal,
 };
 
@@ -42,6 +45,34 @@ const LINE_ENDING: &str = "\r\n";
 #[cfg(not(windows))]
 const LINE_ENDING: &str = "\n";
 
+////////////////////////////////////////////////////////////////////////////////
+// Location History Settings
+////////////////////////////////////////////////////////////////////////////////
+
+/// File (in the home directory) holding recently queried locations, one per line.
+const HISTORY_FILE: &str = ".weather_cli_history";
+
+/// Maximum number of locations kept in the history.
+const HISTORY_LIMIT: usize = 10;
+
+////////////////////////////////////////////////////////////////////////////////
+// API Rate Limit Settings
+////////////////////////////////////////////////////////////////////////////////
+
+/// File (in the home directory) counting the API calls made this minute and today.
+const RATE_STATE_FILE: &str = ".weather_cli_rate.json";
+
+/// Fraction of a limit at which a warning is printed before fetching.
+const RATE_WARN_FRACTION: f64 = 0.8;
+
+/// API calls `fetch_weather` makes for one location: the current weather and
+/// the first forecast step (for the chance of rain), or with `--onecall` the
+/// geocoding lookup and One Call.
+const FIRST_FETCH_CALLS: u32 = 2;
+
+/// 3-hour forecast steps fetched for the Forecast tab (8 steps = 24 hours).
+const FORECAST_STEPS: usize = 8;
+
 ////////////////////////////////////////////////////////////////////////////////
 // CLI Arguments
 ////////////////////////////////////////////////////////////////////////////////
@@ -60,58 +91,317 @@ struct Cli {
     /// Units of measurement: "metric", "imperial", or "standard"
     #[arg(short, long, default_value = "imperial")]
     units: String,
+
+    /// A second location to show side by side with the first
+    #[arg(long, value_name = "LOCATION")]
+    compare: Option<String>,
+
+    /// Print plain text instead of the TUI (automatic when stdout is not a terminal)
+    #[arg(long)]
+    plain: bool,
+
+    /// Print the weather as JSON (implies --plain)
+    #[arg(long)]
+    json: bool,
+
+    /// Show every temperature in plain blue instead of the cold-to-hot gradient
+    #[arg(long)]
+    no_temp_colors: bool,
+
+    /// API calls allowed per minute before fetching is refused (0 disables the check)
+    #[arg(long, default_value_t = 60)]
+    minute_limit: u32,
+
+    /// API calls allowed per UTC day before fetching is refused (0 disables the check)
+    #[arg(long, default_value_t = 1000)]
+    daily_limit: u32,
+
+    /// Fetch current conditions, hourly and daily forecasts, and alerts in one
+    /// One Call 3.0 request (requires a One Call subscription)
+    #[arg(long)]
+    onecall: bool,
 }
 
 ////////////////////////////////////////////////////////////////////////////////
-// JSON Models for Deserialization
+// JSON Models (deserialized from the API, re-serialized for --json)
 ////////////////////////////////////////////////////////////////////////////////
 
-#[derive(Debug, Deserialize)]
+/// Every field may be missing: some endpoints and plans send sparse responses,
+/// and the display shows "N/A" for what isn't there.
+#[derive(Debug, Deserialize, Serialize)]
 struct WeatherResponse {
     coord: Option<Coord>,
+    #[serde(default)]
     weather: Vec<WeatherDescription>,
+    #[serde(default)]
     main: MainData,
     wind: Option<WindData>,
+    rain: Option<PrecipitationData>,
+    snow: Option<PrecipitationData>,
     sys: Option<SysData>,
+    #[serde(default)]
     name: String,
+    /// Probability of precipitation from the forecast endpoint (0.0..=1.0)
+    #[serde(skip_deserializing)]
+    pop: Option<f64>,
+    /// Upcoming 3-hour steps from the forecast endpoint
+    #[serde(skip_deserializing)]
+    forecast: Vec<ForecastEntry>,
+    /// Current air quality from the air pollution endpoint
+    #[serde(skip_deserializing)]
+    air_quality: Option<AirQuality>,
+    /// Daily forecasts, only available from One Call (`--onecall`)
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    daily: Vec<DailyForecast>,
+    /// Active weather alerts, only available from One Call (`--onecall`)
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    alerts: Vec<WeatherAlert>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct Coord {
     lon: f64,
     lat: f64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct WeatherDescription {
     main: String,
     description: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 struct MainData {
-    temp: f64,
+    temp: Option<f64>,
     feels_like: Option<f64>,
     temp_min: Option<f64>,
     temp_max: Option<f64>,
     pressure: Option<f64>,
-    humidity: f64,
+    humidity: Option<f64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct WindData {
-    speed: f64,
+    speed: Option<f64>,
     gust: Option<f64>,
     deg: Option<f64>,
 }
 
+/// Rain or snow volume in millimeters over the last 1 and 3 hours.
+#[derive(Debug, Deserialize, Serialize)]
+struct PrecipitationData {
+    #[serde(rename = "1h")]
+    one_hour: Option<f64>,
+    #[serde(rename = "3h")]
+    three_hours: Option<f64>,
+}
+
+/// The few forecast fields we use from each 3-hour step.
 #[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    list: Vec<ForecastEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ForecastEntry {
+    /// Start of the step (Unix time)
+    dt: u64,
+    main: ForecastMain,
+    #[serde(default)]
+    weather: Vec<WeatherDescription>,
+    pop: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct ForecastMain {
+    temp: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AirPollutionResponse {
+    list: Vec<AirQuality>,
+}
+
+/// Air quality index (1 = good .. 5 = very poor) and pollutant
+/// concentrations in μg/m³, keyed by OpenWeatherMap's names (`pm2_5`, `o3`, ...).
+#[derive(Debug, Deserialize, Serialize)]
+struct AirQuality {
+    main: AirQualityIndex,
+    #[serde(default)]
+    components: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct AirQualityIndex {
+    aqi: u8,
+}
+
+/// Shown in place of a value the API didn't send.
+const NOT_AVAILABLE: &str = "N/A";
+
+impl WeatherResponse {
+    /// City name, or "N/A" when the response has none.
+    fn location(&self) -> &str {
+        if self.name.is_empty() {
+            NOT_AVAILABLE
+        } else {
+            &self.name
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 struct SysData {
     country: Option<String>,
     sunrise: Option<u64>,
     sunset: Option<u64>,
 }
 
+/// A place found by the geocoding API, which One Call needs for its coordinates.
+#[derive(Debug, Deserialize)]
+struct GeoLocation {
+    #[serde(default)]
+    name: String,
+    lat: f64,
+    lon: f64,
+    country: Option<String>,
+}
+
+/// Everything One Call 3.0 returns for a location in a single response.
+#[derive(Debug, Deserialize)]
+struct OneCallResponse {
+    lat: f64,
+    lon: f64,
+    current: Option<OneCallCurrent>,
+    /// Hourly steps for the next 48 hours, starting with the current hour
+    #[serde(default)]
+    hourly: Vec<OneCallHourly>,
+    #[serde(default)]
+    daily: Vec<DailyForecast>,
+    #[serde(default)]
+    alerts: Vec<WeatherAlert>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OneCallCurrent {
+    sunrise: Option<u64>,
+    sunset: Option<u64>,
+    temp: Option<f64>,
+    feels_like: Option<f64>,
+    pressure: Option<f64>,
+    humidity: Option<f64>,
+    wind_speed: Option<f64>,
+    wind_gust: Option<f64>,
+    wind_deg: Option<f64>,
+    #[serde(default)]
+    weather: Vec<WeatherDescription>,
+    rain: Option<PrecipitationData>,
+    snow: Option<PrecipitationData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OneCallHourly {
+    dt: u64,
+    temp: Option<f64>,
+    #[serde(default)]
+    weather: Vec<WeatherDescription>,
+    pop: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct DailyForecast {
+    /// Midday of the forecast day (Unix time)
+    dt: u64,
+    temp: Option<DailyTemperature>,
+    #[serde(default)]
+    weather: Vec<WeatherDescription>,
+    pop: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct DailyTemperature {
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+/// A warning from a national weather service, e.g. a wind or flood alert.
+#[derive(Debug, Deserialize, Serialize)]
+struct WeatherAlert {
+    #[serde(default)]
+    sender_name: String,
+    event: String,
+    start: u64,
+    end: u64,
+    #[serde(default)]
+    description: String,
+}
+
+impl WeatherResponse {
+    /// Puts a One Call response into the shape the views use. The hourly steps
+    /// are thinned to every third hour so the Forecast tab reads the same as
+    /// with the 3-hour forecast endpoint.
+    fn from_one_call(place: GeoLocation, one_call: OneCallResponse) -> Self {
+        let current = one_call.current;
+        let forecast: Vec<ForecastEntry> = one_call
+            .hourly
+            .into_iter()
+            .step_by(3)
+            .take(FORECAST_STEPS)
+            .map(|hour| ForecastEntry {
+                dt: hour.dt,
+                main: ForecastMain { temp: hour.temp },
+                weather: hour.weather,
+                pop: hour.pop,
+            })
+            .collect();
+        let (main, wind, sys) = match &current {
+            Some(now) => (
+                MainData {
+                    temp: now.temp,
+                    feels_like: now.feels_like,
+                    temp_min: None,
+                    temp_max: None,
+                    pressure: now.pressure,
+                    humidity: now.humidity,
+                },
+                Some(WindData {
+                    speed: now.wind_speed,
+                    gust: now.wind_gust,
+                    deg: now.wind_deg,
+                }),
+                Some(SysData {
+                    country: place.country,
+                    sunrise: now.sunrise,
+                    sunset: now.sunset,
+                }),
+            ),
+            None => (MainData::default(), None, None),
+        };
+        let (weather, rain, snow) = match current {
+            Some(now) => (now.weather, now.rain, now.snow),
+            None => (Vec::new(), None, None),
+        };
+        Self {
+            coord: Some(Coord {
+                lon: one_call.lon,
+                lat: one_call.lat,
+            }),
+            weather,
+            main,
+            wind,
+            rain,
+            snow,
+            sys,
+            name: place.name,
+            pop: forecast.first().and_then(|step| step.pop),
+            forecast,
+            air_quality: None,
+            daily: one_call.daily,
+            alerts: one_call.alerts,
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Main (Tokio) Entry Point
 ////////////////////////////////////////////////////////////////////////////////
@@ -124,6 +414,11 @@ async fn main() -> Result<()> {
     let api_key = env::var("OWM_API_KEY")
         .context("Environment variable OWM_API_KEY not set. Please set it or store it in .env.")?;
 
+    // Scripts and pipes get plain output: no raw mode, no TUI
+    if args.plain || args.json || !io::stdout().is_terminal() {
+        return run_plain(&args, &api_key).await;
+    }
+
     // 1) Enable raw mode automatically via RAII guard
     let _raw_guard = RawModeGuard::new().context("Failed to enable raw mode")?;
 
@@ -134,15 +429,22 @@ async fn main() -> Result<()> {
     // 3) Draw the Ratatui “Welcome” screen
     draw_welcome_screen(&mut terminal)?;
 
-    // 4) Temporarily drop raw mode to allow normal keyboard input
+    // 4) Without a location argument, offer recently queried locations first
+    let mut history = load_history();
+    let recent = match &args.location {
+        None if !history.is_empty() => select_recent_location(&mut terminal, &history)?,
+        _ => None,
+    };
+
+    // 5) Temporarily drop raw mode to allow normal keyboard input
     drop(_raw_guard);
 
     // Extra blank lines for a neat console prompt (below the TUI)
     println!("{}", LINE_ENDING);
     println!("{}", LINE_ENDING);
 
-    // 5) If user didn’t pass an input argument, prompt them for a location
-    let location = match args.location {
+    // 6) If user didn’t pass or pick a location, prompt them for one
+    let location = match args.location.clone().or(recent) {
         Some(loc) => loc,
         None => {
             print!("Enter a city name or ZIP code: {}", LINE_ENDING);
@@ -161,38 +463,146 @@ async fn main() -> Result<()> {
         }
     };
 
-    // 6) Fetch weather data
-    let weather = if is_numeric(&location) {
-        fetch_weather_zip(&location, &args.country, &api_key, &args.units).await?
-    } else {
-        fetch_weather_city(&location, &args.country, &api_key, &args.units).await?
-    };
+    // 7) Fetch weather data (both locations in compare mode)
+    let mut results = fetch_results(&args, location, &api_key, &[Tab::Current]).await?;
 
-    // 7) Re-enable raw mode for the final TUI
+    // 8) Remember every location that resolved successfully (history is best-effort)
+    remember_results(&mut history, &results);
+
+    // 9) Re-enable raw mode for the final TUI
     let _raw_guard = RawModeGuard::new().context("Failed to re-enable raw mode")?;
 
-    // 8) Re-create the terminal, clear screen, and draw weather info
+    // 10) Re-create the terminal and show the weather until the user leaves
+    let temp_colors = (!args.no_temp_colors).then_some(args.units.as_str());
     let mut terminal = setup_terminal().context("Failed to create terminal")?;
     clear_screen(&mut terminal).context("Failed to clear terminal")?;
-    draw_weather_info(&mut terminal, &weather)?;
+    run_weather_view(&mut terminal, &mut results, temp_colors, &args, &api_key).await?;
 
-    // 9) Disable raw mode so user can press Enter, then exit
+    // 11) Disable raw mode again
     drop(_raw_guard);
 
-    println!("{}", LINE_ENDING); // Extra blank line
-    print!("Press Enter to exit...{}", LINE_ENDING);
-    io::stdout().flush()?;
-
-    let mut exit_buf = String::new();
-    io::stdin().read_line(&mut exit_buf)?;
-
-    // 10) Final cleanup: clear screen, print goodbye
+    // 12) Final cleanup: clear screen, print goodbye
     execute!(terminal.backend_mut(), Clear(ClearType::All), MoveTo(0, 0))?;
     print!("Goodbye!{}", LINE_ENDING);
 
     Ok(())
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Fetching + Plain (non-TTY) Output
+////////////////////////////////////////////////////////////////////////////////
+
+/// Fetches the requested location, plus the `--compare` location if given,
+/// along with the data for `tabs`. In compare mode both are fetched
+/// concurrently and each keeps its own result, so one failure doesn't hide
+/// the other.
+async fn fetch_results(
+    args: &Cli,
+    location: String,
+    api_key: &str,
+    tabs: &[Tab],
+) -> Result<Vec<(String, Result<WeatherResponse>)>> {
+    let locations = if args.compare.is_some() { 2 } else { 1 };
+    let calls =
+        locations * (FIRST_FETCH_CALLS + tabs.iter().map(|t| tab_calls(*t, args)).sum::<u32>());
+    if let Some(warning) = reserve_api_calls(args, calls)? {
+        eprint!("Warning: {}{}", warning, LINE_ENDING);
+    }
+
+    let fetch = |location: &str| {
+        let location = location.to_string();
+        async move {
+            let mut weather = fetch_weather(&location, args, api_key).await?;
+            for tab in tabs {
+                fetch_tab_data(&mut weather, *tab, args, api_key).await;
+            }
+            Ok(weather)
+        }
+    };
+    Ok(match &args.compare {
+        Some(other) => {
+            let (first, second) = tokio::join!(fetch(&location), fetch(other));
+            vec![(location, first), (other.clone(), second)]
+        }
+        None => {
+            let weather = fetch(&location).await?;
+            vec![(location, Ok(weather))]
+        }
+    })
+}
+
+/// Adds every successfully fetched location to the history and saves it.
+fn remember_results(history: &mut Vec<String>, results: &[(String, Result<WeatherResponse>)]) {
+    for (name, result) in results.iter().rev() {
+        if result.is_ok() {
+            remember_location(history, name);
+        }
+    }
+    let _ = save_history(history);
+}
+
+/// Prints the weather as text (or JSON) without touching raw mode or the TUI.
+/// Without a location argument, the most recent location from history is used.
+async fn run_plain(args: &Cli, api_key: &str) -> Result<()> {
+    let mut history = load_history();
+    let location = args
+        .location
+        .clone()
+        .or_else(|| history.first().cloned())
+        .context("No location given and no recent location to fall back on")?;
+
+    // There are no tabs to wait for here, so everything is fetched up front
+    let results = fetch_results(args, location, api_key, &Tab::ALL).await?;
+    remember_results(&mut history, &results);
+
+    let mut out = io::stdout().lock();
+    if args.json {
+        let json = match results.as_slice() {
+            [(_, Ok(weather))] => serde_json::to_value(weather)?,
+            _ => serde_json::Value::Array(
+                results
+                    .iter()
+                    .map(|(location, result)| match result {
+                        Ok(weather) => {
+                            serde_json::json!({ "location": location, "weather": weather })
+                        }
+                        Err(e) => {
+                            serde_json::json!({ "location": location, "error": format!("{e:#}") })
+                        }
+                    })
+                    .collect(),
+            ),
+        };
+        write!(
+            out,
+            "{}{}",
+            serde_json::to_string_pretty(&json)?,
+            LINE_ENDING
+        )?;
+    } else {
+        for (location, result) in &results {
+            match result {
+                Ok(weather) => {
+                    for line in weather_lines(weather, None) {
+                        write!(out, "{}{}", line, LINE_ENDING)?;
+                    }
+                }
+                Err(e) => write!(
+                    out,
+                    "Could not fetch weather for {location}: {e:#}{LINE_ENDING}{LINE_ENDING}"
+                )?,
+            }
+        }
+    }
+    out.flush()?;
+
+    // Scripts rely on the exit code, so a run that got nothing at all fails
+    if results.iter().all(|(_, result)| result.is_err()) {
+        anyhow::bail!("Could not fetch weather for any location");
+    }
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // RAII guard for raw mode
 ////////////////////////////////////////////////////////////////////////////////
@@ -275,6 +685,219 @@ fn draw_welcome_screen(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>
     Ok(())
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// API Call Budget (local per-minute / per-day counter)
+////////////////////////////////////////////////////////////////////////////////
+
+/// Calls made in the current minute and UTC day, persisted between runs so
+/// back-to-back invocations share one budget.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct RateCounter {
+    /// Unix minute (seconds / 60) that `minute_calls` belongs to
+    minute: i64,
+    minute_calls: u32,
+    /// UTC date (YYYY-MM-DD) that `day_calls` belongs to
+    day: String,
+    day_calls: u32,
+}
+
+impl RateCounter {
+    /// Loads the counter; a missing or unreadable file starts from zero.
+    fn load() -> Self {
+        rate_state_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = rate_state_path().context("Could not determine home directory")?;
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Zeroes whichever counter belongs to an earlier minute or day.
+    fn roll_over(&mut self, now: DateTime<Utc>) {
+        let minute = now.timestamp().div_euclid(60);
+        if self.minute != minute {
+            self.minute = minute;
+            self.minute_calls = 0;
+        }
+        let day = now.format("%Y-%m-%d").to_string();
+        if self.day != day {
+            self.day = day;
+            self.day_calls = 0;
+        }
+    }
+
+    /// Counts `calls` against both limits. Refuses when either limit would be
+    /// exceeded, and returns a warning once a counter passes `RATE_WARN_FRACTION`.
+    fn reserve(
+        &mut self,
+        calls: u32,
+        minute_limit: u32,
+        daily_limit: u32,
+        now: DateTime<Utc>,
+    ) -> Result<Option<String>> {
+        self.roll_over(now);
+
+        if minute_limit > 0 && self.minute_calls + calls > minute_limit {
+            let wait = 60 - now.timestamp().rem_euclid(60);
+            anyhow::bail!(
+                "API rate limit: {}/{} calls already made this minute; try again in {}s",
+                self.minute_calls,
+                minute_limit,
+                wait
+            );
+        }
+        if daily_limit > 0 && self.day_calls + calls > daily_limit {
+            anyhow::bail!(
+                "API rate limit: {}/{} calls already made today; the counter resets at midnight UTC",
+                self.day_calls,
+                daily_limit
+            );
+        }
+
+        self.minute_calls += calls;
+        self.day_calls += calls;
+
+        let near = |used: u32, limit: u32| {
+            limit > 0 && f64::from(used) >= f64::from(limit) * RATE_WARN_FRACTION
+        };
+        Ok(if near(self.minute_calls, minute_limit) {
+            Some(format!(
+                "{}/{} API calls used this minute",
+                self.minute_calls, minute_limit
+            ))
+        } else if near(self.day_calls, daily_limit) {
+            Some(format!(
+                "{}/{} API calls used today (UTC)",
+                self.day_calls, daily_limit
+            ))
+        } else {
+            None
+        })
+    }
+}
+
+fn rate_state_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(RATE_STATE_FILE))
+}
+
+/// Reserves `calls` API calls in the local counter before any request is sent.
+/// The counter is only saved when the calls are allowed; failing to save it
+/// never blocks a fetch.
+fn reserve_api_calls(args: &Cli, calls: u32) -> Result<Option<String>> {
+    let mut counter = RateCounter::load();
+    let warning = counter.reserve(calls, args.minute_limit, args.daily_limit, Utc::now())?;
+    let _ = counter.save();
+    Ok(warning)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Recent Locations (history file + selection screen)
+////////////////////////////////////////////////////////////////////////////////
+
+fn history_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(HISTORY_FILE))
+}
+
+/// Loads the saved locations, most recent first. A missing file is an empty history.
+fn load_history() -> Vec<String> {
+    let Some(path) = history_path() else {
+        return Vec::new();
+    };
+    fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .take(HISTORY_LIMIT)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn save_history(history: &[String]) -> Result<()> {
+    let path = history_path().context("Could not determine home directory")?;
+    let mut contents = history.join(LINE_ENDING);
+    contents.push_str(LINE_ENDING);
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Moves `location` to the front of the history, dropping case-insensitive
+/// duplicates and trimming the list to `HISTORY_LIMIT`.
+fn remember_location(history: &mut Vec<String>, location: &str) {
+    let location = location.trim();
+    if location.is_empty() {
+        return;
+    }
+    history.retain(|entry| !entry.eq_ignore_ascii_case(location));
+    history.insert(0, location.to_string());
+    history.truncate(HISTORY_LIMIT);
+}
+
+/// Shows the recent locations and waits for a choice. Returns `None` when the
+/// user wants to type a new location instead.
+fn select_recent_location(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    history: &[String],
+) -> Result<Option<String>> {
+    let mut state = ListState::default().with_selected(Some(0));
+
+    loop {
+        terminal.draw(|frame| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(5), Constraint::Min(0)].as_ref())
+                .split(frame.area());
+
+            draw_banner(frame, chunks[0]);
+
+            let items: Vec<ListItem> = history
+                .iter()
+                .enumerate()
+                .map(|(i, loc)| ListItem::new(format!("{}) {}", i + 1, loc)))
+                .collect();
+            let list = List::new(items)
+                .block(
+                    Block::default()
+                        .title("Recent Locations (Enter/1-9=select, n/Esc=new location)")
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow)),
+                )
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .highlight_symbol(">> ");
+            frame.render_stateful_widget(list, centered_rect(60, 50, chunks[1]), &mut state);
+        })?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let selected = state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => state.select(Some(selected.saturating_sub(1))),
+            KeyCode::Down | KeyCode::Char('j') => {
+                state.select(Some((selected + 1).min(history.len() - 1)))
+            }
+            KeyCode::Enter => return Ok(history.get(selected).cloned()),
+            KeyCode::Char(c @ '1'..='9') => {
+                let index = c as usize - '1' as usize;
+                if let Some(loc) = history.get(index) {
+                    return Ok(Some(loc.clone()));
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Esc => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Draw a top banner
 ////////////////////////////////////////////////////////////////////////////////
@@ -338,6 +961,189 @@ fn is_numeric(s: &str) -> bool {
     s.chars().all(|c| c.is_ascii_digit())
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Fetch weather by ZIP or city, depending on the input
+////////////////////////////////////////////////////////////////////////////////
+
+/// Fetches what the Current tab shows; the other tabs' data is added by
+/// `fetch_tab_data` when it is needed.
+async fn fetch_weather(location: &str, args: &Cli, api_key: &str) -> Result<WeatherResponse> {
+    let (country, units) = (args.country.as_str(), args.units.as_str());
+    if args.onecall {
+        return fetch_one_call(location, country, api_key, units).await;
+    }
+
+    let mut weather = if is_numeric(location) {
+        fetch_weather_zip(location, country, api_key, units).await?
+    } else {
+        fetch_weather_city(location, country, api_key, units).await?
+    };
+
+    // The chance of rain comes from the first forecast step; it's a
+    // nice-to-have, so a failure is ignored
+    if let Some(coord) = &weather.coord {
+        weather.pop = fetch_forecast(coord, api_key, units, 1)
+            .await
+            .ok()
+            .and_then(|steps| steps.first()?.pop);
+    }
+
+    Ok(weather)
+}
+
+/// Adds the data only `tab` shows to an already fetched location. The
+/// forecast and air quality are nice-to-haves, so their failures are ignored.
+async fn fetch_tab_data(weather: &mut WeatherResponse, tab: Tab, args: &Cli, api_key: &str) {
+    if tab_calls(tab, args) == 0 {
+        return;
+    }
+    let Some(coord) = &weather.coord else {
+        return;
+    };
+    match tab {
+        Tab::Forecast => {
+            weather.forecast = fetch_forecast(coord, api_key, &args.units, FORECAST_STEPS)
+                .await
+                .unwrap_or_default();
+            weather.pop = weather.forecast.first().and_then(|step| step.pop);
+        }
+        Tab::AirQuality => {
+            weather.air_quality = fetch_air_quality(coord, api_key).await.ok().flatten();
+        }
+        Tab::Current => {}
+    }
+}
+
+/// API calls `fetch_tab_data` makes for one location. One Call already
+/// includes the forecast.
+fn tab_calls(tab: Tab, args: &Cli) -> u32 {
+    match tab {
+        Tab::Current => 0,
+        Tab::Forecast if args.onecall => 0,
+        Tab::Forecast | Tab::AirQuality => 1,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// One Call 3.0: geocode the location, then fetch everything in one request
+////////////////////////////////////////////////////////////////////////////////
+
+async fn fetch_one_call(
+    location: &str,
+    country: &str,
+    api_key: &str,
+    units: &str,
+) -> Result<WeatherResponse> {
+    let place = fetch_geo_location(location, country, api_key).await?;
+    let url = format!(
+        "https://api.openweathermap.org/data/3.0/onecall?lat={}&lon={}&exclude=minutely&appid={}&units={}",
+        place.lat, place.lon, api_key, units
+    );
+
+    let client = Client::new();
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to send request to URL: {url}"))?
+        .error_for_status()
+        .context("Received an error status code from One Call 3.0 (it needs a One Call subscription; drop --onecall to use the free endpoints)")?
+        .json::<OneCallResponse>()
+        .await
+        .context("Failed to parse One Call JSON from OpenWeatherMap")?;
+
+    Ok(WeatherResponse::from_one_call(place, resp))
+}
+
+/// Looks up a city name or ZIP code with the geocoding API.
+async fn fetch_geo_location(location: &str, country: &str, api_key: &str) -> Result<GeoLocation> {
+    let client = Client::new();
+    if is_numeric(location) {
+        let url = format!(
+            "https://api.openweathermap.org/geo/1.0/zip?zip={location},{country}&appid={api_key}"
+        );
+        client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to URL: {url}"))?
+            .error_for_status()
+            .context("Received an error status code from OpenWeatherMap")?
+            .json::<GeoLocation>()
+            .await
+            .context("Failed to parse geocoding JSON from OpenWeatherMap")
+    } else {
+        let url = format!(
+            "https://api.openweathermap.org/geo/1.0/direct?q={location},{country}&limit=1&appid={api_key}"
+        );
+        client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to send request to URL: {url}"))?
+            .error_for_status()
+            .context("Received an error status code from OpenWeatherMap")?
+            .json::<Vec<GeoLocation>>()
+            .await
+            .context("Failed to parse geocoding JSON from OpenWeatherMap")?
+            .into_iter()
+            .next()
+            .with_context(|| format!("No location found for '{location}'"))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Fetch the next forecast steps and the current air quality
+////////////////////////////////////////////////////////////////////////////////
+
+/// Fetches the next `steps` 3-hour forecast steps.
+async fn fetch_forecast(
+    coord: &Coord,
+    api_key: &str,
+    units: &str,
+    steps: usize,
+) -> Result<Vec<ForecastEntry>> {
+    let url = format!(
+        "https://api.openweathermap.org/data/2.5/forecast?lat={}&lon={}&cnt={}&appid={}&units={}",
+        coord.lat, coord.lon, steps, api_key, units
+    );
+
+    let client = Client::new();
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to send request to URL: {url}"))?
+        .error_for_status()
+        .context("Received an error status code from OpenWeatherMap")?
+        .json::<ForecastResponse>()
+        .await
+        .context("Failed to parse forecast JSON from OpenWeatherMap")?;
+
+    Ok(resp.list)
+}
+
+async fn fetch_air_quality(coord: &Coord, api_key: &str) -> Result<Option<AirQuality>> {
+    let url = format!(
+        "https://api.openweathermap.org/data/2.5/air_pollution?lat={}&lon={}&appid={}",
+        coord.lat, coord.lon, api_key
+    );
+
+    let client = Client::new();
+    let resp = client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to send request to URL: {url}"))?
+        .error_for_status()
+        .context("Received an error status code from OpenWeatherMap")?
+        .json::<AirPollutionResponse>()
+        .await
+        .context("Failed to parse air pollution JSON from OpenWeatherMap")?;
+
+    Ok(resp.list.into_iter().next())
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Fetch weather by city
 ////////////////////////////////////////////////////////////////////////////////
@@ -401,17 +1207,385 @@ async fn fetch_weather_zip(
 }
 
 ////////////////////////////////////////////////////////////////////////////////
-// Draw the weather info TUI
+// Interactive weather view (tabs: Current / Forecast / Air Quality)
 ////////////////////////////////////////////////////////////////////////////////
 
-fn draw_weather_info(
+/// The panels of the weather view, in tab-bar order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Current,
+    Forecast,
+    AirQuality,
+}
+
+impl Tab {
+    const ALL: [Tab; 3] = [Tab::Current, Tab::Forecast, Tab::AirQuality];
+
+    /// The tab's label, used in panel titles.
+    fn name(self) -> &'static str {
+        match self {
+            Tab::Current => "Current",
+            Tab::Forecast => "Forecast",
+            Tab::AirQuality => "Air Quality",
+        }
+    }
+
+    /// The label in the tab bar, prefixed with the key that selects it.
+    fn title(self) -> String {
+        format!("{} {}", self.index() + 1, self.name())
+    }
+
+    fn index(self) -> usize {
+        Tab::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+
+    /// The tab `delta` steps away, wrapping around at either end.
+    fn step(self, delta: isize) -> Tab {
+        let count = Tab::ALL.len() as isize;
+        Tab::ALL[(self.index() as isize + delta).rem_euclid(count) as usize]
+    }
+}
+
+/// State of the weather view between key presses.
+struct WeatherView {
+    tab: Tab,
+    /// Tabs whose data has been fetched; the rest are fetched when first shown
+    loaded: Vec<Tab>,
+    /// Rate limit warning or refusal from the last fetch for a tab
+    notice: Option<String>,
+}
+
+/// Shows the fetched weather with a tab bar until Enter, `q`, or Esc.
+/// Left/Right (or Tab/Shift-Tab) move between tabs; `1`-`3` jump to one.
+async fn run_weather_view(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
-    weather: &WeatherResponse,
+    results: &mut [(String, Result<WeatherResponse>)],
+    temp_colors: Option<&str>,
+    args: &Cli,
+    api_key: &str,
 ) -> Result<()> {
+    let mut view = WeatherView {
+        tab: Tab::Current,
+        loaded: vec![Tab::Current],
+        notice: None,
+    };
+
+    loop {
+        terminal.draw(|frame| draw_weather_view(frame, results, &view, temp_colors))?;
+        if !view.loaded.contains(&view.tab) {
+            // The "Loading..." panels are on screen while this runs
+            view.notice = load_tab(view.tab, results, args, api_key).await;
+            view.loaded.push(view.tab);
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Right | KeyCode::Tab | KeyCode::Char('l') => view.tab = view.tab.step(1),
+            KeyCode::Left | KeyCode::BackTab | KeyCode::Char('h') => view.tab = view.tab.step(-1),
+            KeyCode::Char(c @ '1'..='3') => view.tab = Tab::ALL[c as usize - '1' as usize],
+            KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+/// Fetches the data for `tab` for every location that loaded. Returns the
+/// rate limit warning, or why the fetch was refused.
+async fn load_tab(
+    tab: Tab,
+    results: &mut [(String, Result<WeatherResponse>)],
+    args: &Cli,
+    api_key: &str,
+) -> Option<String> {
+    let mut locations: Vec<&mut WeatherResponse> = results
+        .iter_mut()
+        .filter_map(|(_, result)| result.as_mut().ok())
+        .collect();
+    let calls = locations.len() as u32 * tab_calls(tab, args);
+    if calls == 0 {
+        return None;
+    }
+    let warning = match reserve_api_calls(args, calls) {
+        Ok(warning) => warning,
+        Err(e) => return Some(format!("{e:#}")),
+    };
+    for weather in &mut locations {
+        fetch_tab_data(weather, tab, args, api_key).await;
+    }
+    warning
+}
+
+/// Draws the tab bar and, below it, one panel per location for the active tab.
+/// A failed fetch shows its error in place of the weather so the other
+/// location is still visible.
+fn draw_weather_view(
+    frame: &mut Frame,
+    results: &[(String, Result<WeatherResponse>)],
+    view: &WeatherView,
+    temp_colors: Option<&str>,
+) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(frame.area());
+
+    let tab_bar = match &view.notice {
+        Some(notice) => Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Weather ({notice})"))
+            .title_style(Style::default().fg(Color::Yellow)),
+        None => Block::default()
+            .borders(Borders::ALL)
+            .title("Weather (←/→ or 1-3: switch tab, Enter/q: exit)"),
+    };
+    let tabs = Tabs::new(Tab::ALL.iter().map(|t| t.title()))
+        .select(view.tab.index())
+        .block(tab_bar)
+        .highlight_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        );
+    frame.render_widget(tabs, rows[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Ratio(1, results.len() as u32);
+            results.len()
+        ])
+        .split(rows[1]);
+
+    for ((location, result), area) in results.iter().zip(columns.iter()) {
+        let (title, lines) = match result {
+            Ok(weather) => (
+                format!("{}: {}", view.tab.name(), weather.location()),
+                match view.tab {
+                    _ if !view.loaded.contains(&view.tab) => vec![Line::from("Loading...")],
+                    Tab::Current => weather_lines(weather, temp_colors),
+                    Tab::Forecast => forecast_lines(weather, temp_colors),
+                    Tab::AirQuality => air_quality_lines(weather),
+                },
+            ),
+            Err(e) => (
+                format!("Weather: {location}"),
+                vec![
+                    Line::from(Span::styled(
+                        format!("Could not fetch weather for '{location}'"),
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    )),
+                    Line::from(Span::styled(
+                        format!("{e:#}"),
+                        Style::default().fg(Color::Red),
+                    )),
+                ],
+            ),
+        };
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let paragraph = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(paragraph, *area);
+    }
+}
+
+/// One line per forecast step: local time, temperature, conditions, chance of
+/// rain. One Call's daily forecasts follow, one line per day.
+fn forecast_lines(weather: &WeatherResponse, temp_colors: Option<&str>) -> Vec<Line<'static>> {
+    if weather.forecast.is_empty() && weather.daily.is_empty() {
+        return vec![Line::from(Span::styled(
+            "No forecast available for this location.",
+            Style::default().fg(Color::DarkGray),
+        ))];
+    }
+
+    let mut lines = Vec::new();
+    if !weather.forecast.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Next {} hours in {} (local time)",
+                weather.forecast.len() * 3,
+                weather.location()
+            ),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    for step in &weather.forecast {
+        let when = Local
+            .timestamp_opt(step.dt as i64, 0)
+            .single()
+            .map(|dt| dt.format("%a %H:%M").to_string())
+            .unwrap_or_else(|| "?".to_string());
+        let conditions = step
+            .weather
+            .first()
+            .map(|w| w.description.clone())
+            .unwrap_or_else(|| NOT_AVAILABLE.to_string());
+        let temperature = match step.main.temp {
+            Some(temp) => Span::styled(
+                format!("{temp:>7.1}°  "),
+                temperature_style(temp, temp_colors),
+            ),
+            None => Span::styled(
+                format!("{NOT_AVAILABLE:>8}  "),
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        let mut spans = vec![
+            Span::styled(format!("{when:<10}"), Style::default().fg(Color::Magenta)),
+            temperature,
+            Span::styled(
+                format!("{conditions:<22}"),
+                Style::default().fg(Color::Yellow),
+            ),
+        ];
+        if let Some(pop) = step.pop {
+            spans.push(Span::styled(
+                format!("rain {:.0}%", pop * 100.0),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if !weather.daily.is_empty() {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            format!("Next {} days", weather.daily.len()),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    for day in &weather.daily {
+        let when = Local
+            .timestamp_opt(day.dt as i64, 0)
+            .single()
+            .map(|dt| dt.format("%a %d %b").to_string())
+            .unwrap_or_else(|| "?".to_string());
+        let mut spans = vec![Span::styled(
+            format!("{when:<12}"),
+            Style::default().fg(Color::Magenta),
+        )];
+        let temps = day.temp.as_ref();
+        for (label, value) in [
+            ("low", temps.and_then(|t| t.min)),
+            ("high", temps.and_then(|t| t.max)),
+        ] {
+            spans.push(match value {
+                Some(temp) => Span::styled(
+                    format!("{label} {temp:>5.1}°  "),
+                    temperature_style(temp, temp_colors),
+                ),
+                None => Span::styled(
+                    format!("{label} {NOT_AVAILABLE:>6}  "),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            });
+        }
+        let conditions = day
+            .weather
+            .first()
+            .map(|w| w.description.clone())
+            .unwrap_or_else(|| NOT_AVAILABLE.to_string());
+        spans.push(Span::styled(
+            format!("{conditions:<22}"),
+            Style::default().fg(Color::Yellow),
+        ));
+        if let Some(pop) = day.pop {
+            spans.push(Span::styled(
+                format!("rain {:.0}%", pop * 100.0),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Pollutants shown on the Air Quality tab: (API key, label), most relevant first.
+const AIR_POLLUTANTS: [(&str, &str); 8] = [
+    ("pm2_5", "PM2.5"),
+    ("pm10", "PM10"),
+    ("o3", "Ozone (O3)"),
+    ("no2", "Nitrogen dioxide (NO2)"),
+    ("so2", "Sulphur dioxide (SO2)"),
+    ("co", "Carbon monoxide (CO)"),
+    ("no", "Nitrogen monoxide (NO)"),
+    ("nh3", "Ammonia (NH3)"),
+];
+
+/// The air quality index with its label, then each pollutant's concentration.
+fn air_quality_lines(weather: &WeatherResponse) -> Vec<Line<'static>> {
+    let Some(air) = &weather.air_quality else {
+        return vec![Line::from(Span::styled(
+            "No air quality data available for this location.",
+            Style::default().fg(Color::DarkGray),
+        ))];
+    };
+
+    let (label, color) = match air.main.aqi {
+        1 => ("Good", Color::Green),
+        2 => ("Fair", Color::LightGreen),
+        3 => ("Moderate", Color::Yellow),
+        4 => ("Poor", Color::LightRed),
+        _ => ("Very Poor", Color::Red),
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("Air quality in {}", weather.location()),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::raw("Air quality index: "),
+            Span::styled(
+                format!("{} ({label})", air.main.aqi),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "  (1 = good, 5 = very poor)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    for (key, name) in AIR_POLLUTANTS {
+        if let Some(value) = air.components.get(key) {
+            lines.push(Line::from(Span::styled(
+                format!("{name:<24}{value:>9.2} μg/m³"),
+                Style::default().fg(Color::Blue),
+            )));
+        }
+    }
+    lines
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Build the weather detail lines shared by the single and comparison views
+////////////////////////////////////////////////////////////////////////////////
+
+/// `temp_colors` is the `--units` value used to color temperatures on a
+/// gradient; `None` keeps them blue.
+fn weather_lines(weather: &WeatherResponse, temp_colors: Option<&str>) -> Vec<Line<'static>> {
+    let temp_style = |value: f64| temperature_style(value, temp_colors);
+
     // Build lines for the TUI
     let heading = format!(
         "Current weather in {}{}",
-        weather.name,
+        weather.location(),
         weather
             .sys
             .as_ref()
@@ -428,39 +1602,78 @@ fn draw_weather_info(
             .add_modifier(Modifier::BOLD),
     )));
 
-    // Weather condition
-    if let Some(desc) = weather.weather.first() {
-        let cond_str = format!("Condition: {} ({})", desc.main, desc.description);
+    // Alerts (One Call only) come first so they can't be missed
+    for alert in &weather.alerts {
+        let sender = if alert.sender_name.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", alert.sender_name)
+        };
         lines.push(Line::from(Span::styled(
-            cond_str,
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
+            format!(
+                "ALERT: {}{} from {} until {} UTC",
+                alert.event,
+                sender,
+                format_timestamp(alert.start),
+                format_timestamp(alert.end)
+            ),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
         )));
+        let description = alert
+            .description
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !description.is_empty() {
+            lines.push(Line::from(Span::styled(
+                description,
+                Style::default().fg(Color::Red),
+            )));
+        }
     }
 
-    // Temperature data
+    // Weather condition
+    let cond_str = match weather.weather.first() {
+        Some(desc) => format!("Condition: {} ({})", desc.main, desc.description),
+        None => format!("Condition: {NOT_AVAILABLE}"),
+    };
     lines.push(Line::from(Span::styled(
-        format!("Temperature: {:.1}°", weather.main.temp),
-        Style::default().fg(Color::Blue),
+        cond_str,
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
     )));
 
+    // Temperature data
+    lines.push(match weather.main.temp {
+        Some(temp) => Line::from(Span::styled(
+            format!("Temperature: {:.1}°", temp),
+            temp_style(temp).add_modifier(Modifier::BOLD),
+        )),
+        None => Line::from(Span::styled(
+            format!("Temperature: {NOT_AVAILABLE}"),
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+        )),
+    });
+
     if let Some(fl) = weather.main.feels_like {
         lines.push(Line::from(Span::styled(
             format!("Feels like: {:.1}°", fl),
-            Style::default().fg(Color::Blue),
+            temp_style(fl),
         )));
     }
     if let Some(min) = weather.main.temp_min {
         lines.push(Line::from(Span::styled(
             format!("Min temp: {:.1}°", min),
-            Style::default().fg(Color::Blue),
+            temp_style(min),
         )));
     }
     if let Some(max) = weather.main.temp_max {
         lines.push(Line::from(Span::styled(
             format!("Max temp: {:.1}°", max),
-            Style::default().fg(Color::Blue),
+            temp_style(max),
         )));
     }
     if let Some(p) = weather.main.pressure {
@@ -471,14 +1684,20 @@ fn draw_weather_info(
     }
 
     lines.push(Line::from(Span::styled(
-        format!("Humidity: {}%", weather.main.humidity),
+        match weather.main.humidity {
+            Some(humidity) => format!("Humidity: {humidity}%"),
+            None => format!("Humidity: {NOT_AVAILABLE}"),
+        },
         Style::default().fg(Color::Blue),
     )));
 
     // Wind data
     if let Some(wind) = &weather.wind {
         lines.push(Line::from(Span::styled(
-            format!("Wind speed: {:.1} mph", wind.speed),
+            match wind.speed {
+                Some(speed) => format!("Wind speed: {speed:.1} mph"),
+                None => format!("Wind speed: {NOT_AVAILABLE}"),
+            },
             Style::default().fg(Color::Blue),
         )));
         if let Some(g) = wind.gust {
@@ -489,11 +1708,31 @@ fn draw_weather_info(
         }
         if let Some(deg) = wind.deg {
             lines.push(Line::from(Span::styled(
-                format!("Wind direction: {}°", deg),
+                format!("Wind direction: {}° (from {})", deg, compass_label(deg)),
                 Style::default().fg(Color::Blue),
             )));
+            lines.extend(compass_rose(deg));
+        }
+    }
+
+    // Precipitation (omitted entirely when the API reports none)
+    for (label, data) in [("Rain", &weather.rain), ("Snow", &weather.snow)] {
+        let Some(data) = data else { continue };
+        for (period, amount) in [("1h", data.one_hour), ("3h", data.three_hours)] {
+            if let Some(mm) = amount {
+                lines.push(Line::from(Span::styled(
+                    format!("{label} ({period}): {mm:.1}mm"),
+                    Style::default().fg(Color::Cyan),
+                )));
+            }
         }
     }
+    if let Some(pop) = weather.pop {
+        lines.push(Line::from(Span::styled(
+            format!("Chance of rain: {:.0}%", pop * 100.0),
+            Style::default().fg(Color::Cyan),
+        )));
+    }
 
     // Coordinates
     if let Some(coord) = &weather.coord {
@@ -522,16 +1761,115 @@ fn draw_weather_info(
     // A blank line for spacing
     lines.push(Line::from(""));
 
-    terminal.draw(|frame| {
-        let screen = frame.area();
-        let block = Block::default().borders(Borders::ALL).title("Weather");
-        let paragraph = Paragraph::new(lines)
-            .block(block)
-            .alignment(Alignment::Left);
-        frame.render_widget(paragraph, screen);
-    })?;
+    lines
+}
 
-    Ok(())
+////////////////////////////////////////////////////////////////////////////////
+// Wind direction: compass label + ASCII rose
+////////////////////////////////////////////////////////////////////////////////
+
+const COMPASS_POINTS: [&str; 16] = [
+    "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW", "NW",
+    "NNW",
+];
+
+/// 16-point compass label for a bearing in degrees, e.g. 247 -> "WSW".
+fn compass_label(deg: f64) -> &'static str {
+    let index = (deg.rem_euclid(360.0) / 22.5).round() as usize % 16;
+    COMPASS_POINTS[index]
+}
+
+/// A 3-line ASCII compass rose showing only the spoke the wind blows *toward*
+/// (`deg` is where it comes from, as reported by the API), so the arrow
+/// still reads correctly in plain output.
+fn compass_rose(deg: f64) -> Vec<Line<'static>> {
+    // Spokes clockwise from N, with their (row, column) in the rose
+    const SPOKES: [(char, usize, usize); 8] = [
+        ('|', 0, 2),
+        ('/', 0, 4),
+        ('-', 1, 4),
+        ('\\', 2, 4),
+        ('|', 2, 2),
+        ('/', 2, 0),
+        ('-', 1, 0),
+        ('\\', 0, 0),
+    ];
+    let toward = ((deg + 180.0).rem_euclid(360.0) / 45.0).round() as usize % 8;
+
+    (0..3)
+        .map(|row| {
+            let mut spans = vec![Span::raw("    ")];
+            for col in 0..5 {
+                let spoke = SPOKES.iter().position(|&(_, r, c)| r == row && c == col);
+                let span = match spoke {
+                    Some(i) if i == toward => Span::styled(
+                        SPOKES[i].0.to_string(),
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Some(_) => Span::styled(".", Style::default().fg(Color::DarkGray)),
+                    None if row == 1 && col == 2 => {
+                        Span::styled("o", Style::default().fg(Color::Blue))
+                    }
+                    None => Span::raw(" "),
+                };
+                spans.push(span);
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Temperature colors
+////////////////////////////////////////////////////////////////////////////////
+
+/// Gradient stops in °C: blue around freezing, green when mild, then yellow
+/// into red when hot. Readings outside the range use the end colors.
+const TEMP_GRADIENT: [(f64, (u8, u8, u8)); 4] = [
+    (-5.0, (70, 130, 255)),
+    (15.0, (60, 200, 90)),
+    (25.0, (230, 200, 50)),
+    (35.0, (235, 60, 50)),
+];
+
+/// Converts a reading in the given `--units` to Celsius.
+fn to_celsius(value: f64, units: &str) -> f64 {
+    match units {
+        "imperial" => (value - 32.0) * 5.0 / 9.0,
+        "standard" => value - 273.15,
+        _ => value,
+    }
+}
+
+/// Gradient color for a temperature, or plain blue when `temp_colors` is `None`.
+fn temperature_style(value: f64, temp_colors: Option<&str>) -> Style {
+    match temp_colors {
+        Some(units) => Style::default().fg(temperature_color(value, units)),
+        None => Style::default().fg(Color::Blue),
+    }
+}
+
+/// Color for a temperature on a blue → green → red gradient.
+fn temperature_color(value: f64, units: &str) -> Color {
+    let celsius = to_celsius(value, units);
+    let (first, last) = (TEMP_GRADIENT[0], TEMP_GRADIENT[TEMP_GRADIENT.len() - 1]);
+    let (r, g, b) = if celsius <= first.0 {
+        first.1
+    } else if celsius >= last.0 {
+        last.1
+    } else {
+        let i = TEMP_GRADIENT
+            .windows(2)
+            .position(|w| celsius < w[1].0)
+            .unwrap_or(0);
+        let ((lo, c0), (hi, c1)) = (TEMP_GRADIENT[i], TEMP_GRADIENT[i + 1]);
+        let t = (celsius - lo) / (hi - lo);
+        let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        (mix(c0.0, c1.0), mix(c0.1, c1.1), mix(c0.2, c1.2))
+    };
+    Color::Rgb(r, g, b)
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -544,3 +1882,164 @@ fn format_timestamp(ts: u64) -> String {
         _ => "Invalid timestamp".to_string(),
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests: JSON models against recorded API responses in `fixtures/`
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse<T: serde::de::DeserializeOwned>(json: &str) -> T {
+        serde_json::from_str(json).expect("fixture should deserialize")
+    }
+
+    #[test]
+    fn city_response_deserializes_every_field() {
+        let weather: WeatherResponse = parse(include_str!("../fixtures/weather_city.json"));
+
+        assert_eq!(weather.name, "London");
+        let coord = weather.coord.expect("coord");
+        assert_eq!((coord.lat, coord.lon), (51.5085, -0.1257));
+        assert_eq!(weather.weather[0].main, "Rain");
+        assert_eq!(weather.weather[0].description, "moderate rain");
+
+        assert_eq!(weather.main.temp, Some(11.62));
+        assert_eq!(weather.main.feels_like, Some(10.98));
+        assert_eq!(weather.main.temp_min, Some(10.35));
+        assert_eq!(weather.main.temp_max, Some(12.71));
+        assert_eq!(weather.main.pressure, Some(1004.0));
+        assert_eq!(weather.main.humidity, Some(87.0));
+
+        let wind = weather.wind.expect("wind");
+        assert_eq!(
+            (wind.speed, wind.deg, wind.gust),
+            (Some(6.17), Some(230.0), Some(11.32))
+        );
+        let rain = weather.rain.expect("rain");
+        assert_eq!((rain.one_hour, rain.three_hours), (Some(1.84), None));
+        assert!(weather.snow.is_none());
+
+        let sys = weather.sys.expect("sys");
+        assert_eq!(sys.country.as_deref(), Some("GB"));
+        assert_eq!(
+            (sys.sunrise, sys.sunset),
+            (Some(1729146652), Some(1729184541))
+        );
+
+        // Filled in from the other endpoints, never from this response
+        assert!(weather.pop.is_none());
+        assert!(weather.forecast.is_empty());
+        assert!(weather.air_quality.is_none());
+    }
+
+    #[test]
+    fn zip_response_without_gust_or_precipitation() {
+        let weather: WeatherResponse = parse(include_str!("../fixtures/weather_zip.json"));
+
+        assert_eq!(weather.name, "New York");
+        assert_eq!(weather.main.temp, Some(58.3));
+        assert_eq!(weather.sys.and_then(|s| s.country).as_deref(), Some("US"));
+        let wind = weather.wind.expect("wind");
+        assert_eq!(wind.gust, None);
+        assert!(weather.rain.is_none());
+        assert!(weather.snow.is_none());
+    }
+
+    #[test]
+    fn minimal_response_leaves_optional_fields_empty() {
+        let weather: WeatherResponse = parse(include_str!("../fixtures/weather_minimal.json"));
+
+        assert_eq!(weather.name, "Tromsø");
+        assert_eq!(
+            (weather.main.temp, weather.main.humidity),
+            (Some(-3.4), Some(93.0))
+        );
+        assert!(weather.coord.is_none());
+        assert!(weather.wind.is_none());
+        assert!(weather.sys.is_none());
+        assert!(weather.main.feels_like.is_none());
+        assert!(weather.main.temp_min.is_none());
+        assert!(weather.main.temp_max.is_none());
+        assert!(weather.main.pressure.is_none());
+        let snow = weather.snow.expect("snow");
+        assert_eq!((snow.one_hour, snow.three_hours), (None, Some(0.6)));
+    }
+
+    fn rendered(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn sparse_response_renders_missing_values_as_na() {
+        let weather: WeatherResponse = parse(include_str!("../fixtures/weather_sparse.json"));
+
+        assert!(weather.weather.is_empty());
+        assert_eq!((weather.main.temp, weather.main.humidity), (None, None));
+        assert_eq!(weather.main.pressure, Some(1012.0));
+        assert_eq!(weather.location(), "N/A");
+
+        let text = rendered(&weather_lines(&weather, Some("metric")));
+        for expected in [
+            "Current weather in N/A",
+            "Condition: N/A",
+            "Temperature: N/A",
+            "Pressure: 1012 hPa",
+            "Humidity: N/A",
+            "Wind speed: N/A",
+            "Wind direction: 90° (from E)",
+        ] {
+            assert!(text.iter().any(|l| l == expected), "missing {expected:?}");
+        }
+
+        // An empty object is still a (very sparse) response
+        let empty: WeatherResponse = parse("{}");
+        assert!(rendered(&weather_lines(&empty, None)).contains(&"Temperature: N/A".to_string()));
+    }
+
+    #[test]
+    fn precipitation_keeps_api_keys_in_json_output() {
+        let weather: WeatherResponse = parse(include_str!("../fixtures/weather_city.json"));
+        let json = serde_json::to_value(&weather).expect("serialize");
+
+        assert_eq!(json["rain"]["1h"], 1.84);
+        assert_eq!(json["name"], "London");
+    }
+
+    #[test]
+    fn coordinate_forecast_response_deserializes() {
+        let forecast: ForecastResponse = parse(include_str!("../fixtures/forecast.json"));
+
+        assert_eq!(forecast.list.len(), 3);
+        let first = &forecast.list[0];
+        assert_eq!(
+            (first.dt, first.main.temp, first.pop),
+            (1729170000, Some(12.04), Some(0.84))
+        );
+        assert_eq!(first.weather[0].description, "light rain");
+        assert_eq!(forecast.list[1].pop, Some(0.0));
+        // A step may come without `weather` or `pop`
+        let last = &forecast.list[2];
+        assert!(last.weather.is_empty());
+        assert!(last.pop.is_none());
+    }
+
+    #[test]
+    fn coordinate_air_pollution_response_deserializes() {
+        let air: AirPollutionResponse = parse(include_str!("../fixtures/air_pollution.json"));
+
+        let quality = air.list.into_iter().next().expect("one entry");
+        assert_eq!(quality.main.aqi, 2);
+        assert_eq!(quality.components.len(), 8);
+        assert_eq!(quality.components.get("pm2_5"), Some(&6.38));
+        assert_eq!(quality.components.get("o3"), Some(&52.21));
+
+        let empty: AirPollutionResponse =
+            parse(include_str!("../fixtures/air_pollution_empty.json"));
+        assert!(empty.list.is_empty());
+    }
+}